@@ -0,0 +1,12 @@
+;; Returns the balance of the address given by the first 20 bytes of the input.
+(module
+	(import "env" "memory" (memory 1))
+	(import "env" "fetch_input" (func $fetch_input (param i32)))
+	(import "env" "balance" (func $balance (param i32 i32)))
+	(import "env" "ret" (func $ret (param i32 i32)))
+	(func (export "call")
+		(call $fetch_input (i32.const 0))
+		(call $balance (i32.const 0) (i32.const 32))
+		(call $ret (i32.const 32) (i32.const 32))
+	)
+)
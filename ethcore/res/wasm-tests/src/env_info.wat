@@ -0,0 +1,16 @@
+;; Returns sender, origin, address (20 bytes each) and value (32 bytes).
+(module
+	(import "env" "memory" (memory 1))
+	(import "env" "sender" (func $sender (param i32)))
+	(import "env" "origin" (func $origin (param i32)))
+	(import "env" "address" (func $address (param i32)))
+	(import "env" "value" (func $value (param i32)))
+	(import "env" "ret" (func $ret (param i32 i32)))
+	(func (export "call")
+		(call $sender (i32.const 0))
+		(call $origin (i32.const 20))
+		(call $address (i32.const 40))
+		(call $value (i32.const 60))
+		(call $ret (i32.const 0) (i32.const 92))
+	)
+)
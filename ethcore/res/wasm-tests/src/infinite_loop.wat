@@ -0,0 +1,7 @@
+;; Never terminates; execution must stop when gas runs out.
+(module
+	(import "env" "memory" (memory 1))
+	(func (export "call")
+		(loop (br 0))
+	)
+)
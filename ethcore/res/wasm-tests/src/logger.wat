@@ -0,0 +1,12 @@
+;; Logs the input: the first 32 bytes are the topic, the rest is the data.
+(module
+	(import "env" "memory" (memory 1))
+	(import "env" "input_length" (func $input_length (result i32)))
+	(import "env" "fetch_input" (func $fetch_input (param i32)))
+	(import "env" "elog" (func $elog (param i32 i32 i32 i32)))
+	(func (export "call") (local $len i32)
+		(set_local $len (call $input_length))
+		(call $fetch_input (i32.const 0))
+		(call $elog (i32.const 0) (i32.const 1) (i32.const 32) (i32.sub (get_local $len) (i32.const 32)))
+	)
+)
@@ -0,0 +1,12 @@
+;; Returns the storage value under the key given by the input.
+(module
+	(import "env" "memory" (memory 1))
+	(import "env" "fetch_input" (func $fetch_input (param i32)))
+	(import "env" "storage_read" (func $storage_read (param i32 i32)))
+	(import "env" "ret" (func $ret (param i32 i32)))
+	(func (export "call")
+		(call $fetch_input (i32.const 0))
+		(call $storage_read (i32.const 0) (i32.const 32))
+		(call $ret (i32.const 32) (i32.const 32))
+	)
+)
@@ -0,0 +1,10 @@
+;; Stores the second 32 bytes of the input under the key given by the first 32 bytes.
+(module
+	(import "env" "memory" (memory 1))
+	(import "env" "fetch_input" (func $fetch_input (param i32)))
+	(import "env" "storage_write" (func $storage_write (param i32 i32)))
+	(func (export "call")
+		(call $fetch_input (i32.const 0))
+		(call $storage_write (i32.const 0) (i32.const 32))
+	)
+)
@@ -20,6 +20,7 @@ use util::hash::{H256, FixedHash};
 use util::sha3::{Hashable, SHA3_EMPTY};
 use ethjson;
 use types::executed::CallType;
+use evm::CreateContractAddress;
 
 use std::sync::Arc;
 
@@ -68,7 +69,8 @@ pub struct ActionParams {
 	pub data: Option<Bytes>,
 	/// Type of call
 	pub call_type: CallType,
-
+	/// Scheme used to derive the address of a created contract.
+	pub address_scheme: CreateContractAddress,
 }
 
 impl Default for ActionParams {
@@ -86,6 +88,7 @@ impl Default for ActionParams {
 			code: None,
 			data: None,
 			call_type: CallType::None,
+			address_scheme: CreateContractAddress::FromSenderAndNonce,
 		}
 	}
 }
@@ -105,6 +108,7 @@ impl From<ethjson::vm::Transaction> for ActionParams {
 			gas_price: t.gas_price.into(),
 			value: ActionValue::Transfer(t.value.into()),
 			call_type: match address.is_zero() { true => CallType::None, false => CallType::Call },	// TODO @debris is this correct?
+			address_scheme: CreateContractAddress::FromSenderAndNonce,
 		}
 	}
 }
@@ -16,14 +16,14 @@
 use std::collections::{HashSet, HashMap, BTreeMap, VecDeque};
 use std::sync::{Arc, Weak};
 use std::path::{Path};
-use std::fmt;
+use std::{fmt, cmp};
 use std::sync::atomic::{AtomicUsize, AtomicBool, Ordering as AtomicOrdering};
 use std::time::{Instant};
 use time::precise_time_ns;
 
 // util
 use util::{Bytes, PerfTimer, Itertools, Mutex, RwLock, Hashable};
-use util::{journaldb, TrieFactory, Trie};
+use util::{journaldb, TrieFactory, Trie, TrieError};
 use util::trie::TrieSpec;
 use util::{U256, H256, Address, H2048, Uint, FixedHash};
 use util::kvdb::*;
@@ -51,16 +51,18 @@ use verification::queue::BlockQueue;
 use blockchain::{BlockChain, BlockProvider, TreeRoute, ImportRoute};
 use client::{
 	BlockId, TransactionId, UncleId, TraceId, ClientConfig, BlockChainClient,
-	MiningBlockChainClient, TraceFilter, CallAnalytics, BlockImportError, Mode,
-	ChainNotify, PruningInfo,
+	MiningBlockChainClient, TraceFilter, CallAnalytics, StateOverride, BlockImportError, Mode,
+	ChainNotify, PruningInfo, AccountProof,
 };
 use client::Error as ClientError;
 use env_info::EnvInfo;
-use executive::{Executive, Executed, TransactOptions, contract_address};
-use receipt::LocalizedReceipt;
+use executive::{self, Executive, Executed, TransactOptions, contract_address};
+use receipt::{Receipt, LocalizedReceipt};
 use trace::{TraceDB, ImportRequest as TraceImportRequest, LocalizedTrace, Database as TraceDatabase};
 use trace;
-use trace::FlatTransactionTraces;
+use trace::Error as TraceError;
+use trace::{FlatTransactionTraces, NoopTracer, StructLogTracer, StructLogOptions, StructLogTrace};
+use trace::{CombinedTracer, ExecutiveVMTracer, NoopVMTracer};
 use evm::{Factory as EvmFactory, Schedule};
 use miner::{Miner, MinerService};
 use snapshot::{self, io as snapshot_io};
@@ -640,6 +642,184 @@ impl Client {
 		.expect("State root of best block header always valid.")
 	}
 
+	// state and environment for a non-persistent call on top of given block.
+	fn call_state(&self, block: BlockId) -> Result<(State, EnvInfo), CallError> {
+		let header = try!(self.block_header(block).ok_or(CallError::StatePruned));
+		let view = HeaderView::new(&header);
+		let last_hashes = self.build_last_hashes(view.parent_hash());
+		let env_info = EnvInfo {
+			number: view.number(),
+			author: view.author(),
+			timestamp: view.timestamp(),
+			difficulty: view.difficulty(),
+			last_hashes: last_hashes,
+			gas_used: U256::zero(),
+			gas_limit: U256::max_value(),
+		};
+		// that's just a copy of the state.
+		let state = try!(self.state_at(block).ok_or(CallError::StatePruned));
+		Ok((state, env_info))
+	}
+
+	// give the sender of a call a sufficient balance to pay for it.
+	fn fund_sender(state: &mut State, t: &SignedTransaction) -> Result<(), CallError> {
+		let sender = try!(t.sender().map_err(|e| {
+			let message = format!("Transaction malformed: {:?}", e);
+			ExecutionError::TransactionMalformed(message)
+		}));
+		let balance = state.balance(&sender);
+		let needed_balance = t.value + t.gas * t.gas_price;
+		if balance < needed_balance {
+			state.add_balance(&sender, &(needed_balance - balance), CleanupMode::NoEmpty);
+		}
+		Ok(())
+	}
+
+	// environment for re-executing transactions of the block with given header.
+	fn block_env_info(&self, view: &HeaderView) -> EnvInfo {
+		EnvInfo {
+			number: view.number(),
+			author: view.author(),
+			timestamp: view.timestamp(),
+			difficulty: view.difficulty(),
+			last_hashes: self.build_last_hashes(view.hash()),
+			gas_used: U256::default(),
+			gas_limit: view.gas_limit(),
+		}
+	}
+
+	// state and environment right before given transaction, with all preceding transactions of its block applied.
+	fn replay_state(&self, id: TransactionId) -> Result<(State, EnvInfo, SignedTransaction), CallError> {
+		let address = try!(self.transaction_address(id).ok_or(CallError::TransactionNotFound));
+		let header_data = try!(self.block_header(BlockId::Hash(address.block_hash)).ok_or(CallError::StatePruned));
+		let body_data = try!(self.block_body(BlockId::Hash(address.block_hash)).ok_or(CallError::StatePruned));
+		let mut state = try!(self.state_at_beginning(BlockId::Hash(address.block_hash)).ok_or(CallError::StatePruned));
+		let mut txs = BodyView::new(&body_data).transactions();
+
+		if address.index >= txs.len() {
+			return Err(CallError::TransactionNotFound);
+		}
+
+		let mut env_info = self.block_env_info(&HeaderView::new(&header_data));
+		for t in txs.iter().take(address.index) {
+			match Executive::new(&mut state, &env_info, &*self.engine, &self.factories.vm).transact(t, Default::default()) {
+				Ok(x) => { env_info.gas_used = env_info.gas_used + x.gas_used; }
+				Err(ee) => { return Err(CallError::Execution(ee)) }
+			}
+			// commit like block import does, so that later transactions see the right original storage values.
+			try!(state.commit().map_err(|_| CallError::StatePruned));
+		}
+
+		Ok((state, env_info, txs.swap_remove(address.index)))
+	}
+
+	// execute a transaction on top of given state, running the requested analytics.
+	fn transact_with_analytics(&self, state: &mut State, env_info: &EnvInfo, t: &SignedTransaction, analytics: CallAnalytics, fund_sender: bool) -> Result<Executed, CallError> {
+		let original_state = if analytics.state_diffing { Some(state.clone()) } else { None };
+		if fund_sender {
+			try!(Self::fund_sender(state, t));
+		}
+
+		let mut ret = if analytics.call_tracing {
+			let mut executive = Executive::new(state, env_info, &*self.engine, &self.factories.vm);
+			let (mut executed, traces, vm_trace) = if analytics.vm_tracing {
+				try!(executive.transact_with_tracers(t, false, CombinedTracer::default(), ExecutiveVMTracer::toplevel()))
+			} else {
+				try!(executive.transact_with_tracers(t, false, CombinedTracer::default(), NoopVMTracer))
+			};
+			let (flat_trace, call_trace) = CombinedTracer::split(traces);
+			if analytics.transaction_tracing {
+				executed.trace = flat_trace;
+			}
+			executed.call_trace = call_trace.into_iter().next();
+			executed.vm_trace = vm_trace;
+			executed
+		} else {
+			let options = TransactOptions { tracing: analytics.transaction_tracing, vm_tracing: analytics.vm_tracing, check_nonce: false };
+			try!(Executive::new(state, env_info, &*self.engine, &self.factories.vm).transact(t, options))
+		};
+
+		// TODO gav move this into Executive.
+		ret.state_diff = original_state.map(|original| state.diff_from(original));
+
+		Ok(ret)
+	}
+
+	fn transact_struct_logs(&self, state: &mut State, env_info: &EnvInfo, t: &SignedTransaction, options: StructLogOptions) -> Result<StructLogTrace, CallError> {
+		let (executed, _, struct_logs) = try!(Executive::new(state, env_info, &*self.engine, &self.factories.vm)
+			.transact_with_tracers(t, false, NoopTracer, StructLogTracer::toplevel(options)));
+
+		Ok(StructLogTrace {
+			gas_used: executed.gas_used,
+			failed: executed.reverted || executed.exception.is_some(),
+			output: executed.output,
+			struct_logs: struct_logs.unwrap_or_default(),
+		})
+	}
+
+	/// Re-execute canonical blocks `from` to `to` (inclusive) and write their traces into the trace database.
+	///
+	/// Progress is stored after every block, so that an interrupted rebuild of the same range
+	/// continues where it stopped. Requires the state of all parent blocks to be available.
+	pub fn rebuild_traces(&self, from: BlockNumber, to: BlockNumber) -> Result<(), EthcoreError> {
+		if !self.tracedb.read().tracing_enabled() {
+			return Err(ClientError::from(TraceError::TracingDisabled).into());
+		}
+
+		let from = match self.tracedb.read().rebuild_progress() {
+			Some(done) if done >= from && done < to => {
+				info!(target: "client", "Resuming traces rebuild after block #{}", done);
+				done + 1
+			},
+			_ => from,
+		};
+
+		// genesis has no transactions to trace.
+		for number in cmp::max(from, 1)..(to + 1) {
+			let unavailable = || EthcoreError::from(ClientError::from(TraceError::BlockUnavailable(number)));
+			let (hash, block_bytes, parent) = {
+				let chain = self.chain.read();
+				let hash = try!(chain.block_hash(number).ok_or_else(&unavailable));
+				let block_bytes = try!(chain.block(&hash).ok_or_else(&unavailable));
+				let parent = try!(chain.block_header(&BlockView::new(&block_bytes).header_view().parent_hash()).ok_or_else(&unavailable));
+				(hash, block_bytes, parent)
+			};
+
+			if self.state_at(BlockId::Number(number - 1)).is_none() {
+				return Err(unavailable());
+			}
+
+			let view = BlockView::new(&block_bytes);
+			let header = view.header();
+			let last_hashes = self.build_last_hashes(header.parent_hash().clone());
+			let db = self.state_db.lock().boxed_clone_canon(header.parent_hash());
+			let block = try!(enact(&header, &view.transactions(), &view.uncles(), &*self.engine, true, db, &parent, last_hashes, self.factories.clone()));
+
+			let traces: Vec<FlatTransactionTraces> = block.traces().clone().unwrap_or_else(Vec::new)
+				.into_iter()
+				.map(Into::into)
+				.collect();
+
+			let mut batch = DBTransaction::new(&self.db.read());
+			let tracedb = self.tracedb.read();
+			tracedb.import(&mut batch, TraceImportRequest {
+				traces: traces.into(),
+				block_hash: hash.clone(),
+				block_number: number,
+				enacted: vec![hash],
+				retracted: 0,
+			});
+			tracedb.set_rebuild_progress(&mut batch, if number == to { None } else { Some(number) });
+			try!(self.db.read().write(batch).map_err(ClientError::Database));
+
+			if number % 1000 == 0 {
+				info!(target: "client", "Rebuilt traces up to block #{}", number);
+			}
+		}
+
+		Ok(())
+	}
+
 	/// Get info on the cache.
 	pub fn blockchain_cache_info(&self) -> BlockChainCacheSize {
 		self.chain.read().cache_size()
@@ -809,78 +989,66 @@ impl snapshot::DatabaseRestore for Client {
 
 
 impl BlockChainClient for Client {
-	fn call(&self, t: &SignedTransaction, block: BlockId, analytics: CallAnalytics) -> Result<Executed, CallError> {
+	fn call(&self, t: &SignedTransaction, block: BlockId, analytics: CallAnalytics, state_override: &StateOverride) -> Result<Executed, CallError> {
+		let (mut state, env_info) = try!(self.call_state(block));
+		state.apply_override(state_override);
+		self.transact_with_analytics(&mut state, &env_info, t, analytics, true)
+	}
+
+	fn estimate_gas(&self, t: &SignedTransaction, block: BlockId, state_override: &StateOverride) -> Result<U256, CallError> {
 		let header = try!(self.block_header(block).ok_or(CallError::StatePruned));
-		let view = HeaderView::new(&header);
-		let last_hashes = self.build_last_hashes(view.parent_hash());
-		let env_info = EnvInfo {
-			number: view.number(),
-			author: view.author(),
-			timestamp: view.timestamp(),
-			difficulty: view.difficulty(),
-			last_hashes: last_hashes,
-			gas_used: U256::zero(),
-			gas_limit: U256::max_value(),
-		};
-		// that's just a copy of the state.
-		let mut state = try!(self.state_at(block).ok_or(CallError::StatePruned));
-		let original_state = if analytics.state_diffing { Some(state.clone()) } else { None };
+		let cap = HeaderView::new(&header).gas_limit();
+		let (mut state, env_info) = try!(self.call_state(block));
+		state.apply_override(state_override);
+		executive::estimate_gas(state, &env_info, &*self.engine, &self.factories.vm, t, cap)
+	}
 
-		let sender = try!(t.sender().map_err(|e| {
-			let message = format!("Transaction malformed: {:?}", e);
-			ExecutionError::TransactionMalformed(message)
-		}));
-		let balance = state.balance(&sender);
-		let needed_balance = t.value + t.gas * t.gas_price;
-		if balance < needed_balance {
-			// give the sender a sufficient balance
-			state.add_balance(&sender, &(needed_balance - balance), CleanupMode::NoEmpty);
-		}
-		let options = TransactOptions { tracing: analytics.transaction_tracing, vm_tracing: analytics.vm_tracing, check_nonce: false };
-		let mut ret = try!(Executive::new(&mut state, &env_info, &*self.engine, &self.factories.vm).transact(t, options));
+	fn call_many(&self, transactions: Vec<SignedTransaction>, analytics: Vec<CallAnalytics>, block: BlockId) -> Result<Vec<Executed>, CallError> {
+		let (mut state, mut env_info) = try!(self.call_state(block));
+		let mut results = Vec::with_capacity(transactions.len());
 
-		// TODO gav move this into Executive.
-		ret.state_diff = original_state.map(|original| state.diff_from(original));
+		for (t, analytics) in transactions.iter().zip(analytics.into_iter().chain(::std::iter::repeat(CallAnalytics::default()))) {
+			let ret = try!(self.transact_with_analytics(&mut state, &env_info, t, analytics, true));
+			try!(state.commit().map_err(|_| CallError::StatePruned));
+			env_info.gas_used = env_info.gas_used + ret.gas_used;
+			results.push(ret);
+		}
 
-		Ok(ret)
+		Ok(results)
 	}
 
 	fn replay(&self, id: TransactionId, analytics: CallAnalytics) -> Result<Executed, CallError> {
-		let address = try!(self.transaction_address(id).ok_or(CallError::TransactionNotFound));
-		let header_data = try!(self.block_header(BlockId::Hash(address.block_hash)).ok_or(CallError::StatePruned));
-		let body_data = try!(self.block_body(BlockId::Hash(address.block_hash)).ok_or(CallError::StatePruned));
-		let mut state = try!(self.state_at_beginning(BlockId::Hash(address.block_hash)).ok_or(CallError::StatePruned));
+		let (mut state, env_info, t) = try!(self.replay_state(id));
+		self.transact_with_analytics(&mut state, &env_info, &t, analytics, false)
+	}
+
+	fn replay_block_transactions(&self, block: BlockId, analytics: CallAnalytics) -> Result<Vec<Executed>, CallError> {
+		let header_data = try!(self.block_header(block).ok_or(CallError::StatePruned));
+		let body_data = try!(self.block_body(block).ok_or(CallError::StatePruned));
+		let mut state = try!(self.state_at_beginning(block).ok_or(CallError::StatePruned));
 		let txs = BodyView::new(&body_data).transactions();
 
-		if address.index >= txs.len() {
-			return Err(CallError::TransactionNotFound);
+		let mut env_info = self.block_env_info(&HeaderView::new(&header_data));
+		let mut results = Vec::with_capacity(txs.len());
+		for t in &txs {
+			let ret = try!(self.transact_with_analytics(&mut state, &env_info, t, analytics, false));
+			try!(state.commit().map_err(|_| CallError::StatePruned));
+			env_info.gas_used = env_info.gas_used + ret.gas_used;
+			results.push(ret);
 		}
 
-		let options = TransactOptions { tracing: analytics.transaction_tracing, vm_tracing: analytics.vm_tracing, check_nonce: false };
-		let view = HeaderView::new(&header_data);
-		let last_hashes = self.build_last_hashes(view.hash());
-		let mut env_info = EnvInfo {
-			number: view.number(),
-			author: view.author(),
-			timestamp: view.timestamp(),
-			difficulty: view.difficulty(),
-			last_hashes: last_hashes,
-			gas_used: U256::default(),
-			gas_limit: view.gas_limit(),
-		};
-		for t in txs.iter().take(address.index) {
-			match Executive::new(&mut state, &env_info, &*self.engine, &self.factories.vm).transact(t, Default::default()) {
-				Ok(x) => { env_info.gas_used = env_info.gas_used + x.gas_used; }
-				Err(ee) => { return Err(CallError::Execution(ee)) }
-			}
-		}
-		let t = &txs[address.index];
+		Ok(results)
+	}
 
-		let original_state = if analytics.state_diffing { Some(state.clone()) } else { None };
-		let mut ret = try!(Executive::new(&mut state, &env_info, &*self.engine, &self.factories.vm).transact(t, options));
-		ret.state_diff = original_state.map(|original| state.diff_from(original));
+	fn call_struct_logs(&self, t: &SignedTransaction, block: BlockId, options: StructLogOptions) -> Result<StructLogTrace, CallError> {
+		let (mut state, env_info) = try!(self.call_state(block));
+		try!(Self::fund_sender(&mut state, t));
+		self.transact_struct_logs(&mut state, &env_info, t, options)
+	}
 
-		Ok(ret)
+	fn replay_struct_logs(&self, id: TransactionId, options: StructLogOptions) -> Result<StructLogTrace, CallError> {
+		let (mut state, env_info, t) = try!(self.replay_state(id));
+		self.transact_struct_logs(&mut state, &env_info, &t, options)
 	}
 
 	fn keep_alive(&self) {
@@ -1092,14 +1260,8 @@ impl BlockChainClient for Client {
 					BodyView::new(&block).localized_transaction_at(&address.block_hash, block_number, address.index)
 				});
 
-			let tx_and_sender = t.and_then(|tx| tx.sender().ok().map(|sender| (tx, sender)));
-
-			match (tx_and_sender, chain.transaction_receipt(&address)) {
-				(Some((tx, sender)), Some(receipt)) => {
-					let block_hash = tx.block_hash.clone();
-					let block_number = tx.block_number.clone();
-					let transaction_hash = tx.hash();
-					let transaction_index = tx.transaction_index;
+			match (t, chain.transaction_receipt(&address)) {
+				(Some(tx), Some(receipt)) => {
 					let prior_gas_used = match tx.transaction_index {
 						0 => U256::zero(),
 						i => {
@@ -1108,34 +1270,29 @@ impl BlockChainClient for Client {
 							prior_receipt.gas_used
 						}
 					};
-					Some(LocalizedReceipt {
-						transaction_hash: tx.hash(),
-						transaction_index: tx.transaction_index,
-						block_hash: tx.block_hash,
-						block_number: tx.block_number,
-						cumulative_gas_used: receipt.gas_used,
-						gas_used: receipt.gas_used - prior_gas_used,
-						contract_address: match tx.action {
-							Action::Call(_) => None,
-							Action::Create => Some(contract_address(&sender, &tx.nonce))
-						},
-						logs: receipt.logs.into_iter().enumerate().map(|(i, log)| LocalizedLogEntry {
-							entry: log,
-							block_hash: block_hash.clone(),
-							block_number: block_number,
-							transaction_hash: transaction_hash.clone(),
-							transaction_index: transaction_index,
-							log_index: i
-						}).collect(),
-						log_bloom: receipt.log_bloom,
-						state_root: receipt.state_root,
-					})
+					Some(localize_receipt(tx, receipt, prior_gas_used))
 				},
 				_ => None
 			}
 		}))
 	}
 
+	fn localized_block_receipts(&self, id: BlockId) -> Option<Vec<LocalizedReceipt>> {
+		let chain = self.chain.read();
+		let hash = match Self::block_hash(&chain, id) {
+			Some(hash) => hash,
+			None => return None,
+		};
+
+		match (chain.block_number(&hash), chain.block_body(&hash), chain.block_receipts(&hash)) {
+			(Some(number), Some(body), Some(receipts)) => {
+				let transactions = BodyView::new(&body).localized_transactions(&hash, number);
+				Some(localize_block_receipts(transactions, receipts.receipts))
+			},
+			_ => None,
+		}
+	}
+
 	fn tree_route(&self, from: &H256, to: &H256) -> Option<TreeRoute> {
 		let chain = self.chain.read();
 		match chain.is_known(from) && chain.is_known(to) {
@@ -1233,6 +1390,8 @@ impl BlockChainClient for Client {
 					range: s as usize..e as usize,
 					from_address: From::from(filter.from_address),
 					to_address: From::from(filter.to_address),
+					after: filter.after,
+					count: filter.count,
 				};
 
 				let traces = self.tracedb.read().filter(&filter);
@@ -1408,6 +1567,10 @@ impl ::client::ProvingBlockChainClient for Client {
 			.unwrap_or_else(Vec::new)
 	}
 
+	fn prove_account_with_storage(&self, address: &Address, storage_keys: &[H256], id: BlockId) -> Option<Result<AccountProof, Box<TrieError>>> {
+		self.state_at(id).map(|state| state.prove_account_with_storage(address, storage_keys))
+	}
+
 	fn code_by_hash(&self, account_key: H256, id: BlockId) -> Bytes {
 		self.state_at(id)
 			.and_then(move |state| state.code_by_address_hash(account_key).ok())
@@ -1416,6 +1579,49 @@ impl ::client::ProvingBlockChainClient for Client {
 	}
 }
 
+/// Localizes the receipt of a transaction.
+/// `prior_gas_used` is the gas used by the transactions before it in the block.
+pub fn localize_receipt(tx: LocalizedTransaction, receipt: Receipt, prior_gas_used: U256) -> LocalizedReceipt {
+	let block_hash = tx.block_hash.clone();
+	let block_number = tx.block_number;
+	let transaction_hash = tx.hash();
+	let transaction_index = tx.transaction_index;
+
+	LocalizedReceipt {
+		transaction_hash: transaction_hash.clone(),
+		transaction_index: transaction_index,
+		block_hash: block_hash.clone(),
+		block_number: block_number,
+		cumulative_gas_used: receipt.gas_used,
+		gas_used: receipt.gas_used - prior_gas_used,
+		contract_address: match tx.action {
+			Action::Call(_) => None,
+			Action::Create => tx.sender().ok().map(|sender| contract_address(&sender, &tx.nonce)),
+		},
+		logs: receipt.logs.into_iter().enumerate().map(|(i, log)| LocalizedLogEntry {
+			entry: log,
+			block_hash: block_hash.clone(),
+			block_number: block_number,
+			transaction_hash: transaction_hash.clone(),
+			transaction_index: transaction_index,
+			log_index: i
+		}).collect(),
+		log_bloom: receipt.log_bloom,
+		state_root: receipt.state_root,
+	}
+}
+
+/// Localizes the receipts of all transactions in a block, given in the same order.
+pub fn localize_block_receipts(transactions: Vec<LocalizedTransaction>, receipts: Vec<Receipt>) -> Vec<LocalizedReceipt> {
+	let mut prior_gas_used = U256::zero();
+	transactions.into_iter().zip(receipts).map(|(tx, receipt)| {
+		let cumulative_gas_used = receipt.gas_used;
+		let receipt = localize_receipt(tx, receipt, prior_gas_used);
+		prior_gas_used = cumulative_gas_used;
+		receipt
+	}).collect()
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -0,0 +1,135 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Client used to run standalone state tests against the EVM.
+
+use std::fmt;
+use util::*;
+use rlp;
+use devtools::RandomTempPath;
+use action_params::ActionParams;
+use db::{NUM_COLUMNS, COL_STATE};
+use error::{Error, ExecutionError};
+use executive::{Executive, Executed, TransactOptions};
+use env_info::EnvInfo;
+use evm::{self, FinalizationResult};
+use factory::Factories;
+use log_entry::LogEntry;
+use pod_state::PodState;
+use spec::Spec;
+use state::{State, Substate};
+use state_db::StateDB;
+use trace::{NoopTracer, VMTracer};
+use transaction::SignedTransaction;
+
+/// Error which prevented a state test from running.
+#[derive(Debug)]
+pub enum EvmTestError {
+	/// The temporary state database could not be opened.
+	Database(String),
+	/// The state could not be committed.
+	State(Error),
+	/// The EVM failed to execute the code.
+	Evm(evm::Error),
+}
+
+impl fmt::Display for EvmTestError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			EvmTestError::Database(ref e) => write!(f, "Cannot open state database: {}", e),
+			EvmTestError::State(ref e) => write!(f, "Cannot commit state: {}", e),
+			EvmTestError::Evm(ref e) => write!(f, "Execution failed: {:?}", e),
+		}
+	}
+}
+
+/// Outcome of a transaction executed by `EvmTestClient`.
+#[derive(Debug)]
+pub struct TransactResult {
+	/// State root after the transaction was applied.
+	pub state_root: H256,
+	/// Hash of the RLP-encoded list of logs produced by the transaction.
+	pub logs_hash: H256,
+	/// Result of the execution. The state is left untouched if the transaction was invalid.
+	pub outcome: Result<Executed, ExecutionError>,
+}
+
+/// Client holding a single state, backed by a temporary database.
+pub struct EvmTestClient<'a> {
+	state: State,
+	spec: &'a Spec,
+	factories: Factories,
+	_temp: RandomTempPath,
+}
+
+impl<'a> EvmTestClient<'a> {
+	/// Creates a new client for given chain spec, starting with its genesis state.
+	pub fn new(spec: &'a Spec) -> Result<Self, EvmTestError> {
+		Self::from_pod_state(spec, spec.genesis_state().clone())
+	}
+
+	/// Creates a new client for given chain spec with given accounts in its state.
+	pub fn from_pod_state(spec: &'a Spec, pod_state: PodState) -> Result<Self, EvmTestError> {
+		let temp = RandomTempPath::new();
+		let db = try!(Database::open(&DatabaseConfig::with_columns(NUM_COLUMNS), temp.as_str()).map_err(EvmTestError::Database));
+		let journal_db = journaldb::new(Arc::new(db), journaldb::Algorithm::EarlyMerge, COL_STATE);
+		let factories = Factories::default();
+
+		let mut state = State::new(StateDB::new(journal_db, 1024 * 1024), spec.engine.account_start_nonce(), factories.clone());
+		state.populate_from(pod_state);
+		try!(state.commit().map_err(EvmTestError::State));
+
+		Ok(EvmTestClient {
+			state: state,
+			spec: spec,
+			factories: factories,
+			_temp: temp,
+		})
+	}
+
+	/// Current state root.
+	pub fn state_root(&self) -> &H256 {
+		self.state.root()
+	}
+
+	/// Executes given message call, reporting every executed instruction to given VM tracer.
+	pub fn call<V: VMTracer>(&mut self, env_info: &EnvInfo, params: ActionParams, vm_tracer: &mut V) -> Result<FinalizationResult, EvmTestError> {
+		let mut substate = Substate::new();
+		let mut output = vec![];
+		Executive::new(&mut self.state, env_info, &*self.spec.engine, &self.factories.vm)
+			.call(params, &mut substate, BytesRef::Flexible(&mut output), &mut NoopTracer, vm_tracer)
+			.map_err(EvmTestError::Evm)
+	}
+
+	/// Executes given transaction and commits the resulting state.
+	pub fn transact(&mut self, env_info: &EnvInfo, transaction: &SignedTransaction) -> Result<TransactResult, EvmTestError> {
+		let options = TransactOptions { tracing: false, vm_tracing: false, check_nonce: true };
+		let outcome = Executive::new(&mut self.state, env_info, &*self.spec.engine, &self.factories.vm).transact(transaction, options);
+		try!(self.state.commit().map_err(EvmTestError::State));
+
+		let logs_hash = {
+			let no_logs = Vec::new();
+			let logs: &Vec<LogEntry> = outcome.as_ref().map(|e| &e.logs).unwrap_or(&no_logs);
+			rlp::encode(logs).to_vec().sha3()
+		};
+
+		Ok(TransactResult {
+			state_root: self.state.root().clone(),
+			logs_hash: logs_hash,
+			outcome: outcome,
+		})
+	}
+}
@@ -17,6 +17,7 @@
 //! Blockchain database client.
 
 mod config;
+mod evm_test_client;
 mod error;
 mod test_client;
 mod trace;
@@ -26,6 +27,7 @@ pub use self::client::*;
 pub use self::config::{Mode, ClientConfig, DatabaseCompactionProfile, BlockChainConfig, VMType};
 pub use self::error::Error;
 pub use self::test_client::{TestBlockChainClient, EachBlockWith};
+pub use self::evm_test_client::{EvmTestClient, EvmTestError, TransactResult};
 pub use self::chain_notify::ChainNotify;
 pub use self::traits::{BlockChainClient, MiningBlockChainClient};
 
@@ -35,6 +37,8 @@ pub use types::ids::*;
 pub use types::trace_filter::Filter as TraceFilter;
 pub use types::pruning_info::PruningInfo;
 pub use types::call_analytics::CallAnalytics;
+pub use types::state_override::{StateOverride, AccountOverride};
+pub use types::account_proof::{AccountProof, StorageProof};
 
 pub use executive::{Executed, Executive, TransactOptions};
 pub use env_info::{LastHashes, EnvInfo};
@@ -24,8 +24,9 @@ use devtools::*;
 use transaction::{Transaction, LocalizedTransaction, SignedTransaction, Action};
 use blockchain::TreeRoute;
 use client::{
-	BlockChainClient, MiningBlockChainClient, BlockChainInfo, BlockStatus, BlockId,
-	TransactionId, UncleId, TraceId, TraceFilter, LastHashes, CallAnalytics, BlockImportError,
+	BlockChainClient, MiningBlockChainClient, ProvingBlockChainClient, BlockChainInfo, BlockStatus, BlockId,
+	TransactionId, UncleId, TraceId, TraceFilter, LastHashes, CallAnalytics, StateOverride, BlockImportError,
+	AccountProof, StorageProof, localize_block_receipts,
 };
 use db::{NUM_COLUMNS, COL_STATE};
 use header::{Header as BlockHeader, BlockNumber};
@@ -45,7 +46,7 @@ use verification::queue::QueueInfo;
 use block::{OpenBlock, SealedBlock};
 use executive::Executed;
 use error::CallError;
-use trace::LocalizedTrace;
+use trace::{LocalizedTrace, StructLogOptions, StructLogTrace};
 use state_db::StateDB;
 
 /// Test client.
@@ -72,6 +73,8 @@ pub struct TestBlockChainClient {
 	pub code: RwLock<HashMap<Address, Bytes>>,
 	/// Execution result.
 	pub execution_result: RwLock<Option<Result<Executed, CallError>>>,
+	/// Struct log trace returned by the `*_struct_logs` calls.
+	pub struct_log_result: RwLock<Option<Result<StructLogTrace, CallError>>>,
 	/// Transaction receipts.
 	pub receipts: RwLock<HashMap<TransactionId, LocalizedReceipt>>,
 	/// Logs
@@ -142,6 +145,7 @@ impl TestBlockChainClient {
 			storage: RwLock::new(HashMap::new()),
 			code: RwLock::new(HashMap::new()),
 			execution_result: RwLock::new(None),
+			struct_log_result: RwLock::new(None),
 			receipts: RwLock::new(HashMap::new()),
 			logs: RwLock::new(Vec::new()),
 			queue_size: AtomicUsize::new(0),
@@ -167,6 +171,11 @@ impl TestBlockChainClient {
 		*self.execution_result.write() = Some(result);
 	}
 
+	/// Set the struct log trace for the next `*_struct_logs` call.
+	pub fn set_struct_log_result(&self, result: Result<StructLogTrace, CallError>) {
+		*self.struct_log_result.write() = Some(result);
+	}
+
 	/// Set the balance of account `address` to `balance`.
 	pub fn set_balance(&self, address: Address, balance: U256) {
 		self.balances.write().insert(address, balance);
@@ -363,14 +372,34 @@ impl MiningBlockChainClient for TestBlockChainClient {
 }
 
 impl BlockChainClient for TestBlockChainClient {
-	fn call(&self, _t: &SignedTransaction, _block: BlockId, _analytics: CallAnalytics) -> Result<Executed, CallError> {
+	fn call(&self, _t: &SignedTransaction, _block: BlockId, _analytics: CallAnalytics, _state_override: &StateOverride) -> Result<Executed, CallError> {
 		self.execution_result.read().clone().unwrap()
 	}
 
+	fn estimate_gas(&self, _t: &SignedTransaction, _block: BlockId, _state_override: &StateOverride) -> Result<U256, CallError> {
+		self.execution_result.read().clone().unwrap().map(|executed| executed.gas_used + executed.refunded)
+	}
+
+	fn call_many(&self, transactions: Vec<SignedTransaction>, _analytics: Vec<CallAnalytics>, _block: BlockId) -> Result<Vec<Executed>, CallError> {
+		transactions.iter().map(|_| self.execution_result.read().clone().unwrap()).collect()
+	}
+
 	fn replay(&self, _id: TransactionId, _analytics: CallAnalytics) -> Result<Executed, CallError> {
 		self.execution_result.read().clone().unwrap()
 	}
 
+	fn replay_block_transactions(&self, _block: BlockId, _analytics: CallAnalytics) -> Result<Vec<Executed>, CallError> {
+		self.execution_result.read().clone().unwrap().map(|executed| vec![executed])
+	}
+
+	fn call_struct_logs(&self, _t: &SignedTransaction, _block: BlockId, _options: StructLogOptions) -> Result<StructLogTrace, CallError> {
+		self.struct_log_result.read().clone().unwrap()
+	}
+
+	fn replay_struct_logs(&self, _id: TransactionId, _options: StructLogOptions) -> Result<StructLogTrace, CallError> {
+		self.struct_log_result.read().clone().unwrap()
+	}
+
 	fn block_total_difficulty(&self, _id: BlockId) -> Option<U256> {
 		Some(U256::zero())
 	}
@@ -448,6 +477,18 @@ impl BlockChainClient for TestBlockChainClient {
 		self.receipts.read().get(&id).cloned()
 	}
 
+	fn localized_block_receipts(&self, id: BlockId) -> Option<Vec<LocalizedReceipt>> {
+		let block = match self.block(id) {
+			Some(block) => block,
+			None => return None,
+		};
+		let view = BlockView::new(&block);
+		self.block_receipts(&view.hash()).map(|receipts| {
+			let receipts: BlockReceipts = decode(&receipts);
+			localize_block_receipts(view.localized_transactions(), receipts.receipts)
+		})
+	}
+
 	fn blocks_with_bloom(&self, _bloom: &H2048, _from_block: BlockId, _to_block: BlockId) -> Option<Vec<BlockNumber>> {
 		unimplemented!();
 	}
@@ -680,3 +721,36 @@ impl BlockChainClient for TestBlockChainClient {
 		}
 	}
 }
+
+impl ProvingBlockChainClient for TestBlockChainClient {
+	fn prove_storage(&self, _: H256, _: H256, _: u32, _: BlockId) -> Vec<Bytes> {
+		Vec::new()
+	}
+
+	fn prove_account(&self, _: H256, _: u32, _: BlockId) -> Vec<Bytes> {
+		Vec::new()
+	}
+
+	fn prove_account_with_storage(&self, address: &Address, storage_keys: &[H256], id: BlockId) -> Option<Result<AccountProof, Box<TrieError>>> {
+		// there is no state trie, so only the values are returned, without proofs.
+		match id {
+			BlockId::Latest => Some(Ok(AccountProof {
+				balance: self.latest_balance(address),
+				nonce: self.latest_nonce(address),
+				code_hash: self.code.read().get(address).map_or(SHA3_EMPTY, |code| code.sha3()),
+				storage_root: SHA3_NULL_RLP,
+				proof: Vec::new(),
+				storage: storage_keys.iter().map(|key| StorageProof {
+					key: key.clone(),
+					value: self.latest_storage_at(address, key),
+					proof: Vec::new(),
+				}).collect(),
+			})),
+			_ => None,
+		}
+	}
+
+	fn code_by_hash(&self, _: H256, _: BlockId) -> Bytes {
+		Vec::new()
+	}
+}
@@ -6,6 +6,7 @@ use header::BlockNumber;
 use trace::DatabaseExtras as TraceDatabaseExtras;
 use blockchain::{BlockChain, BlockProvider};
 use blockchain::extras::TransactionAddress;
+use views::BodyView;
 pub use types::trace_filter::Filter;
 
 impl TraceDatabaseExtras for BlockChain {
@@ -24,4 +25,10 @@ impl TraceDatabaseExtras for BlockChain {
 			})
 			.map(|tx| tx.hash())
 	}
+
+	fn transactions_count(&self, block_number: BlockNumber) -> Option<usize> {
+		(self as &BlockProvider).block_hash(block_number)
+			.and_then(|block_hash| self.block_body(&block_hash))
+			.map(|body| BodyView::new(&body).transactions_count())
+	}
 }
@@ -15,7 +15,7 @@
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::collections::BTreeMap;
-use util::{U256, Address, H256, H2048, Bytes, Itertools};
+use util::{U256, Address, H256, H2048, Bytes, Itertools, TrieError};
 use util::stats::Histogram;
 use blockchain::TreeRoute;
 use verification::queue::QueueInfo as BlockQueueInfo;
@@ -27,7 +27,7 @@ use filter::Filter;
 use views::{BlockView};
 use error::{ImportResult, CallError};
 use receipt::LocalizedReceipt;
-use trace::LocalizedTrace;
+use trace::{LocalizedTrace, StructLogOptions, StructLogTrace};
 use evm::{Factory as EvmFactory, Schedule};
 use executive::Executed;
 use env_info::LastHashes;
@@ -36,6 +36,8 @@ use ipc::IpcConfig;
 use types::ids::*;
 use types::trace_filter::Filter as TraceFilter;
 use types::call_analytics::CallAnalytics;
+use types::state_override::StateOverride;
+use types::account_proof::AccountProof;
 use types::blockchain_info::BlockChainInfo;
 use types::block_status::BlockStatus;
 use types::mode::Mode;
@@ -138,6 +140,9 @@ pub trait BlockChainClient : Sync + Send {
 	/// Get transaction receipt with given hash.
 	fn transaction_receipt(&self, id: TransactionId) -> Option<LocalizedReceipt>;
 
+	/// Get receipts of all transactions in given block.
+	fn localized_block_receipts(&self, id: BlockId) -> Option<Vec<LocalizedReceipt>>;
+
 	/// Get a tree route between `from` and `to`.
 	/// See `BlockChain::tree_route`.
 	fn tree_route(&self, from: &H256, to: &H256) -> Option<TreeRoute>;
@@ -178,12 +183,29 @@ pub trait BlockChainClient : Sync + Send {
 	/// Returns logs matching given filter.
 	fn logs(&self, filter: Filter) -> Vec<LocalizedLogEntry>;
 
-	/// Makes a non-persistent transaction call.
-	fn call(&self, t: &SignedTransaction, block: BlockId, analytics: CallAnalytics) -> Result<Executed, CallError>;
+	/// Makes a non-persistent transaction call, on top of the state of given block with `state_override` applied.
+	fn call(&self, t: &SignedTransaction, block: BlockId, analytics: CallAnalytics, state_override: &StateOverride) -> Result<Executed, CallError>;
+
+	/// Estimates the gas needed by given transaction, searching between its intrinsic gas
+	/// and the gas limit of given block. The state of the block is altered by `state_override`.
+	fn estimate_gas(&self, t: &SignedTransaction, block: BlockId, state_override: &StateOverride) -> Result<U256, CallError>;
+
+	/// Makes multiple non-persistent transaction calls, each on top of the state left by the previous one.
+	/// `analytics[i]` is used for `transactions[i]`; missing entries default to no analytics.
+	fn call_many(&self, transactions: Vec<SignedTransaction>, analytics: Vec<CallAnalytics>, block: BlockId) -> Result<Vec<Executed>, CallError>;
 
 	/// Replays a given transaction for inspection.
 	fn replay(&self, t: TransactionId, analytics: CallAnalytics) -> Result<Executed, CallError>;
 
+	/// Replays all transactions of a given block for inspection.
+	fn replay_block_transactions(&self, block: BlockId, analytics: CallAnalytics) -> Result<Vec<Executed>, CallError>;
+
+	/// Makes a non-persistent transaction call, recording the state of the VM before each instruction.
+	fn call_struct_logs(&self, t: &SignedTransaction, block: BlockId, options: StructLogOptions) -> Result<StructLogTrace, CallError>;
+
+	/// Replays a given transaction, recording the state of the VM before each instruction.
+	fn replay_struct_logs(&self, t: TransactionId, options: StructLogOptions) -> Result<StructLogTrace, CallError>;
+
 	/// Returns traces matching given filter.
 	fn filter_traces(&self, filter: TraceFilter) -> Option<Vec<LocalizedTrace>>;
 
@@ -297,6 +319,11 @@ pub trait ProvingBlockChainClient: BlockChainClient {
 	/// An empty vector indicates unservable query.
 	fn prove_account(&self, key1: H256, from_level: u32, id: BlockId) -> Vec<Bytes>;
 
+	/// Prove an account and the given storage keys of it at a specific block id.
+	/// Everything is read from the same state, so all the proofs are relative to the block's state root.
+	/// Returns `None` if the state is not available, or the trie error encountered while proving.
+	fn prove_account_with_storage(&self, address: &Address, storage_keys: &[H256], id: BlockId) -> Option<Result<AccountProof, Box<TrieError>>>;
+
 	/// Get code by address hash.
 	fn code_by_hash(&self, account_key: H256, id: BlockId) -> Bytes;
 }
@@ -30,12 +30,10 @@ use header::Header;
 use error::{Error, BlockError};
 use blockchain::extras::BlockDetails;
 use views::HeaderView;
-use evm::Schedule;
 use ethjson;
 use io::{IoContext, IoHandler, TimerToken, IoService, IoChannel};
 use service::ClientIoMessage;
 use transaction::SignedTransaction;
-use env_info::EnvInfo;
 use builtin::Builtin;
 
 /// `AuthorityRound` params.
@@ -199,10 +197,6 @@ impl Engine for AuthorityRound {
 		]
 	}
 
-	fn schedule(&self, _env_info: &EnvInfo) -> Schedule {
-		Schedule::new_post_eip150(usize::max_value(), true, true, true)
-	}
-
 	fn populate_from_parent(&self, header: &mut Header, parent: &Header, gas_floor_target: U256, _gas_ceil_target: U256) {
 		header.set_difficulty(parent.difficulty().clone());
 		header.set_gas_limit({
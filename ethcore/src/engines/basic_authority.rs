@@ -22,9 +22,7 @@ use block::*;
 use builtin::Builtin;
 use spec::CommonParams;
 use engines::Engine;
-use env_info::EnvInfo;
 use error::{BlockError, Error};
-use evm::Schedule;
 use ethjson;
 use header::Header;
 use transaction::SignedTransaction;
@@ -87,10 +85,6 @@ impl Engine for BasicAuthority {
 	/// Additional engine-specific information for the user/developer concerning `header`.
 	fn extra_info(&self, _header: &Header) -> BTreeMap<String, String> { map!["signature".to_owned() => "TODO".to_owned()] }
 
-	fn schedule(&self, _env_info: &EnvInfo) -> Schedule {
-		Schedule::new_homestead()
-	}
-
 	fn populate_from_parent(&self, header: &mut Header, parent: &Header, gas_floor_target: U256, _gas_ceil_target: U256) {
 		header.set_difficulty(parent.difficulty().clone());
 		header.set_gas_limit({
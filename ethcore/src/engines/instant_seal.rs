@@ -18,9 +18,7 @@ use std::collections::BTreeMap;
 use util::Address;
 use builtin::Builtin;
 use engines::Engine;
-use env_info::EnvInfo;
 use spec::CommonParams;
-use evm::Schedule;
 use block::ExecutedBlock;
 use util::Bytes;
 
@@ -53,10 +51,6 @@ impl Engine for InstantSeal {
 		&self.builtins
 	}
 
-	fn schedule(&self, _env_info: &EnvInfo) -> Schedule {
-		Schedule::new_post_eip150(usize::max_value(), false, false, false)
-	}
-
 	fn is_sealer(&self, _author: &Address) -> Option<bool> { Some(true) }
 
 	fn generate_seal(&self, _block: &ExecutedBlock) -> Option<Vec<Bytes>> {
@@ -29,7 +29,7 @@ pub use self::authority_round::AuthorityRound;
 use util::*;
 use account_provider::AccountProvider;
 use block::ExecutedBlock;
-use builtin::Builtin;
+use builtin::{Builtin, Error as BuiltinError};
 use env_info::EnvInfo;
 use error::Error;
 use spec::CommonParams;
@@ -63,7 +63,10 @@ pub trait Engine : Sync + Send {
 	fn params(&self) -> &CommonParams;
 
 	/// Get the EVM schedule for the given `env_info`.
-	fn schedule(&self, env_info: &EnvInfo) -> Schedule;
+	/// By default it is built from the transition table of the common parameters.
+	fn schedule(&self, env_info: &EnvInfo) -> Schedule {
+		self.params().schedule(env_info.number)
+	}
 
 	/// Builtin-contracts we would like to see in the chain.
 	/// (In principle these are just hints for the engine since that has the last word on them.)
@@ -116,7 +119,13 @@ pub trait Engine : Sync + Send {
 	fn verify_transaction(&self, _t: &SignedTransaction, _header: &Header) -> Result<(), Error> { Ok(()) }
 
 	/// The network ID that transactions should be signed with.
-	fn signing_network_id(&self, _env_info: &EnvInfo) -> Option<u64> { None }
+	fn signing_network_id(&self, env_info: &EnvInfo) -> Option<u64> {
+		if env_info.number >= self.params().eip155_transition {
+			Some(self.params().network_id)
+		} else {
+			None
+		}
+	}
 
 	/// Verify the seal of a block. This is an auxilliary method that actually just calls other `verify_` methods
 	/// to get the job done. By default it must pass `verify_basic` and `verify_block_unordered`. If more or fewer
@@ -135,17 +144,24 @@ pub trait Engine : Sync + Send {
 
 	// TODO: builtin contract routing - to do this properly, it will require removing the built-in configuration-reading logic
 	// from Spec into here and removing the Spec::builtins field.
-	/// Determine whether a particular address is a builtin contract.
-	fn is_builtin(&self, a: &Address) -> bool { self.builtins().contains_key(a) }
+	/// Determine whether a particular address is a builtin contract active in the block being executed.
+	/// Inactive builtins are treated as plain accounts.
+	fn is_builtin(&self, a: &Address, env_info: &EnvInfo) -> bool {
+		self.builtins().get(a).map_or(false, |b| b.is_active(env_info.number))
+	}
 	/// Determine the code execution cost of the builtin contract with address `a`.
-	/// Panics if `is_builtin(a)` is not true.
-	fn cost_of_builtin(&self, a: &Address, input: &[u8]) -> U256 {
-		self.builtins().get(a).expect("queried cost of nonexistent builtin").cost(input.len())
+	/// Panics if `is_builtin(a, env_info)` is not true.
+	fn cost_of_builtin(&self, a: &Address, env_info: &EnvInfo, input: &[u8]) -> U256 {
+		let builtin = self.builtins().get(a).expect("queried cost of nonexistent builtin");
+		assert!(builtin.is_active(env_info.number), "queried cost of inactive builtin");
+		builtin.cost(input)
 	}
 	/// Execution the builtin contract `a` on `input` and return `output`.
-	/// Panics if `is_builtin(a)` is not true.
-	fn execute_builtin(&self, a: &Address, input: &[u8], output: &mut BytesRef) {
-		self.builtins().get(a).expect("attempted to execute nonexistent builtin").execute(input, output);
+	/// Panics if `is_builtin(a, env_info)` is not true.
+	fn execute_builtin(&self, a: &Address, env_info: &EnvInfo, input: &[u8], output: &mut BytesRef) -> Result<(), BuiltinError> {
+		let builtin = self.builtins().get(a).expect("attempted to execute nonexistent builtin");
+		assert!(builtin.is_active(env_info.number), "attempted to execute inactive builtin");
+		builtin.execute(input, output)
 	}
 
 	/// Check if new block should be chosen as the one  in chain.
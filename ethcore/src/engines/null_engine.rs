@@ -19,8 +19,6 @@ use util::Address;
 use builtin::Builtin;
 use engines::Engine;
 use spec::CommonParams;
-use evm::Schedule;
-use env_info::EnvInfo;
 
 /// An engine which does not provide any consensus mechanism and does not seal blocks.
 pub struct NullEngine {
@@ -57,7 +55,4 @@ impl Engine for NullEngine {
 		&self.builtins
 	}
 
-	fn schedule(&self, _env_info: &EnvInfo) -> Schedule {
-		Schedule::new_homestead()
-	}
 }
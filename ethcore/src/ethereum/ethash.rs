@@ -25,6 +25,8 @@ use views::HeaderView;
 use state::CleanupMode;
 use spec::CommonParams;
 use transaction::SignedTransaction;
+use trace::{Tracer, ExecutiveTracer};
+use trace::trace::RewardType;
 use engines::Engine;
 use evm::Schedule;
 use ethjson;
@@ -62,22 +64,10 @@ pub struct EthashParams {
 	pub difficulty_hardfork_bound_divisor: U256,
 	/// Block on which there is no additional difficulty from the exponential bomb.
 	pub bomb_defuse_transition: u64,
-	/// Number of first block where EIP-150 rules begin.
-	pub eip150_transition: u64,
-	/// Number of first block where EIP-155 rules begin.
-	pub eip155_transition: u64,
-	/// Number of first block where EIP-160 rules begin.
-	pub eip160_transition: u64,
-	/// Number of first block where EIP-161.abc begin.
-	pub eip161abc_transition: u64,
-	/// Number of first block where EIP-161.d begins.
-	pub eip161d_transition: u64,
 	/// Number of first block where ECIP-1010 begins.
 	pub ecip1010_pause_transition: u64,
 	/// Number of first block where ECIP-1010 ends.
 	pub ecip1010_continue_transition: u64,
-	/// Maximum amount of code that can be deploying into a contract.
-	pub max_code_size: u64,
 }
 
 impl From<ethjson::spec::EthashParams> for EthashParams {
@@ -97,14 +87,8 @@ impl From<ethjson::spec::EthashParams> for EthashParams {
 			difficulty_hardfork_transition: p.difficulty_hardfork_transition.map_or(u64::max_value(), Into::into),
 			difficulty_hardfork_bound_divisor: p.difficulty_hardfork_bound_divisor.map_or(p.difficulty_bound_divisor.into(), Into::into),
 			bomb_defuse_transition: p.bomb_defuse_transition.map_or(u64::max_value(), Into::into),
-			eip150_transition: p.eip150_transition.map_or(0, Into::into),
-			eip155_transition: p.eip155_transition.map_or(0, Into::into),
-			eip160_transition: p.eip160_transition.map_or(0, Into::into),
-			eip161abc_transition: p.eip161abc_transition.map_or(0, Into::into),
-			eip161d_transition: p.eip161d_transition.map_or(u64::max_value(), Into::into),
 			ecip1010_pause_transition: p.ecip1010_pause_transition.map_or(u64::max_value(), Into::into),
 			ecip1010_continue_transition: p.ecip1010_continue_transition.map_or(u64::max_value(), Into::into),
-			max_code_size: p.max_code_size.map_or(u64::max_value(), Into::into),
 		}
 	}
 }
@@ -149,27 +133,14 @@ impl Engine for Ethash {
 	}
 
 	fn schedule(&self, env_info: &EnvInfo) -> Schedule {
-		trace!(target: "client", "Creating schedule. fCML={}, bGCML={}", self.ethash_params.homestead_transition, self.ethash_params.eip150_transition);
+		trace!(target: "client", "Creating schedule. fCML={}, bGCML={}", self.ethash_params.homestead_transition, self.params().eip150_transition);
 
 		if env_info.number < self.ethash_params.homestead_transition {
-			Schedule::new_frontier()
-		} else if env_info.number < self.ethash_params.eip150_transition {
-			Schedule::new_homestead()
+			let mut schedule = Schedule::new_frontier();
+			self.params().update_schedule(env_info.number, &mut schedule);
+			schedule
 		} else {
-			Schedule::new_post_eip150(
-				self.ethash_params.max_code_size as usize,
-				env_info.number >= self.ethash_params.eip160_transition,
-				env_info.number >= self.ethash_params.eip161abc_transition,
-				env_info.number >= self.ethash_params.eip161d_transition
-			)
-		}
-	}
-
-	fn signing_network_id(&self, env_info: &EnvInfo) -> Option<u64> {
-		if env_info.number >= self.ethash_params.eip155_transition {
-			Some(self.params().network_id)
-		} else {
-			None
+			self.params().schedule(env_info.number)
 		}
 	}
 
@@ -217,20 +188,34 @@ impl Engine for Ethash {
 	fn on_close_block(&self, block: &mut ExecutedBlock) {
 		let reward = self.ethash_params.block_reward;
 		let fields = block.fields_mut();
+		let mut tracer = ExecutiveTracer::default();
 
 		// Bestow block reward
-		fields.state.add_balance(fields.header.author(), &(reward + reward / U256::from(32) * U256::from(fields.uncles.len())), CleanupMode::NoEmpty);
+		let result_block_reward = reward + reward / U256::from(32) * U256::from(fields.uncles.len());
+		fields.state.add_balance(fields.header.author(), &result_block_reward, CleanupMode::NoEmpty);
+		if fields.traces.is_some() {
+			tracer.trace_reward(fields.header.author().clone(), result_block_reward, RewardType::Block);
+		}
 
 		// Bestow uncle rewards
 		let current_number = fields.header.number();
 		for u in fields.uncles.iter() {
-			fields.state.add_balance(u.author(), &(reward * U256::from(8 + u.number() - current_number) / U256::from(8)), CleanupMode::NoEmpty);
+			let result_uncle_reward = reward * U256::from(8 + u.number() - current_number) / U256::from(8);
+			fields.state.add_balance(u.author(), &result_uncle_reward, CleanupMode::NoEmpty);
+			if fields.traces.is_some() {
+				tracer.trace_reward(u.author().clone(), result_uncle_reward, RewardType::Uncle);
+			}
 		}
 
 		// Commit state so that we can actually figure out the state root.
 		if let Err(e) = fields.state.commit() {
 			warn!("Encountered error on state commit: {}", e);
 		}
+
+		// Rewards are traced as an extra entry after the block's transactions.
+		if let Some(ref mut traces) = *fields.traces {
+			traces.push(tracer.traces());
+		}
 	}
 
 	fn verify_block_basic(&self, header: &Header, _block: Option<&[u8]>) -> result::Result<(), Error> {
@@ -316,7 +301,7 @@ impl Engine for Ethash {
 		}
 
 		if let Some(n) = t.network_id() {
-			if header.number() < self.ethash_params.eip155_transition || n != self.params().network_id {
+			if header.number() < self.params().eip155_transition || n != self.params().network_id {
 				return Err(TransactionError::InvalidNetworkId.into())
 			}
 		}
@@ -441,6 +426,7 @@ mod tests {
 	use header::Header;
 	use super::super::{new_morden, new_homestead_test};
 	use super::{Ethash, EthashParams};
+	use trace::trace::{Action, Reward, RewardType};
 	use rlp;
 
 	#[test]
@@ -477,6 +463,34 @@ mod tests {
 		assert_eq!(b.state().balance(&uncle_author), "3cb71f51fc558000".into());
 	}
 
+	#[test]
+	fn on_close_block_traces_rewards() {
+		let spec = new_morden();
+		let engine = &*spec.engine;
+		let genesis_header = spec.genesis_header();
+		let mut db_result = get_temp_state_db();
+		let mut db = db_result.take();
+		spec.ensure_db_good(&mut db, &TrieFactory::new(TrieSpec::Secure)).unwrap();
+		let last_hashes = Arc::new(vec![genesis_header.hash()]);
+		let mut b = OpenBlock::new(engine, Default::default(), true, db, &genesis_header, last_hashes, Address::zero(), (3141562.into(), 31415620.into()), vec![]).unwrap();
+		let mut uncle = Header::new();
+		let uncle_author: Address = "ef2d6d194084c2de36e0dabfce45d046b37d1106".into();
+		uncle.set_author(uncle_author);
+		b.push_uncle(uncle).unwrap();
+
+		let b = b.close();
+		let traces = b.traces().clone().unwrap();
+		assert_eq!(traces.len(), 1);
+		let rewards: Vec<_> = traces[0].iter().map(|t| match t.action {
+			Action::Reward(ref reward) => reward.clone(),
+			_ => panic!("Expected reward trace"),
+		}).collect();
+		assert_eq!(rewards, vec![
+			Reward { author: Address::zero(), value: "478eae0e571ba000".into(), reward_type: RewardType::Block },
+			Reward { author: uncle_author, value: "3cb71f51fc558000".into(), reward_type: RewardType::Uncle },
+		]);
+	}
+
 	#[test]
 	fn has_valid_metadata() {
 		let engine = new_morden().engine;
@@ -57,6 +57,12 @@ pub fn new_eip150_test() -> Spec { load(include_bytes!("../../res/ethereum/eip15
 /// Create a new Homestead-EIP150 chain spec as though it never changed from Homestead/Frontier.
 pub fn new_eip161_test() -> Spec { load(include_bytes!("../../res/ethereum/eip161_test.json")) }
 
+/// Create a new chain spec with the Byzantium rules active from genesis.
+pub fn new_byzantium_test() -> Spec { load(include_bytes!("../../res/ethereum/byzantium_test.json")) }
+
+/// Create a new chain spec with the Byzantium and Constantinople rules active from genesis.
+pub fn new_constantinople_test() -> Spec { load(include_bytes!("../../res/ethereum/constantinople_test.json")) }
+
 /// Create a new Frontier/Homestead/DAO chain spec with transition points at #5 and #8.
 pub fn new_transition_test() -> Spec { load(include_bytes!("../../res/ethereum/transition_test.json")) }
 
@@ -77,6 +83,7 @@ mod tests {
 	use super::*;
 	use tests::helpers::*;
 	use views::BlockView;
+	use env_info::EnvInfo;
 
 	#[test]
 	fn ensure_db_good() {
@@ -106,6 +113,22 @@ mod tests {
 		let _ = morden.engine;
 	}
 
+	#[test]
+	fn byzantium_and_constantinople_test_specs() {
+		let env_info = EnvInfo::default();
+
+		let byzantium = new_byzantium_test();
+		let schedule = byzantium.engine.schedule(&env_info);
+		assert!(schedule.have_revert && schedule.have_return_data && schedule.have_static_call);
+		assert!(!schedule.have_bitwise_shifting && !schedule.have_create2 && !schedule.sstore_net_metering);
+		assert!(byzantium.engine.is_builtin(&"0000000000000000000000000000000000000008".into(), &env_info));
+
+		let constantinople = new_constantinople_test();
+		let schedule = constantinople.engine.schedule(&env_info);
+		assert!(schedule.have_revert && schedule.have_bitwise_shifting && schedule.have_create2 && schedule.sstore_net_metering);
+		assert!(constantinople.engine.is_builtin(&"0000000000000000000000000000000000000005".into(), &env_info));
+	}
+
 	#[test]
 	fn frontier() {
 		let frontier = new_frontier();
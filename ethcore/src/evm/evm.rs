@@ -17,7 +17,7 @@
 //! Evm interface.
 
 use std::{ops, cmp, fmt};
-use util::{U128, U256, U512, Uint};
+use util::{U128, U256, U512, Uint, Bytes};
 use action_params::ActionParams;
 use evm::Ext;
 
@@ -59,6 +59,19 @@ pub enum Error {
 		/// What was the stack limit
 		limit: usize
 	},
+	/// `OutOfBounds` is returned when `RETURNDATACOPY` tries to read
+	/// past the end of the return data buffer.
+	OutOfBounds,
+	/// `MutableCallInStaticContext` is returned when an instruction
+	/// that modifies the state is executed inside of a `STATICCALL`.
+	MutableCallInStaticContext,
+	/// `ContractCollision` is returned when a contract is created at an address
+	/// which already has code or a nonzero nonce.
+	ContractCollision,
+	/// Built-in contract failed on given input
+	BuiltIn(&'static str),
+	/// Wasm runtime error: the contract could not be loaded or trapped during execution
+	Wasm(&'static str),
 	/// Returned on evm internal error. Should never be ignored during development.
 	/// Likely to cause consensus issues.
 	#[allow(dead_code)] // created only by jit
@@ -74,6 +87,11 @@ impl fmt::Display for Error {
 			BadInstruction { .. } => "Bad instruction",
 			StackUnderflow { .. } => "Stack underflow",
 			OutOfStack { .. } => "Out of stack",
+			OutOfBounds => "Return data out of bounds",
+			MutableCallInStaticContext => "Mutable call in static context",
+			ContractCollision => "Contract address collision",
+			BuiltIn(name) => name,
+			Wasm(msg) => msg,
 			Internal => "Internal error",
 		};
 		message.fmt(f)
@@ -89,8 +107,26 @@ pub type Result<T> = ::std::result::Result<T, Error>;
 pub enum GasLeft<'a> {
 	/// Known gas left
 	Known(U256),
-	/// Return instruction must be processed.
-	NeedsReturn(U256, &'a [u8]),
+	/// Return or revert instruction must be processed.
+	NeedsReturn {
+		/// Amount of gas left.
+		gas_left: U256,
+		/// Return data.
+		data: &'a [u8],
+		/// Apply or revert state changes (`false` on `REVERT`).
+		apply_state: bool,
+	},
+}
+
+/// Finalization result. Gas left, whether the state changes should be applied and the return data.
+#[derive(Debug, PartialEq, Clone)]
+pub struct FinalizationResult {
+	/// Final amount of gas left.
+	pub gas_left: U256,
+	/// Apply execution state changes or revert them.
+	pub apply_state: bool,
+	/// Data returned by `RETURN` or `REVERT`. Becomes the caller's return data buffer.
+	pub return_data: Bytes,
 }
 
 /// Types that can be "finalized" using an EVM.
@@ -98,15 +134,19 @@ pub enum GasLeft<'a> {
 /// In practice, this is just used to define an inherent impl on
 /// `Reult<GasLeft<'a>>`.
 pub trait Finalize {
-	/// Consume the externalities, call return if necessary, and produce a final amount of gas left.
-	fn finalize<E: Ext>(self, ext: E) -> Result<U256>;
+	/// Consume the externalities, call return if necessary, and produce the final result.
+	fn finalize<E: Ext>(self, ext: E) -> Result<FinalizationResult>;
 }
 
 impl<'a> Finalize for Result<GasLeft<'a>> {
-	fn finalize<E: Ext>(self, ext: E) -> Result<U256> {
+	fn finalize<E: Ext>(self, ext: E) -> Result<FinalizationResult> {
 		match self {
-			Ok(GasLeft::Known(gas)) => Ok(gas),
-			Ok(GasLeft::NeedsReturn(gas, ret_code)) => ext.ret(&gas, ret_code),
+			Ok(GasLeft::Known(gas_left)) => Ok(FinalizationResult { gas_left: gas_left, apply_state: true, return_data: Vec::new() }),
+			Ok(GasLeft::NeedsReturn { gas_left, data, apply_state }) => ext.ret(&gas_left, data, apply_state).map(|gas_left| FinalizationResult {
+				gas_left: gas_left,
+				apply_state: apply_state,
+				return_data: data.to_vec(),
+			}),
 			Err(err) => Err(err),
 		}
 	}
@@ -21,11 +21,23 @@ use evm::{self, Schedule};
 use env_info::*;
 use types::executed::CallType;
 
+/// Specifies how an address is calculated for a new contract.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CreateContractAddress {
+	/// Address is calculated from sender and nonce (`CREATE`).
+	FromSenderAndNonce,
+	/// Address is calculated from sender, salt and init code hash (`CREATE2`).
+	FromSenderSaltAndCodeHash(H256),
+}
+
 /// Result of externalities create function.
 pub enum ContractCreateResult {
 	/// Returned when creation was successfull.
 	/// Contains an address of newly created contract and gas left.
 	Created(Address, U256),
+	/// Returned when contract creation was reverted.
+	/// Contains gas left and the data passed to `REVERT`.
+	Reverted(U256, Bytes),
 	/// Returned when contract creation failed.
 	/// VM doesn't have to know the reason.
 	Failed
@@ -34,8 +46,11 @@ pub enum ContractCreateResult {
 /// Result of externalities call function.
 pub enum MessageCallResult {
 	/// Returned when message call was successfull.
-	/// Contains gas left.
-	Success(U256),
+	/// Contains gas left and the data returned by the callee.
+	Success(U256, Bytes),
+	/// Returned when message call was reverted.
+	/// Contains gas left and the data passed to `REVERT`.
+	Reverted(U256, Bytes),
 	/// Returned when message call failed.
 	/// VM doesn't have to know the reason.
 	Failed
@@ -46,6 +61,9 @@ pub trait Ext {
 	/// Returns a value for given key.
 	fn storage_at(&self, key: &H256) -> H256;
 
+	/// Returns the value for given key as it was before the current transaction started.
+	fn original_storage_at(&self, key: &H256) -> H256;
+
 	/// Stores a value for given key.
 	fn set_storage(&mut self, key: H256, value: H256);
 
@@ -67,7 +85,7 @@ pub trait Ext {
 	/// Creates new contract.
 	///
 	/// Returns gas_left and contract address if contract creation was succesfull.
-	fn create(&mut self, gas: &U256, value: &U256, code: &[u8]) -> ContractCreateResult;
+	fn create(&mut self, gas: &U256, value: &U256, code: &[u8], address: CreateContractAddress) -> ContractCreateResult;
 
 	/// Message call.
 	///
@@ -95,9 +113,10 @@ pub trait Ext {
 	/// Creates log entry with given topics and data
 	fn log(&mut self, topics: Vec<H256>, data: &[u8]);
 
-	/// Should be called when transaction calls `RETURN` opcode.
+	/// Should be called when transaction calls `RETURN` or `REVERT` opcode.
+	/// `apply_state` is `false` for `REVERT`, in which case no code is deployed.
 	/// Returns gas_left if cost of returning the data is not too high.
-	fn ret(self, gas: &U256, data: &[u8]) -> evm::Result<U256> where Self: Sized;
+	fn ret(self, gas: &U256, data: &[u8], apply_state: bool) -> evm::Result<U256> where Self: Sized;
 
 	/// Should be called when contract commits suicide.
 	/// Address to which funds should be refunded.
@@ -115,12 +134,21 @@ pub trait Ext {
 	/// then A depth is 0, B is 1, C is 2 and so on.
 	fn depth(&self) -> usize;
 
-	/// Increments sstore refunds count by 1.
-	fn inc_sstore_clears(&mut self);
+	/// Check if running in static context.
+	fn is_static(&self) -> bool;
+
+	/// Increments sstore refunds counter by the given amount of gas.
+	fn add_sstore_refund(&mut self, value: usize);
+
+	/// Decrements sstore refunds counter by the given amount of gas.
+	fn sub_sstore_refund(&mut self, value: usize);
 
 	/// Prepare to trace an operation. Passthrough for the VM trace.
 	fn trace_prepare_execute(&mut self, _pc: usize, _instruction: u8, _gas_cost: &U256) -> bool { false }
 
+	/// Trace the state of the VM before executing the prepared operation. Passthrough for the VM trace.
+	fn trace_state(&mut self, _gas_left: &U256, _stack: &[U256], _memory: &[u8]) {}
+
 	/// Trace the finalised execution of a single instruction.
 	fn trace_executed(&mut self, _gas_used: U256, _stack_push: &[U256], _mem_diff: Option<(usize, &[u8])>, _store_diff: Option<(U256, U256)>) {}
 }
@@ -19,9 +19,12 @@
 //! TODO: consider spliting it into two separate files.
 use std::fmt;
 use std::sync::Arc;
-use evm::Evm;
+use evm::{Evm, Schedule};
 use util::{U256, Uint};
+use action_params::ActionParams;
 use super::interpreter::SharedCache;
+#[cfg(feature = "wasm")]
+use super::wasm::{self, WasmInterpreter};
 
 #[derive(Debug, PartialEq, Clone)]
 /// Type of EVM to use.
@@ -30,7 +33,7 @@ pub enum VMType {
 	#[cfg(feature = "jit")]
 	Jit,
 	/// RUST EVM
-	Interpreter
+	Interpreter,
 }
 
 impl fmt::Display for VMType {
@@ -38,13 +41,13 @@ impl fmt::Display for VMType {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(f, "{}", match *self {
 			VMType::Jit => "JIT",
-			VMType::Interpreter => "INT"
+			VMType::Interpreter => "INT",
 		})
 	}
 	#[cfg(not(feature="jit"))]
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(f, "{}", match *self {
-			VMType::Interpreter => "INT"
+			VMType::Interpreter => "INT",
 		})
 	}
 }
@@ -56,13 +59,13 @@ impl Default for VMType {
 }
 
 impl VMType {
-	/// Return all possible VMs (JIT, Interpreter)
+	/// Return all possible EVMs (JIT, Interpreter)
 	#[cfg(feature = "jit")]
 	pub fn all() -> Vec<VMType> {
 		vec![VMType::Jit, VMType::Interpreter]
 	}
 
-	/// Return all possible VMs (Interpreter)
+	/// Return all possible EVMs (Interpreter)
 	#[cfg(not(feature = "jit"))]
 	pub fn all() -> Vec<VMType> {
 		vec![VMType::Interpreter]
@@ -101,7 +104,7 @@ impl Factory {
 				Box::new(super::interpreter::Interpreter::<usize>::new(self.evm_cache.clone()))
 			} else {
 				Box::new(super::interpreter::Interpreter::<U256>::new(self.evm_cache.clone()))
-			}
+			},
 		}
 	}
 
@@ -114,10 +117,30 @@ impl Factory {
 				Box::new(super::interpreter::Interpreter::<usize>::new(self.evm_cache.clone()))
 			} else {
 				Box::new(super::interpreter::Interpreter::<U256>::new(self.evm_cache.clone()))
-			}
+			},
 		}
 	}
 
+	/// Create fresh instance of VM for given action.
+	/// Picks the wasm runtime for wasm contract code if the schedule enables it,
+	/// otherwise behaves like `create`.
+	#[cfg(feature = "wasm")]
+	pub fn create_for(&self, params: &ActionParams, schedule: &Schedule) -> Box<Evm> {
+		let is_wasm = schedule.wasm.is_some() && params.code.as_ref().map_or(false, |code| wasm::is_wasm_code(code));
+		if is_wasm {
+			Box::new(WasmInterpreter::new())
+		} else {
+			self.create(params.gas)
+		}
+	}
+
+	/// Create fresh instance of VM for given action.
+	/// Built without the `wasm` feature, so this is always the EVM returned by `create`.
+	#[cfg(not(feature = "wasm"))]
+	pub fn create_for(&self, params: &ActionParams, _schedule: &Schedule) -> Box<Evm> {
+		self.create(params.gas)
+	}
+
 	/// Create new instance of specific `VMType` factory, with a size in bytes
 	/// for caching jump destinations.
 	pub fn new(evm: VMType, cache_size: usize) -> Self {
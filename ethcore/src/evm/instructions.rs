@@ -37,6 +37,21 @@ fn test_get_push_bytes() {
 	assert_eq!(get_push_bytes(PUSH32), 32);
 }
 
+/// Returns the mnemonic of given instruction, `INVALID` if the instruction is not defined.
+pub fn instruction_name(i: Instruction) -> &'static str {
+	match INSTRUCTIONS[i as usize].name {
+		"" => "INVALID",
+		name => name,
+	}
+}
+
+#[test]
+fn test_instruction_name() {
+	assert_eq!(instruction_name(PUSH1), "PUSH1");
+	assert_eq!(instruction_name(SSTORE), "SSTORE");
+	assert_eq!(instruction_name(0xef), "INVALID");
+}
+
 /// Returns stack position of item to duplicate
 /// DUP1 -> 0
 pub fn get_dup_position(i: Instruction) -> usize {
@@ -168,6 +183,9 @@ lazy_static! {
 		arr[OR as usize] =				InstructionInfo::new("OR",				0, 2, 1, false, GasPriceTier::VeryLow);
 		arr[XOR as usize] = 			InstructionInfo::new("XOR",				0, 2, 1, false, GasPriceTier::VeryLow);
 		arr[BYTE as usize] =			InstructionInfo::new("BYTE",			0, 2, 1, false, GasPriceTier::VeryLow);
+		arr[SHL as usize] =				InstructionInfo::new("SHL",				0, 2, 1, false, GasPriceTier::VeryLow);
+		arr[SHR as usize] =				InstructionInfo::new("SHR",				0, 2, 1, false, GasPriceTier::VeryLow);
+		arr[SAR as usize] =				InstructionInfo::new("SAR",				0, 2, 1, false, GasPriceTier::VeryLow);
 		arr[ADDMOD as usize] =			InstructionInfo::new("ADDMOD",			0, 3, 1, false, GasPriceTier::Mid);
 		arr[MULMOD as usize] =			InstructionInfo::new("MULMOD",			0, 3, 1, false, GasPriceTier::Mid);
 		arr[SIGNEXTEND as usize] =		InstructionInfo::new("SIGNEXTEND",		0, 2, 1, false, GasPriceTier::Low);
@@ -185,6 +203,8 @@ lazy_static! {
 		arr[GASPRICE as usize] =		InstructionInfo::new("GASPRICE",		0, 0, 1, false, GasPriceTier::Base);
 		arr[EXTCODESIZE as usize] = 	InstructionInfo::new("EXTCODESIZE",		0, 1, 1, false, GasPriceTier::Special);
 		arr[EXTCODECOPY as usize] = 	InstructionInfo::new("EXTCODECOPY",		0, 4, 0, true, GasPriceTier::Special);
+		arr[RETURNDATASIZE as usize] =	InstructionInfo::new("RETURNDATASIZE",	0, 0, 1, false, GasPriceTier::Base);
+		arr[RETURNDATACOPY as usize] =	InstructionInfo::new("RETURNDATACOPY",	0, 3, 0, true, GasPriceTier::VeryLow);
 		arr[BLOCKHASH as usize] =		InstructionInfo::new("BLOCKHASH",		0, 1, 1, false, GasPriceTier::Ext);
 		arr[COINBASE as usize] =		InstructionInfo::new("COINBASE",		0, 0, 1, false, GasPriceTier::Base);
 		arr[TIMESTAMP as usize] =		InstructionInfo::new("TIMESTAMP",		0, 0, 1, false, GasPriceTier::Base);
@@ -273,10 +293,13 @@ lazy_static! {
 		arr[LOG3 as usize] =			InstructionInfo::new("LOG3",			0, 5, 0, true, GasPriceTier::Special);
 		arr[LOG4 as usize] =			InstructionInfo::new("LOG4",			0, 6, 0, true, GasPriceTier::Special);
 		arr[CREATE as usize] =			InstructionInfo::new("CREATE",			0, 3, 1, true, GasPriceTier::Special);
+		arr[CREATE2 as usize] =			InstructionInfo::new("CREATE2",			0, 4, 1, true, GasPriceTier::Special);
 		arr[CALL as usize] =			InstructionInfo::new("CALL",			0, 7, 1, true, GasPriceTier::Special);
 		arr[CALLCODE as usize] =		InstructionInfo::new("CALLCODE",		0, 7, 1, true, GasPriceTier::Special);
 		arr[RETURN as usize] =			InstructionInfo::new("RETURN",			0, 2, 0, true, GasPriceTier::Zero);
 		arr[DELEGATECALL as usize] =	InstructionInfo::new("DELEGATECALL",	0, 6, 1, true, GasPriceTier::Special);
+		arr[STATICCALL as usize] =		InstructionInfo::new("STATICCALL",		0, 6, 1, true, GasPriceTier::Special);
+		arr[REVERT as usize] =			InstructionInfo::new("REVERT",			0, 2, 0, true, GasPriceTier::Zero);
 		arr[SUICIDE as usize] = 		InstructionInfo::new("SUICIDE",			0, 1, 0, true, GasPriceTier::Special);
 		arr
 	};
@@ -330,6 +353,12 @@ pub const XOR: Instruction = 0x18;
 pub const NOT: Instruction = 0x19;
 /// retrieve single byte from word
 pub const BYTE: Instruction = 0x1a;
+/// shift left operation
+pub const SHL: Instruction = 0x1b;
+/// logical shift right operation
+pub const SHR: Instruction = 0x1c;
+/// arithmetic shift right operation
+pub const SAR: Instruction = 0x1d;
 
 /// compute SHA3-256 hash
 pub const SHA3: Instruction = 0x20;
@@ -360,6 +389,10 @@ pub const GASPRICE: Instruction = 0x3a;
 pub const EXTCODESIZE: Instruction = 0x3b;
 /// copy external code (from another contract)
 pub const EXTCODECOPY: Instruction = 0x3c;
+/// get the size of the return data buffer for the last call
+pub const RETURNDATASIZE: Instruction = 0x3d;
+/// copy return data buffer to memory
+pub const RETURNDATACOPY: Instruction = 0x3e;
 
 /// get hash of most recent complete block
 pub const BLOCKHASH: Instruction = 0x40;
@@ -553,6 +586,12 @@ pub const CALLCODE: Instruction = 0xf2;
 pub const RETURN: Instruction = 0xf3;
 /// like CALLCODE but keeps caller's value and sender
 pub const DELEGATECALL: Instruction = 0xf4;
+/// create a new account with associated code at an address derived from sender, salt and init code
+pub const CREATE2: Instruction = 0xf5;
+/// like CALL but it does not take value, nor modify the state
+pub const STATICCALL: Instruction = 0xfa;
+/// halt execution, revert state changes and return output data
+pub const REVERT: Instruction = 0xfd;
 /// halt execution and register account for later deletion
 pub const SUICIDE: Instruction = 0xff;
 
@@ -125,7 +125,10 @@ impl<Gas: CostType> Gasometer<Gas> {
 				let newval = stack.peek(1);
 				let val = U256::from(&*ext.storage_at(&address));
 
-				let gas = if val.is_zero() && !newval.is_zero() {
+				let gas = if schedule.sstore_net_metering {
+					let orig = U256::from(&*ext.original_storage_at(&address));
+					calculate_eip1283_sstore_gas(schedule, &orig, &val, &newval)
+				} else if val.is_zero() && !newval.is_zero() {
 					schedule.sstore_set_gas
 				} else {
 					// Refund for below case is added when actually executing sstore
@@ -164,7 +167,7 @@ impl<Gas: CostType> Gasometer<Gas> {
 			instructions::MSTORE8 => {
 				Request::GasMem(default_gas, try!(mem_needed_const(stack.peek(0), 1)))
 			},
-			instructions::RETURN => {
+			instructions::RETURN | instructions::REVERT => {
 				Request::GasMem(default_gas, try!(mem_needed(stack.peek(0), stack.peek(1))))
 			},
 			instructions::SHA3 => {
@@ -173,7 +176,7 @@ impl<Gas: CostType> Gasometer<Gas> {
 				let gas = Gas::from(schedule.sha3_gas) + (Gas::from(schedule.sha3_word_gas) * words);
 				Request::GasMem(gas, try!(mem_needed(stack.peek(0), stack.peek(1))))
 			},
-			instructions::CALLDATACOPY | instructions::CODECOPY => {
+			instructions::CALLDATACOPY | instructions::CODECOPY | instructions::RETURNDATACOPY => {
 				Request::GasMemCopy(default_gas, try!(mem_needed(stack.peek(0), stack.peek(2))), try!(Gas::from_u256(*stack.peek(2))))
 			},
 			instructions::EXTCODECOPY => {
@@ -213,7 +216,7 @@ impl<Gas: CostType> Gasometer<Gas> {
 
 				Request::GasMemProvide(gas, mem, Some(requested))
 			},
-			instructions::DELEGATECALL => {
+			instructions::DELEGATECALL | instructions::STATICCALL => {
 				let gas = Gas::from(schedule.call_gas);
 				let mem = cmp::max(
 					try!(mem_needed(stack.peek(4), stack.peek(5))),
@@ -229,6 +232,15 @@ impl<Gas: CostType> Gasometer<Gas> {
 
 				Request::GasMemProvide(gas, mem, None)
 			},
+			instructions::CREATE2 => {
+				// init code has to be hashed to derive the address
+				let w = overflowing!(add_gas_usize(try!(Gas::from_u256(*stack.peek(2))), 31));
+				let words = w >> 5;
+				let gas = Gas::from(schedule.create_gas) + (Gas::from(schedule.sha3_word_gas) * words);
+				let mem = try!(mem_needed(stack.peek(1), stack.peek(2)));
+
+				Request::GasMemProvide(gas, mem, None)
+			},
 			instructions::EXP => {
 				let expon = stack.peek(1);
 				let bytes = ((expon.bits() + 7) / 8) as usize;
@@ -333,6 +345,62 @@ fn add_gas_usize<Gas: CostType>(value: Gas, num: usize) -> (Gas, bool) {
 	value.overflow_add(Gas::from(num))
 }
 
+/// Gas cost of `SSTORE` under EIP-1283 net gas metering.
+/// `original` is the value of the slot before the current transaction.
+pub fn calculate_eip1283_sstore_gas(schedule: &Schedule, original: &U256, current: &U256, new: &U256) -> usize {
+	if current == new {
+		// no-op
+		schedule.sstore_dirty_gas
+	} else if original == current {
+		// slot not yet changed by this transaction
+		if original.is_zero() {
+			schedule.sstore_set_gas
+		} else {
+			schedule.sstore_reset_gas
+		}
+	} else {
+		// slot already dirty
+		schedule.sstore_dirty_gas
+	}
+}
+
+/// Apply the EIP-1283 refund rules for `SSTORE`.
+/// Must be called before the new value is written.
+pub fn handle_eip1283_sstore_clears_refund(ext: &mut evm::Ext, original: &U256, current: &U256, new: &U256) {
+	let sstore_clears_schedule = ext.schedule().sstore_refund_gas;
+
+	if current == new {
+		// no-op, no refunds
+		return;
+	}
+
+	if original == current {
+		if !original.is_zero() && new.is_zero() {
+			ext.add_sstore_refund(sstore_clears_schedule);
+		}
+		return;
+	}
+
+	if !original.is_zero() {
+		if current.is_zero() {
+			// the slot was cleared earlier in this transaction and is being recreated
+			ext.sub_sstore_refund(sstore_clears_schedule);
+		} else if new.is_zero() {
+			ext.add_sstore_refund(sstore_clears_schedule);
+		}
+	}
+
+	if original == new {
+		// the slot is reset to its original value
+		let refund = if original.is_zero() {
+			ext.schedule().sstore_set_gas - ext.schedule().sstore_dirty_gas
+		} else {
+			ext.schedule().sstore_reset_gas - ext.schedule().sstore_dirty_gas
+		};
+		ext.add_sstore_refund(refund);
+	}
+}
+
 #[test]
 fn test_mem_gas_cost() {
 	// given
@@ -27,12 +27,13 @@ use self::gasometer::Gasometer;
 use self::stack::{Stack, VecStack};
 use self::memory::Memory;
 pub use self::shared_cache::SharedCache;
+pub use self::gasometer::{calculate_eip1283_sstore_gas, handle_eip1283_sstore_clears_refund};
 
 use std::marker::PhantomData;
 use action_params::{ActionParams, ActionValue};
 use types::executed::CallType;
 use evm::instructions::{self, Instruction, InstructionInfo};
-use evm::{self, MessageCallResult, ContractCreateResult, GasLeft, CostType};
+use evm::{self, MessageCallResult, ContractCreateResult, GasLeft, CostType, CreateContractAddress};
 use bit_set::BitSet;
 
 use util::*;
@@ -85,8 +86,16 @@ enum InstructionResult<Gas> {
 	Ok,
 	UnusedGas(Gas),
 	JumpToPosition(U256),
-	// gas left, init_orf, init_size
-	StopExecutionNeedsReturn(Gas, U256, U256),
+	StopExecutionNeedsReturn {
+		/// Gas left.
+		gas: Gas,
+		/// Return data offset.
+		init_off: U256,
+		/// Return data size.
+		init_size: U256,
+		/// Apply or revert state changes.
+		apply: bool,
+	},
 	StopExecution,
 }
 
@@ -95,12 +104,14 @@ enum InstructionResult<Gas> {
 pub struct Interpreter<Cost: CostType> {
 	mem: Vec<u8>,
 	cache: Arc<SharedCache>,
+	return_data: Bytes,
 	_type: PhantomData<Cost>,
 }
 
 impl<Cost: CostType> evm::Evm for Interpreter<Cost> {
 	fn exec(&mut self, params: ActionParams, ext: &mut evm::Ext) -> evm::Result<GasLeft> {
 		self.mem.clear();
+		self.return_data.clear();
 
 		let mut informant = informant::EvmInformant::new(ext.depth());
 
@@ -118,11 +129,15 @@ impl<Cost: CostType> evm::Evm for Interpreter<Cost> {
 
 			let info = &infos[instruction as usize];
 			try!(self.verify_instruction(ext, instruction, info, &stack));
+			try!(self.verify_static(ext, instruction, &stack));
 
 			// Calculate gas cost
 			let requirements = try!(gasometer.requirements(ext, instruction, info, &stack, self.mem.size()));
 			// TODO: make compile-time removable if too much of a performance hit.
 			let trace_executed = ext.trace_prepare_execute(reader.position - 1, instruction, &requirements.gas_cost.as_u256());
+			if trace_executed {
+				ext.trace_state(&gasometer.current_gas.as_u256(), stack.peek_top(stack.size()), &self.mem);
+			}
 
 			try!(gasometer.verify_gas(&requirements.gas_cost));
 			self.mem.expand(requirements.memory_required_size);
@@ -157,9 +172,13 @@ impl<Cost: CostType> evm::Evm for Interpreter<Cost> {
 					let pos = try!(self.verify_jump(position, &valid_jump_destinations));
 					reader.position = pos;
 				},
-				InstructionResult::StopExecutionNeedsReturn(gas, off, size) => {
+				InstructionResult::StopExecutionNeedsReturn {gas, init_off, init_size, apply} => {
 					informant.done();
-					return Ok(GasLeft::NeedsReturn(gas.as_u256(), self.mem.read_slice(off, size)));
+					return Ok(GasLeft::NeedsReturn {
+						gas_left: gas.as_u256(),
+						data: self.mem.read_slice(init_off, init_size),
+						apply_state: apply
+					});
 				},
 				InstructionResult::StopExecution => break,
 				_ => {},
@@ -176,6 +195,7 @@ impl<Cost: CostType> Interpreter<Cost> {
 		Interpreter {
 			mem: Vec::new(),
 			cache: cache,
+			return_data: Vec::new(),
 			_type: PhantomData::default(),
 		}
 	}
@@ -183,7 +203,13 @@ impl<Cost: CostType> Interpreter<Cost> {
 	fn verify_instruction(&self, ext: &evm::Ext, instruction: Instruction, info: &InstructionInfo, stack: &Stack<U256>) -> evm::Result<()> {
 		let schedule = ext.schedule();
 
-		if !schedule.have_delegate_call && instruction == instructions::DELEGATECALL {
+		if (instruction == instructions::DELEGATECALL && !schedule.have_delegate_call) ||
+			(instruction == instructions::STATICCALL && !schedule.have_static_call) ||
+			(instruction == instructions::CREATE2 && !schedule.have_create2) ||
+			((instruction == instructions::SHL || instruction == instructions::SHR || instruction == instructions::SAR) && !schedule.have_bitwise_shifting) ||
+			(instruction == instructions::REVERT && !schedule.have_revert) ||
+			((instruction == instructions::RETURNDATACOPY || instruction == instructions::RETURNDATASIZE) && !schedule.have_return_data) {
+
 			return Err(evm::Error::BadInstruction {
 				instruction: instruction
 			});
@@ -212,6 +238,25 @@ impl<Cost: CostType> Interpreter<Cost> {
 		}
 	}
 
+	fn verify_static(&self, ext: &evm::Ext, instruction: Instruction, stack: &Stack<U256>) -> evm::Result<()> {
+		if !ext.is_static() {
+			return Ok(());
+		}
+
+		let mutates_state = match instruction {
+			instructions::SSTORE | instructions::CREATE | instructions::CREATE2 | instructions::SUICIDE => true,
+			instructions::LOG0...instructions::LOG4 => true,
+			instructions::CALL => !stack.peek(2).is_zero(),
+			_ => false,
+		};
+
+		if mutates_state {
+			Err(evm::Error::MutableCallInStaticContext)
+		} else {
+			Ok(())
+		}
+	}
+
 	fn mem_written(
 		instruction: Instruction,
 		stack: &Stack<U256>
@@ -219,10 +264,10 @@ impl<Cost: CostType> Interpreter<Cost> {
 		match instruction {
 			instructions::MSTORE | instructions::MLOAD => Some((stack.peek(0).low_u64() as usize, 32)),
 			instructions::MSTORE8 => Some((stack.peek(0).low_u64() as usize, 1)),
-			instructions::CALLDATACOPY | instructions::CODECOPY => Some((stack.peek(0).low_u64() as usize, stack.peek(2).low_u64() as usize)),
+			instructions::CALLDATACOPY | instructions::CODECOPY | instructions::RETURNDATACOPY => Some((stack.peek(0).low_u64() as usize, stack.peek(2).low_u64() as usize)),
 			instructions::EXTCODECOPY => Some((stack.peek(1).low_u64() as usize, stack.peek(3).low_u64() as usize)),
 			instructions::CALL | instructions::CALLCODE => Some((stack.peek(5).low_u64() as usize, stack.peek(6).low_u64() as usize)),
-			instructions::DELEGATECALL => Some((stack.peek(4).low_u64() as usize, stack.peek(5).low_u64() as usize)),
+			instructions::DELEGATECALL | instructions::STATICCALL => Some((stack.peek(4).low_u64() as usize, stack.peek(5).low_u64() as usize)),
 			_ => None,
 		}
 	}
@@ -267,11 +312,19 @@ impl<Cost: CostType> Interpreter<Cost> {
 			instructions::JUMPDEST => {
 				// ignore
 			},
-			instructions::CREATE => {
+			instructions::CREATE | instructions::CREATE2 => {
 				let endowment = stack.pop_back();
 				let init_off = stack.pop_back();
 				let init_size = stack.pop_back();
-				let create_gas = provided.expect("`provided` comes through Self::exec from `Gasometer::get_gas_cost_mem`; `gas_gas_mem_cost` guarantees `Some` when instruction is `CALL`/`CALLCODE`/`DELEGATECALL`/`CREATE`; this is `CREATE`; qed");
+				let address_scheme = if instruction == instructions::CREATE {
+					CreateContractAddress::FromSenderAndNonce
+				} else {
+					CreateContractAddress::FromSenderSaltAndCodeHash(stack.pop_back().into())
+				};
+				let create_gas = provided.expect("`provided` comes through Self::exec from `Gasometer::get_gas_cost_mem`; `gas_gas_mem_cost` guarantees `Some` when instruction is `CALL`/`CALLCODE`/`DELEGATECALL`/`CREATE`/`CREATE2`; this is one of `CREATE`/`CREATE2`; qed");
+
+				// clear return data buffer before creating new call frame.
+				self.return_data = Vec::new();
 
 				let contract_code = self.mem.read_slice(init_off, init_size);
 				let can_create = ext.balance(&params.address) >= endowment && ext.depth() < ext.schedule().max_depth;
@@ -281,27 +334,34 @@ impl<Cost: CostType> Interpreter<Cost> {
 					return Ok(InstructionResult::UnusedGas(create_gas));
 				}
 
-				let create_result = ext.create(&create_gas.as_u256(), &endowment, contract_code);
+				let create_result = ext.create(&create_gas.as_u256(), &endowment, contract_code, address_scheme);
 				return match create_result {
 					ContractCreateResult::Created(address, gas_left) => {
 						stack.push(address_to_u256(address));
 						Ok(InstructionResult::UnusedGas(Cost::from_u256(gas_left).expect("Gas left cannot be greater.")))
 					},
+					ContractCreateResult::Reverted(gas_left, return_data) => {
+						self.return_data = return_data;
+						stack.push(U256::zero());
+						Ok(InstructionResult::UnusedGas(Cost::from_u256(gas_left).expect("Gas left cannot be greater.")))
+					},
 					ContractCreateResult::Failed => {
 						stack.push(U256::zero());
 						Ok(InstructionResult::Ok)
 					}
 				};
 			},
-			instructions::CALL | instructions::CALLCODE | instructions::DELEGATECALL => {
+			instructions::CALL | instructions::CALLCODE | instructions::DELEGATECALL | instructions::STATICCALL => {
 				assert!(ext.schedule().call_value_transfer_gas > ext.schedule().call_stipend, "overflow possible");
 				stack.pop_back();
-				let call_gas = provided.expect("`provided` comes through Self::exec from `Gasometer::get_gas_cost_mem`; `gas_gas_mem_cost` guarantees `Some` when instruction is `CALL`/`CALLCODE`/`DELEGATECALL`/`CREATE`; this is one of `CALL`/`CALLCODE`/`DELEGATECALL`; qed");
+				let call_gas = provided.expect("`provided` comes through Self::exec from `Gasometer::get_gas_cost_mem`; `gas_gas_mem_cost` guarantees `Some` when instruction is `CALL`/`CALLCODE`/`DELEGATECALL`/`STATICCALL`/`CREATE`; this is one of `CALL`/`CALLCODE`/`DELEGATECALL`/`STATICCALL`; qed");
 				let code_address = stack.pop_back();
 				let code_address = u256_to_address(&code_address);
 
 				let value = if instruction == instructions::DELEGATECALL {
 					None
+				} else if instruction == instructions::STATICCALL {
+					Some(U256::zero())
 				} else {
 					Some(stack.pop_back())
 				};
@@ -328,9 +388,13 @@ impl<Cost: CostType> Interpreter<Cost> {
 						(&params.address, &params.address, has_balance, CallType::CallCode)
 					},
 					instructions::DELEGATECALL => (&params.sender, &params.address, true, CallType::DelegateCall),
+					instructions::STATICCALL => (&params.address, &code_address, true, CallType::StaticCall),
 					_ => panic!(format!("Unexpected instruction {} in CALL branch.", instruction))
 				};
 
+				// clear return data buffer before creating new call frame.
+				self.return_data = Vec::new();
+
 				let can_call = has_balance && ext.depth() < ext.schedule().max_depth;
 				if !can_call {
 					stack.push(U256::zero());
@@ -346,8 +410,14 @@ impl<Cost: CostType> Interpreter<Cost> {
 				};
 
 				return match call_result {
-					MessageCallResult::Success(gas_left) => {
+					MessageCallResult::Success(gas_left, return_data) => {
 						stack.push(U256::one());
+						self.return_data = return_data;
+						Ok(InstructionResult::UnusedGas(Cost::from_u256(gas_left).expect("Gas left cannot be greater then current one")))
+					},
+					MessageCallResult::Reverted(gas_left, return_data) => {
+						stack.push(U256::zero());
+						self.return_data = return_data;
 						Ok(InstructionResult::UnusedGas(Cost::from_u256(gas_left).expect("Gas left cannot be greater then current one")))
 					},
 					MessageCallResult::Failed  => {
@@ -360,7 +430,13 @@ impl<Cost: CostType> Interpreter<Cost> {
 				let init_off = stack.pop_back();
 				let init_size = stack.pop_back();
 
-				return Ok(InstructionResult::StopExecutionNeedsReturn(gas, init_off, init_size))
+				return Ok(InstructionResult::StopExecutionNeedsReturn {gas: gas, init_off: init_off, init_size: init_size, apply: true})
+			},
+			instructions::REVERT => {
+				let init_off = stack.pop_back();
+				let init_size = stack.pop_back();
+
+				return Ok(InstructionResult::StopExecutionNeedsReturn {gas: gas, init_off: init_off, init_size: init_size, apply: false})
 			},
 			instructions::STOP => {
 				return Ok(InstructionResult::StopExecution);
@@ -420,8 +496,12 @@ impl<Cost: CostType> Interpreter<Cost> {
 
 				let current_val = U256::from(&*ext.storage_at(&address));
 				// Increase refund for clear
-				if !self.is_zero(&current_val) && self.is_zero(&val) {
-					ext.inc_sstore_clears();
+				if ext.schedule().sstore_net_metering {
+					let original_val = U256::from(&*ext.original_storage_at(&address));
+					gasometer::handle_eip1283_sstore_clears_refund(ext, &original_val, &current_val, &val);
+				} else if !self.is_zero(&current_val) && self.is_zero(&val) {
+					let sstore_clears_schedule = ext.schedule().sstore_refund_gas;
+					ext.add_sstore_refund(sstore_clears_schedule);
 				}
 				ext.set_storage(address, H256::from(&val));
 			},
@@ -478,16 +558,31 @@ impl<Cost: CostType> Interpreter<Cost> {
 				let len = ext.extcodesize(&address);
 				stack.push(U256::from(len));
 			},
+			instructions::RETURNDATASIZE => {
+				stack.push(U256::from(self.return_data.len()));
+			},
 			instructions::CALLDATACOPY => {
-				self.copy_data_to_memory(stack, params.data.as_ref().map_or_else(|| &[] as &[u8], |d| &*d as &[u8]));
+				Self::copy_data_to_memory(&mut self.mem, stack, params.data.as_ref().map_or_else(|| &[] as &[u8], |d| &*d as &[u8]));
+			},
+			instructions::RETURNDATACOPY => {
+				{
+					let source_offset = stack.peek(1);
+					let size = stack.peek(2);
+					let return_data_len = U256::from(self.return_data.len());
+					let (end, overflow) = source_offset.overflowing_add(*size);
+					if overflow || end > return_data_len {
+						return Err(evm::Error::OutOfBounds);
+					}
+				}
+				Self::copy_data_to_memory(&mut self.mem, stack, &self.return_data);
 			},
 			instructions::CODECOPY => {
-				self.copy_data_to_memory(stack, params.code.as_ref().map_or_else(|| &[] as &[u8], |c| &**c as &[u8]));
+				Self::copy_data_to_memory(&mut self.mem, stack, params.code.as_ref().map_or_else(|| &[] as &[u8], |c| &**c as &[u8]));
 			},
 			instructions::EXTCODECOPY => {
 				let address = u256_to_address(&stack.pop_back());
 				let code = ext.extcode(&address);
-				self.copy_data_to_memory(stack, &code);
+				Self::copy_data_to_memory(&mut self.mem, stack, &code);
 			},
 			instructions::GASPRICE => {
 				stack.push(params.gas_price.clone());
@@ -519,7 +614,7 @@ impl<Cost: CostType> Interpreter<Cost> {
 		Ok(InstructionResult::Ok)
 	}
 
-	fn copy_data_to_memory(&mut self, stack: &mut Stack<U256>, source: &[u8]) {
+	fn copy_data_to_memory(mem: &mut Vec<u8>, stack: &mut Stack<U256>, source: &[u8]) {
 		let dest_offset = stack.pop_back();
 		let source_offset = stack.pop_back();
 		let size = stack.pop_back();
@@ -528,9 +623,9 @@ impl<Cost: CostType> Interpreter<Cost> {
 		let output_end = match source_offset > source_size || size > source_size || source_offset + size > source_size {
 			true => {
 				let zero_slice = if source_offset > source_size {
-					self.mem.writeable_slice(dest_offset, size)
+					mem.writeable_slice(dest_offset, size)
 				} else {
-					self.mem.writeable_slice(dest_offset + source_size - source_offset, source_offset + size - source_size)
+					mem.writeable_slice(dest_offset + source_size - source_offset, source_offset + size - source_size)
 				};
 				for i in zero_slice.iter_mut() {
 					*i = 0;
@@ -542,7 +637,7 @@ impl<Cost: CostType> Interpreter<Cost> {
 
 		if source_offset < source_size {
 			let output_begin = source_offset.low_u64() as usize;
-			self.mem.write_slice(dest_offset, &source[output_begin..output_end]);
+			mem.write_slice(dest_offset, &source[output_begin..output_end]);
 		}
 	}
 
@@ -730,6 +825,35 @@ impl<Cost: CostType> Interpreter<Cost> {
 				};
 				stack.push(byte);
 			},
+			instructions::SHL => {
+				let shift = stack.pop_back();
+				let value = stack.pop_back();
+				stack.push(match shift < U256::from(256) {
+					true => value << (shift.low_u64() as usize),
+					false => U256::zero(),
+				});
+			},
+			instructions::SHR => {
+				let shift = stack.pop_back();
+				let value = stack.pop_back();
+				stack.push(match shift < U256::from(256) {
+					true => value >> (shift.low_u64() as usize),
+					false => U256::zero(),
+				});
+			},
+			instructions::SAR => {
+				let shift = stack.pop_back();
+				let value = stack.pop_back();
+				let negative = value.bit(255);
+				// for negative values shift the ones' complement and invert it back,
+				// so that vacated bits are filled with the sign bit
+				stack.push(match (shift < U256::from(256), negative) {
+					(true, false) => value >> (shift.low_u64() as usize),
+					(true, true) => !(!value >> (shift.low_u64() as usize)),
+					(false, false) => U256::zero(),
+					(false, true) => !U256::zero(),
+				});
+			},
 			instructions::ADDMOD => {
 				let a = stack.pop_back();
 				let b = stack.pop_back();
@@ -140,7 +140,8 @@ impl<'a> evmjit::Ext for ExtAdapter<'a> {
 		let old_value = self.ext.storage_at(&key);
 		// if SSTORE nonzero -> zero, increment refund count
 		if !old_value.is_zero() && value.is_zero() {
-			self.ext.inc_sstore_clears();
+			let sstore_clears_schedule = self.ext.schedule().sstore_refund_gas;
+			self.ext.add_sstore_refund(sstore_clears_schedule);
 		}
 		self.ext.set_storage(key, value);
 	}
@@ -174,11 +175,15 @@ impl<'a> evmjit::Ext for ExtAdapter<'a> {
 
 		// check if balance is sufficient and we are not too deep
 		if self.ext.balance(&self.address) >= value && self.ext.depth() < self.ext.schedule().max_depth {
-			match self.ext.create(&gas, &value, code) {
+			match self.ext.create(&gas, &value, code, evm::CreateContractAddress::FromSenderAndNonce) {
 				evm::ContractCreateResult::Created(new_address, gas_left) => unsafe {
 					*address = new_address.into_jit();
 					*io_gas = gas_left.low_u64();
 				},
+				evm::ContractCreateResult::Reverted(gas_left, _) => unsafe {
+					*address = Address::new().into_jit();
+					*io_gas = gas_left.low_u64();
+				},
 				evm::ContractCreateResult::Failed => unsafe {
 					*address = Address::new().into_jit();
 					*io_gas = 0;
@@ -260,10 +265,14 @@ impl<'a> evmjit::Ext for ExtAdapter<'a> {
 					  unsafe { slice::from_raw_parts_mut(out_beg, out_size as usize) },
 					  call_type,
 					  ) {
-			evm::MessageCallResult::Success(gas_left) => unsafe {
+			evm::MessageCallResult::Success(gas_left, _) => unsafe {
 				*io_gas = (gas + gas_left).low_u64();
 				true
 			},
+			evm::MessageCallResult::Reverted(gas_left, _) => unsafe {
+				*io_gas = (gas + gas_left).low_u64();
+				false
+			},
 			evm::MessageCallResult::Failed => unsafe {
 				*io_gas = gas.low_u64();
 				false
@@ -365,7 +374,11 @@ impl evm::Evm for JitEvm {
 		match res {
 			evmjit::ReturnCode::Stop => Ok(GasLeft::Known(U256::from(context.gas_left()))),
 			evmjit::ReturnCode::Return =>
-				Ok(GasLeft::NeedsReturn(U256::from(context.gas_left()), context.output_data())),
+				Ok(GasLeft::NeedsReturn {
+					gas_left: U256::from(context.gas_left()),
+					data: context.output_data(),
+					apply_state: true,
+				}),
 			evmjit::ReturnCode::Suicide => {
 				ext.suicide(&Address::from_jit(&context.suicide_refund_address()));
 				Ok(GasLeft::Known(U256::from(context.gas_left())))
@@ -25,14 +25,17 @@ pub mod schedule;
 mod instructions;
 #[cfg(feature = "jit" )]
 mod jit;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 #[cfg(test)]
 mod tests;
 #[cfg(all(feature="benches", test))]
 mod benches;
 
-pub use self::evm::{Evm, Error, Finalize, GasLeft, Result, CostType};
-pub use self::ext::{Ext, ContractCreateResult, MessageCallResult};
+pub use self::evm::{Evm, Error, Finalize, FinalizationResult, GasLeft, Result, CostType};
+pub use self::ext::{Ext, ContractCreateResult, MessageCallResult, CreateContractAddress};
 pub use self::factory::{Factory, VMType};
-pub use self::schedule::Schedule;
+pub use self::instructions::instruction_name;
+pub use self::schedule::{Schedule, WasmCosts};
 pub use types::executed::CallType;
@@ -22,6 +22,18 @@ pub struct Schedule {
 	pub exceptional_failed_code_deposit: bool,
 	/// Does it have a delegate cal
 	pub have_delegate_call: bool,
+	/// Does it have a `REVERT` instruction
+	pub have_revert: bool,
+	/// Does it have `RETURNDATASIZE` and `RETURNDATACOPY` instructions
+	pub have_return_data: bool,
+	/// Does it have a `STATICCALL` instruction
+	pub have_static_call: bool,
+	/// Does it have `SHL`, `SHR` and `SAR` instructions
+	pub have_bitwise_shifting: bool,
+	/// Does it have a `CREATE2` instruction
+	pub have_create2: bool,
+	/// Does it use EIP-1283 net gas metering for `SSTORE`
+	pub sstore_net_metering: bool,
 	/// VM stack limit
 	pub stack_limit: usize,
 	/// Max number of nested calls/creates
@@ -44,6 +56,8 @@ pub struct Schedule {
 	pub sstore_reset_gas: usize,
 	/// Gas refund for `SSTORE` clearing (when `storage!=0`, `new==0`)
 	pub sstore_refund_gas: usize,
+	/// Gas price for `SSTORE` on an already modified slot or when the value does not change (net gas metering)
+	pub sstore_dirty_gas: usize,
 	/// Gas price for `JUMPDEST` opcode
 	pub jumpdest_gas: usize,
 	/// Gas price for `LOG*`
@@ -99,6 +113,32 @@ pub struct Schedule {
 	pub no_empty: bool,
 	/// Kill empty accounts if touched.
 	pub kill_empty: bool,
+	/// Wasm cost table, if wasm contracts are enabled.
+	pub wasm: Option<WasmCosts>,
+}
+
+/// Cost table for the wasm runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WasmCosts {
+	/// Gas charged for every regular wasm instruction.
+	pub regular: u32,
+	/// Gas charged for every 64KiB page of linear memory grown.
+	pub grow_mem: u32,
+	/// Maximum number of 64KiB pages of linear memory a contract may use.
+	pub max_memory_pages: u32,
+	/// Gas charged for every byte copied across the sandbox boundary.
+	pub copy_byte: u32,
+}
+
+impl Default for WasmCosts {
+	fn default() -> Self {
+		WasmCosts {
+			regular: 1,
+			grow_mem: 8192,
+			max_memory_pages: 16,
+			copy_byte: 1,
+		}
+	}
 }
 
 impl Schedule {
@@ -117,6 +157,12 @@ impl Schedule {
 		Schedule {
 			exceptional_failed_code_deposit: true,
 			have_delegate_call: true,
+			have_revert: false,
+			have_return_data: false,
+			have_static_call: false,
+			have_bitwise_shifting: false,
+			have_create2: false,
+			sstore_net_metering: false,
 			stack_limit: 1024,
 			max_depth: 1024,
 			tier_step_gas: [0, 2, 3, 5, 8, 10, 20, 0],
@@ -128,6 +174,7 @@ impl Schedule {
 			sstore_set_gas: 20000,
 			sstore_reset_gas: 5000,
 			sstore_refund_gas: 15000,
+			sstore_dirty_gas: 200,
 			jumpdest_gas: 1,
 			log_gas: 375,
 			log_data_gas: 8,
@@ -155,6 +202,7 @@ impl Schedule {
 			sub_gas_cap_divisor: Some(64),
 			no_empty: no_empty,
 			kill_empty: kill_empty,
+			wasm: None,
 		}
 	}
 
@@ -162,6 +210,12 @@ impl Schedule {
 		Schedule {
 			exceptional_failed_code_deposit: efcd,
 			have_delegate_call: hdc,
+			have_revert: false,
+			have_return_data: false,
+			have_static_call: false,
+			have_bitwise_shifting: false,
+			have_create2: false,
+			sstore_net_metering: false,
 			stack_limit: 1024,
 			max_depth: 1024,
 			tier_step_gas: [0, 2, 3, 5, 8, 10, 20, 0],
@@ -173,6 +227,7 @@ impl Schedule {
 			sstore_set_gas: 20000,
 			sstore_reset_gas: 5000,
 			sstore_refund_gas: 15000,
+			sstore_dirty_gas: 200,
 			jumpdest_gas: 1,
 			log_gas: 375,
 			log_data_gas: 8,
@@ -200,6 +255,7 @@ impl Schedule {
 			sub_gas_cap_divisor: None,
 			no_empty: false,
 			kill_empty: false,
+			wasm: None,
 		}
 	}
 }
@@ -18,12 +18,12 @@ use util::*;
 use action_params::{ActionParams, ActionValue};
 use env_info::EnvInfo;
 use types::executed::CallType;
-use evm::{self, Ext, Schedule, Factory, GasLeft, VMType, ContractCreateResult, MessageCallResult};
+use evm::{self, Ext, Schedule, Factory, GasLeft, VMType, ContractCreateResult, MessageCallResult, CreateContractAddress};
 use std::fmt::Debug;
 
 pub struct FakeLogEntry {
-	topics: Vec<H256>,
-	data: Bytes
+	pub topics: Vec<H256>,
+	pub data: Bytes
 }
 
 #[derive(PartialEq, Eq, Hash, Debug)]
@@ -47,16 +47,18 @@ pub struct FakeCall {
 /// Can't do recursive calls.
 #[derive(Default)]
 pub struct FakeExt {
-	sstore_clears: usize,
+	sstore_clears: i64,
 	depth: usize,
-	store: HashMap<H256, H256>,
+	pub store: HashMap<H256, H256>,
+	original_store: HashMap<H256, H256>,
 	blockhashes: HashMap<U256, H256>,
 	codes: HashMap<Address, Arc<Bytes>>,
-	logs: Vec<FakeLogEntry>,
+	pub logs: Vec<FakeLogEntry>,
 	_suicides: HashSet<Address>,
 	info: EnvInfo,
-	schedule: Schedule,
-	balances: HashMap<Address, U256>,
+	pub schedule: Schedule,
+	pub is_static: bool,
+	pub balances: HashMap<Address, U256>,
 	calls: HashSet<FakeCall>,
 }
 
@@ -64,7 +66,7 @@ pub struct FakeExt {
 fn test_finalize(res: Result<GasLeft, evm::Error>) -> Result<U256, evm::Error> {
 	match res {
 		Ok(GasLeft::Known(gas)) => Ok(gas),
-		Ok(GasLeft::NeedsReturn(_, _)) => unimplemented!(), // since ret is unimplemented.
+		Ok(GasLeft::NeedsReturn{..}) => unimplemented!(), // since ret is unimplemented.
 		Err(e) => Err(e),
 	}
 }
@@ -86,6 +88,10 @@ impl Ext for FakeExt {
 		self.store.get(key).unwrap_or(&H256::new()).clone()
 	}
 
+	fn original_storage_at(&self, key: &H256) -> H256 {
+		self.original_store.get(key).unwrap_or(&H256::new()).clone()
+	}
+
 	fn set_storage(&mut self, key: H256, value: H256) {
 		self.store.insert(key, value);
 	}
@@ -110,7 +116,7 @@ impl Ext for FakeExt {
 		self.blockhashes.get(number).unwrap_or(&H256::new()).clone()
 	}
 
-	fn create(&mut self, gas: &U256, value: &U256, code: &[u8]) -> ContractCreateResult {
+	fn create(&mut self, gas: &U256, value: &U256, code: &[u8], _address: CreateContractAddress) -> ContractCreateResult {
 		self.calls.insert(FakeCall {
 			call_type: FakeCallType::Create,
 			gas: *gas,
@@ -143,7 +149,7 @@ impl Ext for FakeExt {
 			data: data.to_vec(),
 			code_address: Some(code_address.clone())
 		});
-		MessageCallResult::Success(*gas)
+		MessageCallResult::Success(*gas, vec![])
 	}
 
 	fn extcode(&self, address: &Address) -> Arc<Bytes> {
@@ -161,7 +167,7 @@ impl Ext for FakeExt {
 		});
 	}
 
-	fn ret(self, _gas: &U256, _data: &[u8]) -> evm::Result<U256> {
+	fn ret(self, _gas: &U256, _data: &[u8], _apply_state: bool) -> evm::Result<U256> {
 		unimplemented!();
 	}
 
@@ -181,8 +187,16 @@ impl Ext for FakeExt {
 		self.depth
 	}
 
-	fn inc_sstore_clears(&mut self) {
-		self.sstore_clears += 1;
+	fn is_static(&self) -> bool {
+		self.is_static
+	}
+
+	fn add_sstore_refund(&mut self, value: usize) {
+		self.sstore_clears += value as i64;
+	}
+
+	fn sub_sstore_refund(&mut self, value: usize) {
+		self.sstore_clears -= value as i64;
 	}
 }
 
@@ -846,6 +860,183 @@ fn test_badinstruction_int() {
 	}
 }
 
+#[test]
+fn test_revert_disabled_int() {
+	let factory = super::Factory::new(VMType::Interpreter, 1024 * 32);
+	let code = "60206000fd".from_hex().unwrap();
+
+	let mut params = ActionParams::default();
+	params.gas = U256::from(100_000);
+	params.code = Some(Arc::new(code));
+	let mut ext = FakeExt::new();
+
+	let err = {
+		let mut vm = factory.create(params.gas);
+		test_finalize(vm.exec(params, &mut ext)).unwrap_err()
+	};
+
+	match err {
+		evm::Error::BadInstruction { instruction: 0xfd } => (),
+		_ => assert!(false, "Expected bad instruction")
+	}
+}
+
+#[test] // JIT does not support REVERT
+fn test_revert_int() {
+	let factory = super::Factory::new(VMType::Interpreter, 1024 * 32);
+	let code = "602a60005260206000fd".from_hex().unwrap();
+
+	let mut params = ActionParams::default();
+	params.gas = U256::from(100_000);
+	params.code = Some(Arc::new(code));
+	let mut ext = FakeExt::new();
+	ext.schedule.have_revert = true;
+
+	let (gas_left, data, apply_state) = {
+		let mut vm = factory.create(params.gas);
+		match vm.exec(params, &mut ext).unwrap() {
+			GasLeft::NeedsReturn { gas_left, data, apply_state } => (gas_left, data.to_vec(), apply_state),
+			_ => panic!("Expected REVERT to return data"),
+		}
+	};
+
+	assert_eq!(gas_left, U256::from(99_982));
+	assert_eq!(data, "000000000000000000000000000000000000000000000000000000000000002a".from_hex().unwrap());
+	assert!(!apply_state);
+}
+
+#[test] // JIT does not support RETURNDATACOPY
+fn test_returndatacopy_out_of_bounds_int() {
+	let factory = super::Factory::new(VMType::Interpreter, 1024 * 32);
+	let code = "6001600060003e".from_hex().unwrap();
+
+	let mut params = ActionParams::default();
+	params.gas = U256::from(100_000);
+	params.code = Some(Arc::new(code));
+	let mut ext = FakeExt::new();
+	ext.schedule.have_return_data = true;
+
+	let err = {
+		let mut vm = factory.create(params.gas);
+		test_finalize(vm.exec(params, &mut ext)).unwrap_err()
+	};
+
+	match err {
+		evm::Error::OutOfBounds => (),
+		_ => assert!(false, "Expected out of bounds")
+	}
+}
+
+#[test]
+fn test_staticcall_disabled_int() {
+	let factory = super::Factory::new(VMType::Interpreter, 1024 * 32);
+	let code = "600060006000600060006000fa".from_hex().unwrap();
+
+	let mut params = ActionParams::default();
+	params.gas = U256::from(100_000);
+	params.code = Some(Arc::new(code));
+	let mut ext = FakeExt::new();
+
+	let err = {
+		let mut vm = factory.create(params.gas);
+		test_finalize(vm.exec(params, &mut ext)).unwrap_err()
+	};
+
+	match err {
+		evm::Error::BadInstruction { instruction: 0xfa } => (),
+		_ => assert!(false, "Expected bad instruction")
+	}
+}
+
+#[test]
+fn test_create2_disabled_int() {
+	let factory = super::Factory::new(VMType::Interpreter, 1024 * 32);
+	let code = "60006000600060006000f5".from_hex().unwrap();
+
+	let mut params = ActionParams::default();
+	params.gas = U256::from(100_000);
+	params.code = Some(Arc::new(code));
+	let mut ext = FakeExt::new();
+
+	let err = {
+		let mut vm = factory.create(params.gas);
+		test_finalize(vm.exec(params, &mut ext)).unwrap_err()
+	};
+
+	match err {
+		evm::Error::BadInstruction { instruction: 0xf5 } => (),
+		_ => assert!(false, "Expected bad instruction")
+	}
+}
+
+#[test] // JIT does not support static context
+fn test_sstore_in_static_context_int() {
+	let factory = super::Factory::new(VMType::Interpreter, 1024 * 32);
+	let code = "602a600055".from_hex().unwrap();
+
+	let mut params = ActionParams::default();
+	params.gas = U256::from(100_000);
+	params.code = Some(Arc::new(code));
+	let mut ext = FakeExt::new();
+	ext.is_static = true;
+
+	let err = {
+		let mut vm = factory.create(params.gas);
+		test_finalize(vm.exec(params, &mut ext)).unwrap_err()
+	};
+
+	match err {
+		evm::Error::MutableCallInStaticContext => (),
+		_ => assert!(false, "Expected mutable call in static context")
+	}
+	assert!(ext.store.is_empty());
+}
+
+#[test] // JIT does not support net gas metering
+fn test_sstore_eip1283_int() {
+	// test vectors from EIP-1283: code, original value, gas used, refund
+	let vectors: Vec<(&str, u64, u64, i64)> = vec![
+		("60006000556000600055", 0, 412, 0),
+		("60006000556001600055", 0, 20212, 0),
+		("60016000556000600055", 0, 20212, 19800),
+		("60016000556002600055", 0, 20212, 0),
+		("60016000556001600055", 0, 20212, 0),
+		("60006000556000600055", 1, 5212, 15000),
+		("60006000556001600055", 1, 5212, 4800),
+		("60006000556002600055", 1, 5212, 0),
+		("60026000556000600055", 1, 5212, 15000),
+		("60026000556003600055", 1, 5212, 0),
+		("60026000556001600055", 1, 5212, 4800),
+		("60026000556002600055", 1, 5212, 0),
+		("60016000556000600055", 1, 5212, 15000),
+		("60016000556002600055", 1, 5212, 0),
+		("60016000556001600055", 1, 412, 0),
+		("600160005560006000556001600055", 0, 40218, 19800),
+		("600060005560016000556000600055", 1, 10218, 19800),
+	];
+
+	for (code, original, gas_used, refund) in vectors {
+		let factory = super::Factory::new(VMType::Interpreter, 1024 * 32);
+		let mut params = ActionParams::default();
+		params.gas = U256::from(100_000);
+		params.code = Some(Arc::new(code.from_hex().unwrap()));
+		let mut ext = FakeExt::new();
+		ext.schedule.sstore_net_metering = true;
+		if original != 0 {
+			ext.store.insert(H256::new(), H256::from(original));
+			ext.original_store.insert(H256::new(), H256::from(original));
+		}
+
+		let gas_left = {
+			let mut vm = factory.create(params.gas);
+			test_finalize(vm.exec(params, &mut ext)).unwrap()
+		};
+
+		assert_eq!(gas_left, U256::from(100_000 - gas_used), "gas used by {}", code);
+		assert_eq!(ext.sstore_clears, refund, "refund of {}", code);
+	}
+}
+
 evm_test!{test_pop: test_pop_jit, test_pop_int}
 fn test_pop(factory: super::Factory) {
 	let code = "60f060aa50600055".from_hex().unwrap();
@@ -903,7 +1094,7 @@ fn test_jumps(factory: super::Factory) {
 		test_finalize(vm.exec(params, &mut ext)).unwrap()
 	};
 
-	assert_eq!(ext.sstore_clears, 1);
+	assert_eq!(ext.sstore_clears, 15000);
 	assert_store(&ext, 0, "0000000000000000000000000000000000000000000000000000000000000000"); // 5!
 	assert_store(&ext, 1, "0000000000000000000000000000000000000000000000000000000000000078"); // 5!
 	assert_eq!(gas_left, U256::from(54_117));
@@ -954,6 +1145,54 @@ fn test_calls(factory: super::Factory) {
 	assert_eq!(ext.calls.len(), 2);
 }
 
+evm_test!{ignorejit => test_shl: test_shl_jit, test_shl_int}
+fn test_shl(factory: super::Factory) {
+	push_two_pop_one_shift_test(&factory, 0x1b, "0000000000000000000000000000000000000000000000000000000000000001", "0000000000000000000000000000000000000000000000000000000000000000", "0000000000000000000000000000000000000000000000000000000000000001");
+	push_two_pop_one_shift_test(&factory, 0x1b, "0000000000000000000000000000000000000000000000000000000000000001", "0000000000000000000000000000000000000000000000000000000000000001", "0000000000000000000000000000000000000000000000000000000000000002");
+	push_two_pop_one_shift_test(&factory, 0x1b, "0000000000000000000000000000000000000000000000000000000000000001", "00000000000000000000000000000000000000000000000000000000000000ff", "8000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_shift_test(&factory, 0x1b, "0000000000000000000000000000000000000000000000000000000000000001", "0000000000000000000000000000000000000000000000000000000000000100", "0000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_shift_test(&factory, 0x1b, "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff", "0000000000000000000000000000000000000000000000000000000000000001", "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe");
+}
+
+evm_test!{ignorejit => test_shr: test_shr_jit, test_shr_int}
+fn test_shr(factory: super::Factory) {
+	push_two_pop_one_shift_test(&factory, 0x1c, "0000000000000000000000000000000000000000000000000000000000000001", "0000000000000000000000000000000000000000000000000000000000000000", "0000000000000000000000000000000000000000000000000000000000000001");
+	push_two_pop_one_shift_test(&factory, 0x1c, "0000000000000000000000000000000000000000000000000000000000000001", "0000000000000000000000000000000000000000000000000000000000000001", "0000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_shift_test(&factory, 0x1c, "8000000000000000000000000000000000000000000000000000000000000000", "0000000000000000000000000000000000000000000000000000000000000001", "4000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_shift_test(&factory, 0x1c, "8000000000000000000000000000000000000000000000000000000000000000", "00000000000000000000000000000000000000000000000000000000000000ff", "0000000000000000000000000000000000000000000000000000000000000001");
+	push_two_pop_one_shift_test(&factory, 0x1c, "8000000000000000000000000000000000000000000000000000000000000000", "0000000000000000000000000000000000000000000000000000000000000100", "0000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_shift_test(&factory, 0x1c, "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff", "00000000000000000000000000000000000000000000000000000000000000ff", "0000000000000000000000000000000000000000000000000000000000000001");
+}
+
+evm_test!{ignorejit => test_sar: test_sar_jit, test_sar_int}
+fn test_sar(factory: super::Factory) {
+	push_two_pop_one_shift_test(&factory, 0x1d, "8000000000000000000000000000000000000000000000000000000000000000", "0000000000000000000000000000000000000000000000000000000000000001", "c000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_shift_test(&factory, 0x1d, "8000000000000000000000000000000000000000000000000000000000000000", "00000000000000000000000000000000000000000000000000000000000000ff", "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+	push_two_pop_one_shift_test(&factory, 0x1d, "8000000000000000000000000000000000000000000000000000000000000000", "0000000000000000000000000000000000000000000000000000000000000100", "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+	push_two_pop_one_shift_test(&factory, 0x1d, "4000000000000000000000000000000000000000000000000000000000000000", "00000000000000000000000000000000000000000000000000000000000000fe", "0000000000000000000000000000000000000000000000000000000000000001");
+	push_two_pop_one_shift_test(&factory, 0x1d, "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff", "00000000000000000000000000000000000000000000000000000000000000f8", "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+	push_two_pop_one_shift_test(&factory, 0x1d, "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff", "00000000000000000000000000000000000000000000000000000000000000fe", "0000000000000000000000000000000000000000000000000000000000000001");
+	push_two_pop_one_shift_test(&factory, 0x1d, "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff", "0000000000000000000000000000000000000000000000000000000000000100", "0000000000000000000000000000000000000000000000000000000000000000");
+	push_two_pop_one_shift_test(&factory, 0x1d, "0000000000000000000000000000000000000000000000000000000000000001", "0000000000000000000000000000000000000000000000000000000000000001", "0000000000000000000000000000000000000000000000000000000000000000");
+}
+
+fn push_two_pop_one_shift_test(factory: &super::Factory, opcode: u8, value: &str, shift: &str, result: &str) {
+	let code = format!("7f{}7f{}{:02x}600055", value, shift, opcode).from_hex().unwrap();
+
+	let mut params = ActionParams::default();
+	params.gas = U256::from(100_000);
+	params.code = Some(Arc::new(code));
+	let mut ext = FakeExt::new();
+	ext.schedule.have_bitwise_shifting = true;
+
+	let _ = {
+		let mut vm = factory.create(params.gas);
+		test_finalize(vm.exec(params, &mut ext)).unwrap()
+	};
+
+	assert_store(&ext, 0, result);
+}
+
 fn assert_set_contains<T : Debug + Eq + PartialEq + Hash>(set: &HashSet<T>, val: &T) {
 	let contains = set.contains(val);
 	if !contains {
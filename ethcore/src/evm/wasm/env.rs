@@ -0,0 +1,182 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Host module exposed to wasm contracts under the `env` namespace.
+
+use std::cell::RefCell;
+use wasmi::{
+	self, Signature, FuncRef, FuncInstance, MemoryDescriptor,
+	MemoryRef, MemoryInstance, ModuleImportResolver,
+};
+use wasmi::memory_units::Pages;
+
+/// Indices of host functions, passed back to `Runtime::invoke_index`.
+pub mod ids {
+	pub const GAS_FUNC: usize = 0;
+	pub const STORAGE_READ_FUNC: usize = 1;
+	pub const STORAGE_WRITE_FUNC: usize = 2;
+	pub const BALANCE_FUNC: usize = 3;
+	pub const CCALL_FUNC: usize = 4;
+	pub const DCALL_FUNC: usize = 5;
+	pub const SCALL_FUNC: usize = 6;
+	pub const CREATE_FUNC: usize = 7;
+	pub const ELOG_FUNC: usize = 8;
+	pub const INPUT_LENGTH_FUNC: usize = 9;
+	pub const FETCH_INPUT_FUNC: usize = 10;
+	pub const RET_FUNC: usize = 11;
+	pub const SENDER_FUNC: usize = 12;
+	pub const ORIGIN_FUNC: usize = 13;
+	pub const ADDRESS_FUNC: usize = 14;
+	pub const VALUE_FUNC: usize = 15;
+	pub const SUICIDE_FUNC: usize = 16;
+}
+
+/// Signatures of host functions.
+pub mod signatures {
+	use wasmi::{self, ValueType};
+	use wasmi::ValueType::*;
+
+	pub struct StaticSignature(pub &'static [ValueType], pub Option<ValueType>);
+
+	pub const GAS: StaticSignature = StaticSignature(&[I32], None);
+
+	pub const STORAGE_READ: StaticSignature = StaticSignature(&[I32, I32], None);
+
+	pub const STORAGE_WRITE: StaticSignature = StaticSignature(&[I32, I32], None);
+
+	pub const BALANCE: StaticSignature = StaticSignature(&[I32, I32], None);
+
+	pub const CCALL: StaticSignature = StaticSignature(&[I64, I32, I32, I32, I32, I32, I32], Some(I32));
+
+	pub const DCALL: StaticSignature = StaticSignature(&[I64, I32, I32, I32, I32, I32], Some(I32));
+
+	pub const SCALL: StaticSignature = StaticSignature(&[I64, I32, I32, I32, I32, I32], Some(I32));
+
+	pub const CREATE: StaticSignature = StaticSignature(&[I32, I32, I32, I32], Some(I32));
+
+	pub const ELOG: StaticSignature = StaticSignature(&[I32, I32, I32, I32], None);
+
+	pub const INPUT_LENGTH: StaticSignature = StaticSignature(&[], Some(I32));
+
+	pub const FETCH_INPUT: StaticSignature = StaticSignature(&[I32], None);
+
+	pub const RET: StaticSignature = StaticSignature(&[I32, I32], None);
+
+	pub const SENDER: StaticSignature = StaticSignature(&[I32], None);
+
+	pub const ORIGIN: StaticSignature = StaticSignature(&[I32], None);
+
+	pub const ADDRESS: StaticSignature = StaticSignature(&[I32], None);
+
+	pub const VALUE: StaticSignature = StaticSignature(&[I32], None);
+
+	pub const SUICIDE: StaticSignature = StaticSignature(&[I32], None);
+
+	impl Into<wasmi::Signature> for StaticSignature {
+		fn into(self) -> wasmi::Signature {
+			wasmi::Signature::new(self.0, self.1)
+		}
+	}
+}
+
+fn host(signature: signatures::StaticSignature, idx: usize) -> FuncRef {
+	FuncInstance::alloc_host(signature.into(), idx)
+}
+
+/// Resolves `env` imports of a contract module.
+///
+/// Keeps track of the linear memory requested by the module, so that the runtime
+/// can read from and write to it.
+pub struct ImportResolver {
+	max_memory: u32,
+	memory: RefCell<Option<MemoryRef>>,
+}
+
+impl ImportResolver {
+	/// New import resolver which allows at most `max_memory` pages of linear memory.
+	pub fn with_limit(max_memory: u32) -> ImportResolver {
+		ImportResolver {
+			max_memory: max_memory,
+			memory: RefCell::new(None),
+		}
+	}
+
+	/// Returns the memory imported by the module, or an empty memory if it did not import any.
+	pub fn memory_ref(&self) -> MemoryRef {
+		let mut mem_ref = self.memory.borrow_mut();
+		if mem_ref.is_none() {
+			*mem_ref = Some(
+				MemoryInstance::alloc(Pages(0), Some(Pages(0)))
+					.expect("Allocating zero pages of memory cannot fail; qed")
+			);
+		}
+		mem_ref.clone().expect("Memory is set to `Some` above; qed")
+	}
+}
+
+impl ModuleImportResolver for ImportResolver {
+	fn resolve_func(&self, field_name: &str, signature: &Signature) -> Result<FuncRef, wasmi::Error> {
+		let func_ref = match field_name {
+			"gas" => host(signatures::GAS, ids::GAS_FUNC),
+			"storage_read" => host(signatures::STORAGE_READ, ids::STORAGE_READ_FUNC),
+			"storage_write" => host(signatures::STORAGE_WRITE, ids::STORAGE_WRITE_FUNC),
+			"balance" => host(signatures::BALANCE, ids::BALANCE_FUNC),
+			"ccall" => host(signatures::CCALL, ids::CCALL_FUNC),
+			"dcall" => host(signatures::DCALL, ids::DCALL_FUNC),
+			"scall" => host(signatures::SCALL, ids::SCALL_FUNC),
+			"create" => host(signatures::CREATE, ids::CREATE_FUNC),
+			"elog" => host(signatures::ELOG, ids::ELOG_FUNC),
+			"input_length" => host(signatures::INPUT_LENGTH, ids::INPUT_LENGTH_FUNC),
+			"fetch_input" => host(signatures::FETCH_INPUT, ids::FETCH_INPUT_FUNC),
+			"ret" => host(signatures::RET, ids::RET_FUNC),
+			"sender" => host(signatures::SENDER, ids::SENDER_FUNC),
+			"origin" => host(signatures::ORIGIN, ids::ORIGIN_FUNC),
+			"address" => host(signatures::ADDRESS, ids::ADDRESS_FUNC),
+			"value" => host(signatures::VALUE, ids::VALUE_FUNC),
+			"suicide" => host(signatures::SUICIDE, ids::SUICIDE_FUNC),
+			_ => {
+				return Err(wasmi::Error::Instantiation(
+					format!("Export {} not found", field_name),
+				))
+			}
+		};
+
+		if func_ref.signature() != signature {
+			return Err(wasmi::Error::Instantiation(
+				format!("Export {} has a bad signature", field_name),
+			));
+		}
+
+		Ok(func_ref)
+	}
+
+	fn resolve_memory(&self, field_name: &str, descriptor: &MemoryDescriptor) -> Result<MemoryRef, wasmi::Error> {
+		if field_name != "memory" {
+			return Err(wasmi::Error::Instantiation(
+				format!("Export {} not found", field_name),
+			));
+		}
+
+		let effective_max = descriptor.maximum().unwrap_or(self.max_memory);
+		if descriptor.initial() > self.max_memory || effective_max > self.max_memory {
+			return Err(wasmi::Error::Instantiation("Module requested too much memory".to_owned()));
+		}
+
+		let mem = try!(MemoryInstance::alloc(Pages(descriptor.initial() as usize), Some(Pages(effective_max as usize))));
+		*self.memory.borrow_mut() = Some(mem.clone());
+		Ok(mem)
+	}
+}
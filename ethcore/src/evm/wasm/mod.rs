@@ -0,0 +1,160 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Wasm contract runtime.
+//!
+//! Contracts are wasm modules starting with the wasm magic number. A contract
+//! exports a `call` function taking no arguments and may import its linear memory
+//! as `env.memory`. State is accessed through host functions imported from `env`:
+//!
+//! - `storage_read(key_ptr, val_ptr)`, `storage_write(key_ptr, val_ptr)`
+//! - `balance(address_ptr, result_ptr)`
+//! - `ccall`, `dcall`, `scall` for message calls and `create` for contract creation
+//! - `elog(topic_ptr, topic_count, data_ptr, data_len)`
+//! - `input_length()`, `fetch_input(dst_ptr)` and `ret(ptr, len)`
+//! - `sender(ptr)`, `origin(ptr)`, `address(ptr)`, `value(ptr)` and `suicide(address_ptr)`
+//!
+//! Words are 32 bytes big-endian and addresses are 20 bytes. Gas is charged by
+//! calls to `env.gas`, which are injected into the contract code before it runs.
+
+mod env;
+mod runtime;
+#[cfg(test)]
+mod tests;
+
+use parity_wasm::elements::{self, Deserialize};
+use pwasm_utils::{self, rules};
+use wasmi;
+use util::{U256, Uint};
+use action_params::ActionParams;
+use evm::{self, GasLeft};
+use evm::schedule::WasmCosts;
+use self::runtime::{Runtime, RuntimeContext, UserTrap};
+
+/// Wasm binary magic number, `\0asm`.
+pub const WASM_MAGIC_NUMBER: &'static [u8; 4] = b"\0asm";
+
+/// Returns true if given code is a wasm module rather than EVM bytecode.
+pub fn is_wasm_code(code: &[u8]) -> bool {
+	code.len() > WASM_MAGIC_NUMBER.len() && &code[0..WASM_MAGIC_NUMBER.len()] == WASM_MAGIC_NUMBER
+}
+
+/// Wasm interpreter instance.
+#[derive(Default)]
+pub struct WasmInterpreter {
+	result: Vec<u8>,
+}
+
+impl WasmInterpreter {
+	/// New wasm interpreter.
+	pub fn new() -> Self {
+		WasmInterpreter::default()
+	}
+}
+
+/// Deserialize the contract and inject gas metering into its code.
+fn prepare_module(code: &[u8], costs: &WasmCosts) -> evm::Result<wasmi::Module> {
+	let mut cursor = ::std::io::Cursor::new(code);
+	let module = try!(elements::Module::deserialize(&mut cursor).map_err(|e| {
+		debug!(target: "wasm", "Error deserializing contract code: {:?}", e);
+		evm::Error::Wasm("Error deserializing contract code")
+	}));
+
+	let gas_rules = rules::Set::new(costs.regular, Default::default())
+		.with_grow_cost(costs.grow_mem)
+		.with_forbidden_floats();
+	let module = try!(pwasm_utils::inject_gas_counter(module, &gas_rules).map_err(|_| {
+		evm::Error::Wasm("Contract code uses forbidden instructions")
+	}));
+
+	wasmi::Module::from_parity_wasm_module(module).map_err(|e| {
+		debug!(target: "wasm", "Invalid contract code: {:?}", e);
+		evm::Error::Wasm("Invalid contract code")
+	})
+}
+
+/// Map a failed contract execution to an evm error. `Ok` means the contract stopped with `ret` or `suicide`.
+fn map_error(err: wasmi::Error) -> evm::Result<()> {
+	match err.as_host_error().and_then(|e| e.downcast_ref::<UserTrap>()) {
+		Some(&UserTrap::Return) | Some(&UserTrap::Suicide) => Ok(()),
+		Some(&UserTrap::GasLimit) => Err(evm::Error::OutOfGas),
+		Some(&UserTrap::MutableCallInStaticContext) => Err(evm::Error::MutableCallInStaticContext),
+		Some(trap) => {
+			debug!(target: "wasm", "Contract execution stopped: {}", trap);
+			Err(evm::Error::Wasm("Contract trapped"))
+		},
+		None => {
+			debug!(target: "wasm", "Contract execution failed: {:?}", err);
+			Err(evm::Error::Wasm("Contract trapped"))
+		},
+	}
+}
+
+impl evm::Evm for WasmInterpreter {
+	fn exec(&mut self, params: ActionParams, ext: &mut evm::Ext) -> evm::Result<GasLeft> {
+		let code = params.code.clone().expect("exec always called with code; qed");
+		let costs = ext.schedule().wasm.clone().unwrap_or_default();
+		trace!(target: "wasm", "Started wasm interpreter with code.len={:?}", code.len());
+
+		let module = try!(prepare_module(&code, &costs));
+		let resolver = env::ImportResolver::with_limit(costs.max_memory_pages);
+		let instance = try!(wasmi::ModuleInstance::new(
+			&module,
+			&wasmi::ImportsBuilder::new().with_resolver("env", &resolver),
+		).map_err(|e| {
+			debug!(target: "wasm", "Error instantiating contract: {:?}", e);
+			evm::Error::Wasm("Error instantiating contract")
+		}));
+
+		let gas_limit = if params.gas > U256::from(u64::max_value()) {
+			u64::max_value()
+		} else {
+			params.gas.low_u64()
+		};
+
+		let (gas_left, result) = {
+			let context = RuntimeContext {
+				address: params.address.clone(),
+				sender: params.sender.clone(),
+				origin: params.origin.clone(),
+				value: params.value.value(),
+			};
+			let args = params.data.clone().unwrap_or_default();
+			let mut runtime = Runtime::new(ext, resolver.memory_ref(), gas_limit, costs, context, args);
+
+			let execution = instance.run_start(&mut runtime)
+				.map_err(wasmi::Error::from)
+				.and_then(|instance| instance.invoke_export("call", &[], &mut runtime));
+			if let Err(err) = execution {
+				try!(map_error(err));
+			}
+
+			(runtime.gas_left(), runtime.into_result())
+		};
+
+		self.result = result;
+		if self.result.is_empty() {
+			trace!(target: "wasm", "Contract execution result is empty.");
+			Ok(GasLeft::Known(gas_left))
+		} else {
+			Ok(GasLeft::NeedsReturn {
+				gas_left: gas_left,
+				data: &self.result,
+				apply_state: true,
+			})
+		}
+	}
+}
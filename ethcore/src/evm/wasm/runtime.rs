@@ -0,0 +1,513 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Wasm contract runtime. Services host function calls of a contract through `evm::Ext`.
+
+use std::{cmp, fmt};
+use util::{U256, H256, Address, Uint, FixedHash};
+use wasmi::{MemoryRef, RuntimeArgs, RuntimeValue, Externals, Trap, HostError};
+use evm::{self, MessageCallResult, ContractCreateResult, CreateContractAddress};
+use evm::interpreter::{calculate_eip1283_sstore_gas, handle_eip1283_sstore_clears_refund};
+use evm::schedule::WasmCosts;
+use types::executed::CallType;
+use super::env::ids::*;
+
+/// Maximum number of topics a single log entry can have.
+const MAX_LOG_TOPICS: usize = 4;
+
+/// Reasons for the runtime to stop contract execution early.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UserTrap {
+	/// Contract returned data with `ret`.
+	Return,
+	/// Contract called `suicide`.
+	Suicide,
+	/// Contract ran out of gas.
+	GasLimit,
+	/// Contract passed a pointer outside of its linear memory.
+	MemoryAccessViolation,
+	/// Contract attempted to modify state inside of a static call.
+	MutableCallInStaticContext,
+	/// Contract attempted to log with more than four topics.
+	TooManyTopics,
+	/// Contract called a host function the runtime does not know about.
+	UnknownFunction(usize),
+}
+
+impl fmt::Display for UserTrap {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			UserTrap::Return => write!(f, "Contract returned"),
+			UserTrap::Suicide => write!(f, "Contract suicided"),
+			UserTrap::GasLimit => write!(f, "Gas limit reached"),
+			UserTrap::MemoryAccessViolation => write!(f, "Memory access violation"),
+			UserTrap::MutableCallInStaticContext => write!(f, "Mutable call in static context"),
+			UserTrap::TooManyTopics => write!(f, "Too many log topics"),
+			UserTrap::UnknownFunction(idx) => write!(f, "Unknown host function {}", idx),
+		}
+	}
+}
+
+impl HostError for UserTrap {}
+
+/// Parameters of the action the contract is executed in.
+pub struct RuntimeContext {
+	/// Address of the executed contract.
+	pub address: Address,
+	/// Sender of the current call.
+	pub sender: Address,
+	/// Transaction initiator.
+	pub origin: Address,
+	/// Value passed with the current call.
+	pub value: U256,
+}
+
+/// Wasm contract runtime.
+pub struct Runtime<'a> {
+	ext: &'a mut evm::Ext,
+	memory: MemoryRef,
+	gas_counter: u64,
+	gas_limit: u64,
+	costs: WasmCosts,
+	context: RuntimeContext,
+	args: Vec<u8>,
+	result: Vec<u8>,
+}
+
+type Result<T> = ::std::result::Result<T, Trap>;
+
+impl<'a> Runtime<'a> {
+	/// New runtime for a contract with given linear memory, gas limit and input.
+	pub fn new(
+		ext: &'a mut evm::Ext,
+		memory: MemoryRef,
+		gas_limit: u64,
+		costs: WasmCosts,
+		context: RuntimeContext,
+		args: Vec<u8>,
+	) -> Runtime<'a> {
+		Runtime {
+			ext: ext,
+			memory: memory,
+			gas_counter: 0,
+			gas_limit: gas_limit,
+			costs: costs,
+			context: context,
+			args: args,
+			result: Vec::new(),
+		}
+	}
+
+	/// Gas left after the execution.
+	pub fn gas_left(&self) -> U256 {
+		U256::from(self.gas_limit - self.gas_counter)
+	}
+
+	/// Data returned by the contract with `ret`.
+	pub fn into_result(self) -> Vec<u8> {
+		self.result
+	}
+
+	fn charge(&mut self, amount: u64) -> Result<()> {
+		let new_counter = self.gas_counter.saturating_add(amount);
+		if new_counter > self.gas_limit {
+			return Err(UserTrap::GasLimit.into());
+		}
+		self.gas_counter = new_counter;
+		Ok(())
+	}
+
+	fn charge_copy(&mut self, len: usize) -> Result<()> {
+		let amount = (len as u64).saturating_mul(self.costs.copy_byte as u64);
+		self.charge(amount)
+	}
+
+	/// Return gas which was charged up front but not used, e.g. by a sub-call.
+	fn refund(&mut self, amount: u64) {
+		self.gas_counter = self.gas_counter.saturating_sub(amount);
+	}
+
+	/// Gas which may be passed on to a sub-call or create.
+	fn gas_cap(&self) -> u64 {
+		let available = self.gas_limit - self.gas_counter;
+		match self.ext.schedule().sub_gas_cap_divisor {
+			Some(divisor) => available - available / divisor as u64,
+			None => available,
+		}
+	}
+
+	fn ensure_mutable(&self) -> Result<()> {
+		if self.ext.is_static() {
+			return Err(UserTrap::MutableCallInStaticContext.into());
+		}
+		Ok(())
+	}
+
+	fn read_memory(&self, ptr: u32, len: u32) -> Result<Vec<u8>> {
+		self.memory.get(ptr, len as usize).map_err(|_| UserTrap::MemoryAccessViolation.into())
+	}
+
+	fn write_memory(&self, ptr: u32, data: &[u8]) -> Result<()> {
+		self.memory.set(ptr, data).map_err(|_| UserTrap::MemoryAccessViolation.into())
+	}
+
+	fn read_h256(&self, ptr: u32) -> Result<H256> {
+		Ok(H256::from_slice(&try!(self.read_memory(ptr, 32))))
+	}
+
+	fn read_address(&self, ptr: u32) -> Result<Address> {
+		Ok(Address::from_slice(&try!(self.read_memory(ptr, 20))))
+	}
+
+	fn read_u256(&self, ptr: u32) -> Result<U256> {
+		Ok(U256::from(&*try!(self.read_memory(ptr, 32))))
+	}
+
+	fn write_u256(&self, ptr: u32, value: &U256) -> Result<()> {
+		let mut buf = [0u8; 32];
+		value.to_big_endian(&mut buf);
+		self.write_memory(ptr, &buf)
+	}
+
+	/// Charge gas metered by the instrumented contract code.
+	fn gas(&mut self, args: RuntimeArgs) -> Result<Option<RuntimeValue>> {
+		let amount: u32 = try!(args.nth_checked(0));
+		try!(self.charge(amount as u64));
+		Ok(None)
+	}
+
+	/// Read a storage value: `storage_read(key_ptr, val_ptr)`.
+	fn storage_read(&mut self, args: RuntimeArgs) -> Result<Option<RuntimeValue>> {
+		let key_ptr: u32 = try!(args.nth_checked(0));
+		let val_ptr: u32 = try!(args.nth_checked(1));
+
+		let sload_gas = self.ext.schedule().sload_gas as u64;
+		try!(self.charge(sload_gas));
+
+		let key = try!(self.read_h256(key_ptr));
+		let val = self.ext.storage_at(&key);
+		try!(self.write_memory(val_ptr, &*val));
+		Ok(None)
+	}
+
+	/// Write a storage value: `storage_write(key_ptr, val_ptr)`.
+	fn storage_write(&mut self, args: RuntimeArgs) -> Result<Option<RuntimeValue>> {
+		let key_ptr: u32 = try!(args.nth_checked(0));
+		let val_ptr: u32 = try!(args.nth_checked(1));
+		try!(self.ensure_mutable());
+
+		let key = try!(self.read_h256(key_ptr));
+		let val = try!(self.read_h256(val_ptr));
+
+		let current = U256::from(&*self.ext.storage_at(&key));
+		let new = U256::from(&*val);
+		let net_metering = self.ext.schedule().sstore_net_metering;
+		let original = if net_metering {
+			U256::from(&*self.ext.original_storage_at(&key))
+		} else {
+			current
+		};
+
+		let gas = if net_metering {
+			calculate_eip1283_sstore_gas(self.ext.schedule(), &original, &current, &new)
+		} else if current.is_zero() && !new.is_zero() {
+			self.ext.schedule().sstore_set_gas
+		} else {
+			self.ext.schedule().sstore_reset_gas
+		};
+		try!(self.charge(gas as u64));
+
+		if net_metering {
+			handle_eip1283_sstore_clears_refund(&mut *self.ext, &original, &current, &new);
+		} else if !current.is_zero() && new.is_zero() {
+			let sstore_clears_schedule = self.ext.schedule().sstore_refund_gas;
+			self.ext.add_sstore_refund(sstore_clears_schedule);
+		}
+
+		self.ext.set_storage(key, val);
+		Ok(None)
+	}
+
+	/// Query the balance of an account: `balance(address_ptr, result_ptr)`.
+	fn balance(&mut self, args: RuntimeArgs) -> Result<Option<RuntimeValue>> {
+		let address_ptr: u32 = try!(args.nth_checked(0));
+		let result_ptr: u32 = try!(args.nth_checked(1));
+
+		let balance_gas = self.ext.schedule().balance_gas as u64;
+		try!(self.charge(balance_gas));
+
+		let address = try!(self.read_address(address_ptr));
+		let balance = self.ext.balance(&address);
+		try!(self.write_u256(result_ptr, &balance));
+		Ok(None)
+	}
+
+	/// Message call to another contract. Returns `0` on success and `-1` otherwise.
+	///
+	/// `ccall(gas, address_ptr, value_ptr, input_ptr, input_len, result_ptr, result_len)`
+	/// `dcall(gas, address_ptr, input_ptr, input_len, result_ptr, result_len)`
+	/// `scall(gas, address_ptr, input_ptr, input_len, result_ptr, result_len)`
+	fn do_call(&mut self, call_type: CallType, args: RuntimeArgs) -> Result<Option<RuntimeValue>> {
+		let use_value = call_type == CallType::Call;
+		let offset = if use_value { 3 } else { 2 };
+
+		let requested_gas: u64 = try!(args.nth_checked(0));
+		let address_ptr: u32 = try!(args.nth_checked(1));
+		let input_ptr: u32 = try!(args.nth_checked(offset));
+		let input_len: u32 = try!(args.nth_checked(offset + 1));
+		let result_ptr: u32 = try!(args.nth_checked(offset + 2));
+		let result_len: u32 = try!(args.nth_checked(offset + 3));
+
+		let address = try!(self.read_address(address_ptr));
+		let value = match call_type {
+			CallType::Call => Some(try!(self.read_u256(try!(args.nth_checked(2))))),
+			CallType::StaticCall => Some(U256::zero()),
+			_ => None,
+		};
+		let is_value_transfer = value.map_or(false, |v| !v.is_zero());
+		if is_value_transfer {
+			try!(self.ensure_mutable());
+		}
+
+		let mut cost = self.ext.schedule().call_gas as u64;
+		if is_value_transfer {
+			cost += self.ext.schedule().call_value_transfer_gas as u64;
+			let exists = if self.ext.schedule().no_empty {
+				self.ext.exists_and_not_null(&address)
+			} else {
+				self.ext.exists(&address)
+			};
+			if !exists {
+				cost += self.ext.schedule().call_new_account_gas as u64;
+			}
+		}
+		try!(self.charge(cost));
+		try!(self.charge_copy(input_len as usize + result_len as usize));
+
+		let input = try!(self.read_memory(input_ptr, input_len));
+
+		let call_gas = cmp::min(requested_gas, self.gas_cap());
+		try!(self.charge(call_gas));
+		let call_gas = if is_value_transfer {
+			call_gas + self.ext.schedule().call_stipend as u64
+		} else {
+			call_gas
+		};
+
+		let (sender, receiver, has_balance) = match call_type {
+			CallType::Call => {
+				let has_balance = self.ext.balance(&self.context.address) >= value.expect("value set for call; qed");
+				(self.context.address.clone(), address.clone(), has_balance)
+			},
+			CallType::DelegateCall => (self.context.sender.clone(), self.context.address.clone(), true),
+			CallType::StaticCall => (self.context.address.clone(), address.clone(), true),
+			_ => panic!("Unexpected call type {:?} in wasm runtime call", call_type),
+		};
+
+		if !has_balance || self.ext.depth() >= self.ext.schedule().max_depth {
+			self.refund(call_gas);
+			return Ok(Some(RuntimeValue::I32(-1)));
+		}
+
+		let mut result = vec![0u8; result_len as usize];
+		let call_result = self.ext.call(&U256::from(call_gas), &sender, &receiver, value, &input, &address, &mut result, call_type);
+
+		match call_result {
+			MessageCallResult::Success(gas_left, _) => {
+				self.refund(gas_left.low_u64());
+				try!(self.write_memory(result_ptr, &result));
+				Ok(Some(RuntimeValue::I32(0)))
+			},
+			MessageCallResult::Reverted(gas_left, _) => {
+				self.refund(gas_left.low_u64());
+				try!(self.write_memory(result_ptr, &result));
+				Ok(Some(RuntimeValue::I32(-1)))
+			},
+			MessageCallResult::Failed => Ok(Some(RuntimeValue::I32(-1))),
+		}
+	}
+
+	/// Create a new contract: `create(endowment_ptr, code_ptr, code_len, result_ptr)`.
+	/// Writes the new contract address to `result_ptr` and returns `0` on success, `-1` otherwise.
+	fn create(&mut self, args: RuntimeArgs) -> Result<Option<RuntimeValue>> {
+		let endowment_ptr: u32 = try!(args.nth_checked(0));
+		let code_ptr: u32 = try!(args.nth_checked(1));
+		let code_len: u32 = try!(args.nth_checked(2));
+		let result_ptr: u32 = try!(args.nth_checked(3));
+		try!(self.ensure_mutable());
+
+		let create_gas = self.ext.schedule().create_gas as u64;
+		try!(self.charge(create_gas));
+		try!(self.charge_copy(code_len as usize));
+
+		let endowment = try!(self.read_u256(endowment_ptr));
+		let code = try!(self.read_memory(code_ptr, code_len));
+
+		let gas = self.gas_cap();
+		try!(self.charge(gas));
+
+		let can_create = self.ext.balance(&self.context.address) >= endowment && self.ext.depth() < self.ext.schedule().max_depth;
+		if !can_create {
+			self.refund(gas);
+			return Ok(Some(RuntimeValue::I32(-1)));
+		}
+
+		match self.ext.create(&U256::from(gas), &endowment, &code, CreateContractAddress::FromSenderAndNonce) {
+			ContractCreateResult::Created(address, gas_left) => {
+				self.refund(gas_left.low_u64());
+				try!(self.write_memory(result_ptr, &*address));
+				Ok(Some(RuntimeValue::I32(0)))
+			},
+			ContractCreateResult::Reverted(gas_left, _) => {
+				self.refund(gas_left.low_u64());
+				Ok(Some(RuntimeValue::I32(-1)))
+			},
+			ContractCreateResult::Failed => Ok(Some(RuntimeValue::I32(-1))),
+		}
+	}
+
+	/// Emit a log entry: `elog(topic_ptr, topic_count, data_ptr, data_len)`.
+	fn elog(&mut self, args: RuntimeArgs) -> Result<Option<RuntimeValue>> {
+		let topic_ptr: u32 = try!(args.nth_checked(0));
+		let topic_count: u32 = try!(args.nth_checked(1));
+		let data_ptr: u32 = try!(args.nth_checked(2));
+		let data_len: u32 = try!(args.nth_checked(3));
+		try!(self.ensure_mutable());
+
+		if topic_count as usize > MAX_LOG_TOPICS {
+			return Err(UserTrap::TooManyTopics.into());
+		}
+
+		let gas = {
+			let schedule = self.ext.schedule();
+			schedule.log_gas as u64
+				+ schedule.log_topic_gas as u64 * topic_count as u64
+				+ schedule.log_data_gas as u64 * data_len as u64
+		};
+		try!(self.charge(gas));
+
+		let mut topics = Vec::with_capacity(topic_count as usize);
+		for i in 0..topic_count {
+			let ptr = try!(i.checked_mul(32)
+				.and_then(|offset| topic_ptr.checked_add(offset))
+				.ok_or_else(|| Trap::from(UserTrap::MemoryAccessViolation)));
+			topics.push(try!(self.read_h256(ptr)));
+		}
+		let data = try!(self.read_memory(data_ptr, data_len));
+
+		self.ext.log(topics, &data);
+		Ok(None)
+	}
+
+	/// Length of the call input: `input_length() -> i32`.
+	fn input_length(&mut self) -> Result<Option<RuntimeValue>> {
+		Ok(Some(RuntimeValue::I32(self.args.len() as i32)))
+	}
+
+	/// Copy the call input to contract memory: `fetch_input(dst_ptr)`.
+	fn fetch_input(&mut self, args: RuntimeArgs) -> Result<Option<RuntimeValue>> {
+		let dst_ptr: u32 = try!(args.nth_checked(0));
+
+		let len = self.args.len();
+		try!(self.charge_copy(len));
+		try!(self.write_memory(dst_ptr, &self.args));
+		Ok(None)
+	}
+
+	/// Stop execution and return data: `ret(ptr, len)`.
+	fn ret(&mut self, args: RuntimeArgs) -> Result<Option<RuntimeValue>> {
+		let ptr: u32 = try!(args.nth_checked(0));
+		let len: u32 = try!(args.nth_checked(1));
+
+		try!(self.charge_copy(len as usize));
+		self.result = try!(self.read_memory(ptr, len));
+		Err(UserTrap::Return.into())
+	}
+
+	fn write_address(&mut self, args: RuntimeArgs, address: Address) -> Result<Option<RuntimeValue>> {
+		let ptr: u32 = try!(args.nth_checked(0));
+		try!(self.write_memory(ptr, &*address));
+		Ok(None)
+	}
+
+	/// Copy the value passed with the current call to contract memory: `value(ptr)`.
+	fn value(&mut self, args: RuntimeArgs) -> Result<Option<RuntimeValue>> {
+		let ptr: u32 = try!(args.nth_checked(0));
+		let value = self.context.value;
+		try!(self.write_u256(ptr, &value));
+		Ok(None)
+	}
+
+	/// Destroy the contract and send its funds to given address: `suicide(refund_address_ptr)`.
+	fn suicide(&mut self, args: RuntimeArgs) -> Result<Option<RuntimeValue>> {
+		let refund_address_ptr: u32 = try!(args.nth_checked(0));
+		try!(self.ensure_mutable());
+
+		let refund_address = try!(self.read_address(refund_address_ptr));
+
+		let gas = {
+			let schedule = self.ext.schedule();
+			let is_value_transfer = !self.ext.origin_balance().is_zero();
+			let mut gas = schedule.suicide_gas as u64;
+			if (
+				!schedule.no_empty && !self.ext.exists(&refund_address)
+			) || (
+				schedule.no_empty && is_value_transfer && !self.ext.exists_and_not_null(&refund_address)
+			) {
+				gas += schedule.suicide_to_new_account_cost as u64;
+			}
+			gas
+		};
+		try!(self.charge(gas));
+
+		self.ext.suicide(&refund_address);
+		Err(UserTrap::Suicide.into())
+	}
+}
+
+impl<'a> Externals for Runtime<'a> {
+	fn invoke_index(&mut self, index: usize, args: RuntimeArgs) -> Result<Option<RuntimeValue>> {
+		match index {
+			GAS_FUNC => self.gas(args),
+			STORAGE_READ_FUNC => self.storage_read(args),
+			STORAGE_WRITE_FUNC => self.storage_write(args),
+			BALANCE_FUNC => self.balance(args),
+			CCALL_FUNC => self.do_call(CallType::Call, args),
+			DCALL_FUNC => self.do_call(CallType::DelegateCall, args),
+			SCALL_FUNC => self.do_call(CallType::StaticCall, args),
+			CREATE_FUNC => self.create(args),
+			ELOG_FUNC => self.elog(args),
+			INPUT_LENGTH_FUNC => self.input_length(),
+			FETCH_INPUT_FUNC => self.fetch_input(args),
+			RET_FUNC => self.ret(args),
+			SENDER_FUNC => {
+				let sender = self.context.sender.clone();
+				self.write_address(args, sender)
+			},
+			ORIGIN_FUNC => {
+				let origin = self.context.origin.clone();
+				self.write_address(args, origin)
+			},
+			ADDRESS_FUNC => {
+				let address = self.context.address.clone();
+				self.write_address(args, address)
+			},
+			VALUE_FUNC => self.value(args),
+			SUICIDE_FUNC => self.suicide(args),
+			_ => Err(UserTrap::UnknownFunction(index).into()),
+		}
+	}
+}
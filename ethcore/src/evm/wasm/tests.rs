@@ -0,0 +1,216 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+use std::str::FromStr;
+use util::{U256, H256, Address, Bytes};
+use action_params::{ActionParams, ActionValue};
+use evm::{self, Evm, GasLeft, Factory, VMType, WasmCosts};
+use evm::tests::FakeExt;
+use super::{is_wasm_code, WasmInterpreter};
+
+macro_rules! load_sample {
+	($name: expr) => {
+		Arc::new(include_bytes!(concat!("../../../res/wasm-tests/", $name)).to_vec())
+	}
+}
+
+fn test_params(code: Arc<Bytes>, data: Bytes) -> ActionParams {
+	let mut params = ActionParams::default();
+	params.gas = U256::from(100_000);
+	params.code = Some(code);
+	params.data = Some(data);
+	params
+}
+
+// similar to `test_finalize` in the EVM tests, but keeps the returned data.
+fn exec(mut vm: Box<Evm>, params: ActionParams, ext: &mut FakeExt) -> Result<(U256, Bytes), evm::Error> {
+	match vm.exec(params, ext) {
+		Ok(GasLeft::Known(gas_left)) => Ok((gas_left, Vec::new())),
+		Ok(GasLeft::NeedsReturn { gas_left, data, .. }) => Ok((gas_left, data.to_vec())),
+		Err(e) => Err(e),
+	}
+}
+
+fn exec_wasm(params: ActionParams, ext: &mut FakeExt) -> Result<(U256, Bytes), evm::Error> {
+	exec(Box::new(WasmInterpreter::new()), params, ext)
+}
+
+#[test]
+fn wasm_magic_number() {
+	assert!(is_wasm_code(&load_sample!("identity.wasm")));
+	assert!(!is_wasm_code(b"\0asm"));
+	assert!(!is_wasm_code(&[0x60, 0x01, 0x60, 0x00, 0x55]));
+}
+
+#[test]
+fn identity() {
+	let input = b"the quick brown fox".to_vec();
+	let params = test_params(load_sample!("identity.wasm"), input.clone());
+	let mut ext = FakeExt::new();
+
+	let (gas_left, result) = exec_wasm(params, &mut ext).unwrap();
+
+	assert_eq!(result, input);
+	assert_eq!(gas_left, U256::from(99_955));
+}
+
+#[test]
+fn storage_write() {
+	let key = H256::from(1);
+	let value = H256::from_str("00000000000000000000000000000000000000000000000000000000000000ff").unwrap();
+	let mut input = key.to_vec();
+	input.extend_from_slice(&*value);
+	let params = test_params(load_sample!("storage_write.wasm"), input);
+	let mut ext = FakeExt::new();
+
+	let (gas_left, result) = exec_wasm(params, &mut ext).unwrap();
+
+	assert!(result.is_empty());
+	assert_eq!(ext.store.get(&key), Some(&value));
+	assert_eq!(gas_left, U256::from(79_931));
+}
+
+#[test]
+fn storage_read() {
+	let key = H256::from(1);
+	let value = H256::from(42);
+	let params = test_params(load_sample!("storage_read.wasm"), key.to_vec());
+	let mut ext = FakeExt::new();
+	ext.store.insert(key, value.clone());
+
+	let (gas_left, result) = exec_wasm(params, &mut ext).unwrap();
+
+	assert_eq!(result, value.to_vec());
+	assert_eq!(gas_left, U256::from(99_878));
+}
+
+#[test]
+fn storage_write_in_static_context() {
+	let mut input = H256::from(1).to_vec();
+	input.extend_from_slice(&*H256::from(2));
+	let params = test_params(load_sample!("storage_write.wasm"), input);
+	let mut ext = FakeExt::new();
+	ext.is_static = true;
+
+	match exec_wasm(params, &mut ext) {
+		Err(evm::Error::MutableCallInStaticContext) => (),
+		_ => assert!(false, "Expected mutable call in static context"),
+	}
+	assert!(ext.store.is_empty());
+}
+
+#[test]
+fn logger() {
+	let topic = H256::from(7);
+	let mut input = topic.to_vec();
+	input.extend_from_slice(b"log data");
+	let params = test_params(load_sample!("logger.wasm"), input);
+	let mut ext = FakeExt::new();
+
+	let (gas_left, _) = exec_wasm(params, &mut ext).unwrap();
+
+	assert_eq!(ext.logs.len(), 1);
+	assert_eq!(ext.logs[0].topics, vec![topic]);
+	assert_eq!(ext.logs[0].data, b"log data".to_vec());
+	assert_eq!(gas_left, U256::from(99_135));
+}
+
+#[test]
+fn env_info() {
+	let sender = Address::from_str("0f572e5295c57f15886f9b263e2f6d2d6c7b5ec6").unwrap();
+	let origin = Address::from_str("cd1722f2947def4cf144679da39c4c32bdc35681").unwrap();
+	let address = Address::from_str("1f572e5295c57f15886f9b263e2f6d2d6c7b5ec6").unwrap();
+	let mut params = test_params(load_sample!("env_info.wasm"), Vec::new());
+	params.sender = sender.clone();
+	params.origin = origin.clone();
+	params.address = address.clone();
+	params.value = ActionValue::Transfer(U256::from(1_000_000_000));
+	let mut ext = FakeExt::new();
+
+	let (_, result) = exec_wasm(params, &mut ext).unwrap();
+
+	assert_eq!(&result[0..20], &*sender);
+	assert_eq!(&result[20..40], &*origin);
+	assert_eq!(&result[40..60], &*address);
+	assert_eq!(U256::from(&result[60..92]), U256::from(1_000_000_000));
+}
+
+#[test]
+fn balance() {
+	let address = Address::from_str("0f572e5295c57f15886f9b263e2f6d2d6c7b5ec6").unwrap();
+	let params = test_params(load_sample!("balance.wasm"), address.to_vec());
+	let mut ext = FakeExt::new();
+	ext.balances.insert(address, U256::from(12_345));
+
+	let (_, result) = exec_wasm(params, &mut ext).unwrap();
+
+	assert_eq!(U256::from(&*result), U256::from(12_345));
+}
+
+#[test]
+fn infinite_loop_runs_out_of_gas() {
+	let params = test_params(load_sample!("infinite_loop.wasm"), Vec::new());
+	let mut ext = FakeExt::new();
+
+	match exec_wasm(params, &mut ext) {
+		Err(evm::Error::OutOfGas) => (),
+		_ => assert!(false, "Expected out of gas"),
+	}
+}
+
+#[test]
+fn invalid_code() {
+	let params = test_params(Arc::new(b"\0asm\x01\0\0\0\x01".to_vec()), Vec::new());
+	let mut ext = FakeExt::new();
+
+	match exec_wasm(params, &mut ext) {
+		Err(evm::Error::Wasm(_)) => (),
+		_ => assert!(false, "Expected wasm error"),
+	}
+}
+
+#[test]
+fn too_much_memory() {
+	let params = test_params(load_sample!("identity.wasm"), Vec::new());
+	let mut ext = FakeExt::new();
+	ext.schedule.wasm = Some(WasmCosts { max_memory_pages: 0, ..Default::default() });
+
+	match exec_wasm(params, &mut ext) {
+		Err(evm::Error::Wasm(_)) => (),
+		_ => assert!(false, "Expected wasm error"),
+	}
+}
+
+#[test]
+fn factory_selects_runtime_by_schedule() {
+	let factory = Factory::new(VMType::Interpreter, 1024 * 32);
+	let input = b"hello".to_vec();
+
+	// without wasm in the schedule the code is run by the EVM, which stops on the leading zero byte
+	let params = test_params(load_sample!("identity.wasm"), input.clone());
+	let mut ext = FakeExt::new();
+	let vm = factory.create_for(&params, &ext.schedule);
+	let (_, result) = exec(vm, params, &mut ext).unwrap();
+	assert!(result.is_empty());
+
+	let params = test_params(load_sample!("identity.wasm"), input.clone());
+	let mut ext = FakeExt::new();
+	ext.schedule.wasm = Some(WasmCosts::default());
+	let vm = factory.create_for(&params, &ext.schedule);
+	let (_, result) = exec(vm, params, &mut ext).unwrap();
+	assert_eq!(result, input);
+}
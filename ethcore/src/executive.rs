@@ -21,11 +21,11 @@ use state::{State, Substate, CleanupMode};
 use engines::Engine;
 use types::executed::CallType;
 use env_info::EnvInfo;
-use error::ExecutionError;
-use evm::{self, Ext, Factory, Finalize};
+use error::{ExecutionError, CallError};
+use evm::{self, Ext, Factory, Finalize, FinalizationResult, CreateContractAddress};
 use externalities::*;
 use trace::{FlatTrace, Tracer, NoopTracer, ExecutiveTracer, VMTrace, VMTracer, ExecutiveVMTracer, NoopVMTracer};
-use transaction::{Action, SignedTransaction};
+use transaction::{Action, Transaction, SignedTransaction};
 use crossbeam;
 pub use types::executed::{Executed, ExecutionResult};
 
@@ -44,6 +44,73 @@ pub fn contract_address(address: &Address, nonce: &U256) -> Address {
 	From::from(stream.out().sha3())
 }
 
+/// Returns new address created from sender and nonce, or from sender, salt
+/// and init code hash, depending on the given address derivation scheme.
+pub fn contract_address_from_scheme(address_scheme: CreateContractAddress, sender: &Address, nonce: &U256, code: &[u8]) -> Address {
+	match address_scheme {
+		CreateContractAddress::FromSenderAndNonce => contract_address(sender, nonce),
+		CreateContractAddress::FromSenderSaltAndCodeHash(salt) => {
+			let code_hash = code.sha3();
+			let mut buffer = [0u8; 1 + 20 + 32 + 32];
+			buffer[0] = 0xff;
+			(&mut buffer[1..(1+20)]).copy_from_slice(&sender[..]);
+			(&mut buffer[(1+20)..(1+20+32)]).copy_from_slice(&salt[..]);
+			(&mut buffer[(1+20+32)..]).copy_from_slice(&code_hash[..]);
+			From::from((&buffer[..]).sha3())
+		},
+	}
+}
+
+/// Finds the lowest gas limit with which given transaction executes without an exception,
+/// between the intrinsic gas of the transaction and `cap`. The sender is given enough balance
+/// to pay for the transaction with `cap` gas.
+pub fn estimate_gas(mut state: State, env_info: &EnvInfo, engine: &Engine, vm_factory: &Factory, t: &SignedTransaction, cap: U256) -> Result<U256, CallError> {
+	let sender = try!(t.sender().map_err(|e| {
+		let message = format!("Transaction malformed: {:?}", e);
+		ExecutionError::TransactionMalformed(message)
+	}));
+	let balance = state.balance(&sender);
+	let needed_balance = t.value + cap * t.gas_price;
+	if balance < needed_balance {
+		state.add_balance(&sender, &(needed_balance - balance), CleanupMode::NoEmpty);
+	}
+
+	let intrinsic_gas = U256::from(t.gas_required(&engine.schedule(env_info)));
+	binary_search_gas(intrinsic_gas, cap, |gas| {
+		let mut tx = Transaction::clone(t);
+		tx.gas = gas;
+		let tx = tx.fake_sign(sender);
+		let mut state = state.clone();
+		let options = TransactOptions { tracing: false, vm_tracing: false, check_nonce: false };
+		let executed = try!(Executive::new(&mut state, env_info, engine, vm_factory).transact(&tx, options));
+		Ok(executed.exception.is_none() && !executed.reverted)
+	})
+}
+
+// lowest gas in `lower..cap + 1` for which `succeeds` returns true, assuming it
+// keeps returning true for any higher gas.
+fn binary_search_gas<F>(lower: U256, cap: U256, mut succeeds: F) -> Result<U256, CallError>
+	where F: FnMut(U256) -> Result<bool, CallError> {
+	if cap < lower || !try!(succeeds(cap)) {
+		return Err(CallError::GasEstimationFailed(cap));
+	}
+	if try!(succeeds(lower)) {
+		return Ok(lower);
+	}
+
+	// invariant: transaction fails with `lower` and succeeds with `upper`.
+	let (mut lower, mut upper) = (lower, cap);
+	while upper - lower > U256::one() {
+		let mid = lower + (upper - lower) / U256::from(2);
+		if try!(succeeds(mid)) {
+			upper = mid;
+		} else {
+			lower = mid;
+		}
+	}
+	Ok(upper)
+}
+
 /// Transaction execution options.
 #[derive(Default)]
 pub struct TransactOptions {
@@ -62,6 +129,7 @@ pub struct Executive<'a> {
 	engine: &'a Engine,
 	vm_factory: &'a Factory,
 	depth: usize,
+	static_flag: bool,
 }
 
 impl<'a> Executive<'a> {
@@ -73,17 +141,19 @@ impl<'a> Executive<'a> {
 			engine: engine,
 			vm_factory: vm_factory,
 			depth: 0,
+			static_flag: false,
 		}
 	}
 
 	/// Populates executive from parent properties. Increments executive depth.
-	pub fn from_parent(state: &'a mut State, info: &'a EnvInfo, engine: &'a Engine, vm_factory: &'a Factory, parent_depth: usize) -> Self {
+	pub fn from_parent(state: &'a mut State, info: &'a EnvInfo, engine: &'a Engine, vm_factory: &'a Factory, parent_depth: usize, static_flag: bool) -> Self {
 		Executive {
 			state: state,
 			info: info,
 			engine: engine,
 			vm_factory: vm_factory,
 			depth: parent_depth + 1,
+			static_flag: static_flag,
 		}
 	}
 
@@ -94,9 +164,11 @@ impl<'a> Executive<'a> {
 		substate: &'any mut Substate,
 		output: OutputPolicy<'any, 'any>,
 		tracer: &'any mut T,
-		vm_tracer: &'any mut V
+		vm_tracer: &'any mut V,
+		static_call: bool,
 	) -> Externalities<'any, T, V> where T: Tracer, V: VMTracer {
-		Externalities::new(self.state, self.info, self.engine, self.vm_factory, self.depth, origin_info, substate, output, tracer, vm_tracer)
+		let is_static = self.static_flag || static_call;
+		Externalities::new(self.state, self.info, self.engine, self.vm_factory, self.depth, origin_info, substate, output, tracer, vm_tracer, is_static)
 	}
 
 	/// This function should be used to execute transaction.
@@ -116,12 +188,27 @@ impl<'a> Executive<'a> {
 
 	/// Execute transaction/call with tracing enabled
 	pub fn transact_with_tracer<T, V>(
+		&'a mut self,
+		t: &SignedTransaction,
+		check_nonce: bool,
+		tracer: T,
+		vm_tracer: V
+	) -> Result<Executed, ExecutionError> where T: Tracer<Output=FlatTrace>, V: VMTracer<Output=VMTrace> {
+		let (mut executed, trace, vm_trace) = try!(self.transact_with_tracers(t, check_nonce, tracer, vm_tracer));
+		executed.trace = trace;
+		executed.vm_trace = vm_trace;
+		Ok(executed)
+	}
+
+	/// Execute transaction/call with tracing enabled. The outputs of both tracers are returned
+	/// alongside the execution result.
+	pub fn transact_with_tracers<T, V>(
 		&'a mut self,
 		t: &SignedTransaction,
 		check_nonce: bool,
 		mut tracer: T,
 		mut vm_tracer: V
-	) -> Result<Executed, ExecutionError> where T: Tracer, V: VMTracer {
+	) -> Result<(Executed, Vec<T::Output>, Option<V::Output>), ExecutionError> where T: Tracer, V: VMTracer {
 		let sender = try!(t.sender().map_err(|e| {
 			let message = format!("Transaction malformed: {:?}", e);
 			ExecutionError::TransactionMalformed(message)
@@ -168,7 +255,7 @@ impl<'a> Executive<'a> {
 
 		let mut substate = Substate::new();
 
-		let (gas_left, output) = match t.action {
+		let (result, output) = match t.action {
 			Action::Create => {
 				let new_address = contract_address(&sender, &nonce);
 				let params = ActionParams {
@@ -183,6 +270,7 @@ impl<'a> Executive<'a> {
 					code: Some(Arc::new(t.data.clone())),
 					data: None,
 					call_type: CallType::None,
+					address_scheme: CreateContractAddress::FromSenderAndNonce,
 				};
 				(self.create(params, &mut substate, &mut tracer, &mut vm_tracer), vec![])
 			},
@@ -199,6 +287,7 @@ impl<'a> Executive<'a> {
 					code_hash: self.state.code_hash(address),
 					data: Some(t.data.clone()),
 					call_type: CallType::Call,
+					address_scheme: CreateContractAddress::FromSenderAndNonce,
 				};
 				let mut out = vec![];
 				(self.call(params, &mut substate, BytesRef::Flexible(&mut out), &mut tracer, &mut vm_tracer), out)
@@ -206,7 +295,8 @@ impl<'a> Executive<'a> {
 		};
 
 		// finalize here!
-		Ok(try!(self.finalize(t, substate, gas_left, output, tracer.traces(), vm_tracer.drain())))
+		let executed = try!(self.finalize(t, substate, result, output));
+		Ok((executed, tracer.traces(), vm_tracer.drain()))
 	}
 
 	fn exec_vm<T, V>(
@@ -215,17 +305,19 @@ impl<'a> Executive<'a> {
 		unconfirmed_substate: &mut Substate,
 		output_policy: OutputPolicy,
 		tracer: &mut T,
-		vm_tracer: &mut V
-	) -> evm::Result<U256> where T: Tracer, V: VMTracer {
+		vm_tracer: &mut V,
+		static_call: bool,
+	) -> evm::Result<FinalizationResult> where T: Tracer, V: VMTracer {
 
 		let depth_threshold = ::io::LOCAL_STACK_SIZE.with(|sz| sz.get() / STACK_SIZE_PER_DEPTH);
 
 		// Ordinary execution - keep VM in same thread
 		if (self.depth + 1) % depth_threshold != 0 {
 			let vm_factory = self.vm_factory;
-			let mut ext = self.as_externalities(OriginInfo::from(&params), unconfirmed_substate, output_policy, tracer, vm_tracer);
+			let mut ext = self.as_externalities(OriginInfo::from(&params), unconfirmed_substate, output_policy, tracer, vm_tracer, static_call);
 			trace!(target: "executive", "ext.schedule.have_delegate_call: {}", ext.schedule().have_delegate_call);
-			return vm_factory.create(params.gas).exec(params, &mut ext).finalize(ext);
+			let vm = vm_factory.create_for(&params, ext.schedule());
+			return vm.exec(params, &mut ext).finalize(ext);
 		}
 
 		// Start in new thread to reset stack
@@ -233,10 +325,11 @@ impl<'a> Executive<'a> {
 		// https://github.com/aturon/crossbeam/issues/16
 		crossbeam::scope(|scope| {
 			let vm_factory = self.vm_factory;
-			let mut ext = self.as_externalities(OriginInfo::from(&params), unconfirmed_substate, output_policy, tracer, vm_tracer);
+			let mut ext = self.as_externalities(OriginInfo::from(&params), unconfirmed_substate, output_policy, tracer, vm_tracer, static_call);
 
 			scope.spawn(move || {
-				vm_factory.create(params.gas).exec(params, &mut ext).finalize(ext)
+				let vm = vm_factory.create_for(&params, ext.schedule());
+				vm.exec(params, &mut ext).finalize(ext)
 			})
 		}).join()
 	}
@@ -252,7 +345,7 @@ impl<'a> Executive<'a> {
 		mut output: BytesRef,
 		tracer: &mut T,
 		vm_tracer: &mut V
-	) -> evm::Result<U256> where T: Tracer, V: VMTracer {
+	) -> evm::Result<FinalizationResult> where T: Tracer, V: VMTracer {
 		// backup used in case of running out of gas
 		self.state.checkpoint();
 
@@ -264,7 +357,7 @@ impl<'a> Executive<'a> {
 		}
 		trace!("Executive::call(params={:?}) self.env_info={:?}", params, self.info);
 
-		if self.engine.is_builtin(&params.code_address) {
+		if self.engine.is_builtin(&params.code_address, self.info) {
 			// if destination is builtin, try to execute it
 
 			let default = [];
@@ -272,9 +365,21 @@ impl<'a> Executive<'a> {
 
 			let trace_info = tracer.prepare_trace_call(&params);
 
-			let cost = self.engine.cost_of_builtin(&params.code_address, data);
+			let cost = self.engine.cost_of_builtin(&params.code_address, self.info, data);
 			if cost <= params.gas {
-				self.engine.execute_builtin(&params.code_address, data, &mut output);
+				let mut builtin_out_buffer = Vec::new();
+				let result = self.engine.execute_builtin(&params.code_address, self.info, data, &mut BytesRef::Flexible(&mut builtin_out_buffer));
+				if let Err(e) = result {
+					// failed built-ins consume all the gas, just like any other exceptional halt
+					self.state.revert_to_checkpoint();
+					let evm_err = evm::Error::BuiltIn(e.0);
+					// like successful ones below, only top level failures are traced
+					if self.depth == 0 {
+						tracer.trace_failed_call(trace_info, vec![], evm_err.into());
+					}
+					return Err(evm_err);
+				}
+				output.write(0, &builtin_out_buffer);
 				self.state.discard_checkpoint();
 
 				// trace only top level calls to builtins to avoid DDoS attacks
@@ -292,7 +397,11 @@ impl<'a> Executive<'a> {
 					);
 				}
 
-				Ok(params.gas - cost)
+				Ok(FinalizationResult {
+					gas_left: params.gas - cost,
+					apply_state: true,
+					return_data: builtin_out_buffer,
+				})
 			} else {
 				// just drain the whole gas
 				self.state.revert_to_checkpoint();
@@ -311,12 +420,13 @@ impl<'a> Executive<'a> {
 			if params.code.is_some() {
 				// part of substate that may be reverted
 				let mut unconfirmed_substate = Substate::new();
+				let static_call = params.call_type == CallType::StaticCall;
 
 				// TODO: make ActionParams pass by ref then avoid copy altogether.
 				let mut subvmtracer = vm_tracer.prepare_subtrace(params.code.as_ref().expect("scope is conditional on params.code.is_some(); qed"));
 
 				let res = {
-					self.exec_vm(params, &mut unconfirmed_substate, OutputPolicy::Return(output, trace_output.as_mut()), &mut subtracer, &mut subvmtracer)
+					self.exec_vm(params, &mut unconfirmed_substate, OutputPolicy::Return(output, trace_output.as_mut()), &mut subtracer, &mut subvmtracer, static_call)
 				};
 
 				vm_tracer.done_subtrace(subvmtracer);
@@ -325,12 +435,13 @@ impl<'a> Executive<'a> {
 
 				let traces = subtracer.traces();
 				match res {
-					Ok(gas_left) => tracer.trace_call(
+					Ok(ref res) if res.apply_state => tracer.trace_call(
 						trace_info,
-						gas - gas_left,
+						gas - res.gas_left,
 						trace_output,
 						traces
 					),
+					Ok(ref res) => tracer.trace_reverted_call(trace_info, gas - res.gas_left, &res.return_data, traces),
 					Err(e) => tracer.trace_failed_call(trace_info, traces, e.into()),
 				};
 
@@ -344,7 +455,11 @@ impl<'a> Executive<'a> {
 				self.state.discard_checkpoint();
 
 				tracer.trace_call(trace_info, U256::zero(), trace_output, vec![]);
-				Ok(params.gas)
+				Ok(FinalizationResult {
+					gas_left: params.gas,
+					apply_state: true,
+					return_data: Vec::new(),
+				})
 			}
 		}
 	}
@@ -358,7 +473,15 @@ impl<'a> Executive<'a> {
 		substate: &mut Substate,
 		tracer: &mut T,
 		vm_tracer: &mut V
-	) -> evm::Result<U256> where T: Tracer, V: VMTracer {
+	) -> evm::Result<FinalizationResult> where T: Tracer, V: VMTracer {
+		// creating a contract on top of an existing one fails and consumes all of the gas
+		let has_code = self.state.code_size(&params.address).map_or(false, |size| size > 0);
+		if has_code || self.state.nonce(&params.address) != self.engine.account_start_nonce() {
+			let trace_info = tracer.prepare_trace_create(&params);
+			tracer.trace_failed_create(trace_info, vec![], evm::Error::ContractCollision.into());
+			return Err(evm::Error::ContractCollision);
+		}
+
 		// backup used in case of running out of gas
 		self.state.checkpoint();
 
@@ -385,19 +508,20 @@ impl<'a> Executive<'a> {
 		let mut subvmtracer = vm_tracer.prepare_subtrace(params.code.as_ref().expect("two ways into create (Externalities::create and Executive::transact_with_tracer); both place `Some(...)` `code` in `params`; qed"));
 
 		let res = {
-			self.exec_vm(params, &mut unconfirmed_substate, OutputPolicy::InitContract(trace_output.as_mut()), &mut subtracer, &mut subvmtracer)
+			self.exec_vm(params, &mut unconfirmed_substate, OutputPolicy::InitContract(trace_output.as_mut()), &mut subtracer, &mut subvmtracer, false)
 		};
 
 		vm_tracer.done_subtrace(subvmtracer);
 
 		match res {
-			Ok(gas_left) => tracer.trace_create(
+			Ok(ref res) if res.apply_state => tracer.trace_create(
 				trace_info,
-				gas - gas_left,
+				gas - res.gas_left,
 				trace_output,
 				created,
 				subtracer.traces()
 			),
+			Ok(ref res) => tracer.trace_reverted_create(trace_info, gas - res.gas_left, &res.return_data, subtracer.traces()),
 			Err(e) => tracer.trace_failed_create(trace_info, subtracer.traces(), e.into())
 		};
 
@@ -410,21 +534,20 @@ impl<'a> Executive<'a> {
 		&mut self,
 		t: &SignedTransaction,
 		mut substate: Substate,
-		result: evm::Result<U256>,
-		output: Bytes,
-		trace: Vec<FlatTrace>,
-		vm_trace: Option<VMTrace>
+		result: evm::Result<FinalizationResult>,
+		output: Bytes
 	) -> ExecutionResult {
 		let schedule = self.engine.schedule(self.info);
 
 		// refunds from SSTORE nonzero -> zero
-		let sstore_refunds = U256::from(schedule.sstore_refund_gas) * substate.sstore_clears_count;
+		assert!(substate.sstore_clears_refund >= 0, "On transaction level, sstore clears refund cannot go below zero.");
+		let sstore_refunds = U256::from(substate.sstore_clears_refund as u64);
 		// refunds from contract suicides
 		let suicide_refunds = U256::from(schedule.suicide_refund_gas) * U256::from(substate.suicides.len());
 		let refunds_bound = sstore_refunds + suicide_refunds;
 
 		// real ammount to refund
-		let gas_left_prerefund = match result { Ok(x) => x, _ => 0.into() };
+		let gas_left_prerefund = match result { Ok(ref r) => r.gas_left, _ => 0.into() };
 		let refunded = cmp::min(refunds_bound, (t.gas - gas_left_prerefund) >> 1);
 		let gas_left = gas_left_prerefund + refunded;
 
@@ -463,7 +586,7 @@ impl<'a> Executive<'a> {
 
 		match result {
 			Err(evm::Error::Internal) => Err(ExecutionError::Internal),
-			Err(_) => {
+			Err(e) => {
 				Ok(Executed {
 					gas: t.gas,
 					gas_used: t.gas,
@@ -472,9 +595,29 @@ impl<'a> Executive<'a> {
 					logs: vec![],
 					contracts_created: vec![],
 					output: output,
-					trace: trace,
-					vm_trace: vm_trace,
+					trace: vec![],
+					vm_trace: None,
+					call_trace: None,
 					state_diff: None,
+					reverted: false,
+					exception: Some(e.into()),
+				})
+			},
+			Ok(FinalizationResult{ apply_state: false, return_data, .. }) => {
+				Ok(Executed {
+					gas: t.gas,
+					gas_used: gas_used,
+					refunded: refunded,
+					cumulative_gas_used: self.info.gas_used + gas_used,
+					logs: vec![],
+					contracts_created: vec![],
+					output: return_data,
+					trace: vec![],
+					vm_trace: None,
+					call_trace: None,
+					state_diff: None,
+					reverted: true,
+					exception: None,
 				})
 			},
 			_ => {
@@ -486,21 +629,30 @@ impl<'a> Executive<'a> {
 					logs: substate.logs,
 					contracts_created: substate.contracts_created,
 					output: output,
-					trace: trace,
-					vm_trace: vm_trace,
+					trace: vec![],
+					vm_trace: None,
+					call_trace: None,
 					state_diff: None,
+					reverted: false,
+					exception: None,
 				})
 			},
 		}
 	}
 
-	fn enact_result(&mut self, result: &evm::Result<U256>, substate: &mut Substate, un_substate: Substate) {
+	fn enact_result(&mut self, result: &evm::Result<FinalizationResult>, substate: &mut Substate, un_substate: Substate) {
 		match *result {
 			Err(evm::Error::OutOfGas)
 				| Err(evm::Error::BadJumpDestination {..})
 				| Err(evm::Error::BadInstruction {.. })
 				| Err(evm::Error::StackUnderflow {..})
-				| Err(evm::Error::OutOfStack {..}) => {
+				| Err(evm::Error::OutOfStack {..})
+				| Err(evm::Error::OutOfBounds)
+				| Err(evm::Error::MutableCallInStaticContext)
+				| Err(evm::Error::BuiltIn(_))
+				| Err(evm::Error::Wasm(_))
+				| Err(evm::Error::ContractCollision)
+				| Ok(FinalizationResult{ apply_state: false, .. }) => {
 					self.state.revert_to_checkpoint();
 			},
 			Ok(_) | Err(evm::Error::Internal) => {
@@ -521,12 +673,12 @@ mod tests {
 	use util::bytes::BytesRef;
 	use action_params::{ActionParams, ActionValue};
 	use env_info::EnvInfo;
-	use evm::{Factory, VMType};
-	use error::ExecutionError;
+	use evm::{Factory, VMType, FinalizationResult, CreateContractAddress, Schedule};
+	use error::{ExecutionError, CallError};
 	use state::{Substate, CleanupMode};
 	use tests::helpers::*;
 	use trace::trace;
-	use trace::{FlatTrace, Tracer, NoopTracer, ExecutiveTracer};
+	use trace::{FlatTrace, Tracer, NoopTracer, ExecutiveTracer, TraceError};
 	use trace::{VMTrace, VMOperation, VMExecutedOperation, MemoryDiff, StorageDiff, VMTracer, NoopVMTracer, ExecutiveVMTracer};
 	use transaction::{Action, Transaction};
 
@@ -539,6 +691,45 @@ mod tests {
 		assert_eq!(expected_address, contract_address(&address, &U256::from(88)));
 	}
 
+	#[test]
+	fn test_contract_address_from_salt() {
+		let scheme = CreateContractAddress::FromSenderSaltAndCodeHash(H256::zero());
+		let code = "00".from_hex().unwrap();
+
+		let address = Address::zero();
+		let expected_address = Address::from_str("4d1a2e2bb4f88f0250f26ffff098b0b30b26bf38").unwrap();
+		assert_eq!(expected_address, contract_address_from_scheme(scheme, &address, &U256::zero(), &code));
+
+		let address = Address::from_str("deadbeef00000000000000000000000000000000").unwrap();
+		let expected_address = Address::from_str("b928f69bb1d91cd65274e3c79d8986362984fda3").unwrap();
+		assert_eq!(expected_address, contract_address_from_scheme(scheme, &address, &U256::zero(), &code));
+	}
+
+	#[test]
+	fn should_find_lowest_succeeding_gas() {
+		let mut runs = 0;
+		let gas = binary_search_gas(21_000.into(), 4_700_000.into(), |gas| {
+			runs += 1;
+			Ok(gas >= U256::from(53_427))
+		});
+		assert_eq!(gas, Ok(53_427.into()));
+		assert!(runs < 30);
+
+		assert_eq!(binary_search_gas(21_000.into(), 4_700_000.into(), |_| Ok(true)), Ok(21_000.into()));
+	}
+
+	#[test]
+	fn should_fail_estimation_at_cap() {
+		assert_eq!(
+			binary_search_gas(21_000.into(), 4_700_000.into(), |_| Ok(false)),
+			Err(CallError::GasEstimationFailed(4_700_000.into()))
+		);
+		assert_eq!(
+			binary_search_gas(21_000.into(), 20_000.into(), |_| Ok(true)),
+			Err(CallError::GasEstimationFailed(20_000.into()))
+		);
+	}
+
 	// TODO: replace params with transactions!
 	evm_test!{test_sender_balance: test_sender_balance_jit, test_sender_balance_int}
 	fn test_sender_balance(factory: Factory) {
@@ -557,7 +748,7 @@ mod tests {
 		let engine = TestEngine::new(0);
 		let mut substate = Substate::new();
 
-		let gas_left = {
+		let FinalizationResult { gas_left, .. } = {
 			let mut ex = Executive::new(&mut state, &info, &engine, &factory);
 			ex.create(params, &mut substate, &mut NoopTracer, &mut NoopVMTracer).unwrap()
 		};
@@ -616,7 +807,7 @@ mod tests {
 		let engine = TestEngine::new(0);
 		let mut substate = Substate::new();
 
-		let gas_left = {
+		let FinalizationResult { gas_left, .. } = {
 			let mut ex = Executive::new(&mut state, &info, &engine, &factory);
 			ex.create(params, &mut substate, &mut NoopTracer, &mut NoopVMTracer).unwrap()
 		};
@@ -677,7 +868,7 @@ mod tests {
 		let mut tracer = ExecutiveTracer::default();
 		let mut vm_tracer = ExecutiveVMTracer::toplevel();
 
-		let gas_left = {
+		let FinalizationResult { gas_left, .. } = {
 			let mut ex = Executive::new(&mut state, &info, &engine, &factory);
 			let output = BytesRef::Fixed(&mut[0u8;0]);
 			ex.call(params, &mut substate, output, &mut tracer, &mut vm_tracer).unwrap()
@@ -707,7 +898,8 @@ mod tests {
 				from: "b010143a42d5980c7e5ef0e4a4416dc098a4fed3".into(),
 				value: 23.into(),
 				gas: 67979.into(),
-				init: vec![96, 16, 128, 96, 12, 96, 0, 57, 96, 0, 243, 0, 96, 0, 53, 84, 21, 96, 9, 87, 0, 91, 96, 32, 53, 96, 0, 53, 85]
+				init: vec![96, 16, 128, 96, 12, 96, 0, 57, 96, 0, 243, 0, 96, 0, 53, 84, 21, 96, 9, 87, 0, 91, 96, 32, 53, 96, 0, 53, 85],
+				create_type: trace::CreateType::Create,
 			}),
 			result: trace::Res::Create(trace::CreateResult {
 				gas_used: U256::from(3224),
@@ -752,6 +944,70 @@ mod tests {
 		assert_eq!(vm_tracer.drain().unwrap(), expected_vm_trace);
 	}
 
+	#[test]
+	fn test_create2_collision() {
+		let factory = Factory::new(VMType::Interpreter, 1024 * 32);
+
+		// init code, deploys a single byte `ff`:
+		//
+		// 60 ff - push 0xff
+		// 60 00 - push 0
+		// 53 - mstore8
+		// 60 01 - push 1
+		// 60 00 - push 0
+		// f3 - return
+		//
+		// code:
+		//
+		// 69 60ff60005360016000f3 - push init code
+		// 60 00 - push 0
+		// 52 - mstore
+		// 60 00 60 0a 60 16 60 00 - push salt 0, size 10, offset 22, value 0
+		// f5 - create2
+		// 60 00 - push 0
+		// 55 - sstore
+		// 60 00 60 0a 60 16 60 00 - push salt 0, size 10, offset 22, value 0
+		// f5 - create2 (same salt and init code)
+		// 60 01 - push 1
+		// 55 - sstore
+
+		let init_code = "60ff60005360016000f3".from_hex().unwrap();
+		let code = "6960ff60005360016000f36000526000600a60166000f56000556000600a60166000f5600155".from_hex().unwrap();
+
+		let sender = Address::from_str("cd1722f3947def4cf144679da39c4c32bdc35681").unwrap();
+		let address = contract_address(&sender, &U256::zero());
+		let created = contract_address_from_scheme(CreateContractAddress::FromSenderSaltAndCodeHash(H256::zero()), &address, &U256::zero(), &init_code);
+		let mut params = ActionParams::default();
+		params.address = address.clone();
+		params.code_address = address.clone();
+		params.sender = sender.clone();
+		params.origin = sender.clone();
+		params.gas = U256::from(1_000_000);
+		params.code = Some(Arc::new(code));
+		params.call_type = CallType::Call;
+		let mut state_result = get_temp_state();
+		let mut state = state_result.reference_mut();
+		let info = EnvInfo::default();
+		let mut schedule = Schedule::new_post_eip150(usize::max_value(), true, true, true);
+		schedule.have_create2 = true;
+		let engine = TestEngine::new_with_schedule(5, schedule);
+		let mut substate = Substate::new();
+		let mut tracer = ExecutiveTracer::default();
+
+		{
+			let mut ex = Executive::new(&mut state, &info, &engine, &factory);
+			let output = BytesRef::Fixed(&mut[0u8;0]);
+			ex.call(params, &mut substate, output, &mut tracer, &mut NoopVMTracer).unwrap();
+		}
+
+		let traces = tracer.traces();
+		assert_eq!(traces.len(), 3);
+		assert_eq!(traces[2].result, trace::Res::FailedCreate(TraceError::ContractCollision));
+		assert_eq!(state.storage_at(&address, &H256::from(0)), H256::from(created.clone()));
+		assert_eq!(state.storage_at(&address, &H256::from(1)), H256::zero());
+		assert_eq!(state.code(&created), Some(Arc::new(vec![0xff])));
+	}
+
 	#[test]
 	fn test_create_contract() {
 		// Tracing is not supported in JIT
@@ -788,7 +1044,7 @@ mod tests {
 		let mut tracer = ExecutiveTracer::default();
 		let mut vm_tracer = ExecutiveVMTracer::toplevel();
 
-		let gas_left = {
+		let FinalizationResult { gas_left, .. } = {
 			let mut ex = Executive::new(&mut state, &info, &engine, &factory);
 			ex.create(params.clone(), &mut substate, &mut tracer, &mut vm_tracer).unwrap()
 		};
@@ -803,6 +1059,7 @@ mod tests {
 				value: 100.into(),
 				gas: params.gas,
 				init: vec![96, 16, 128, 96, 12, 96, 0, 57, 96, 0, 243, 0, 96, 0, 53, 84, 21, 96, 9, 87, 0, 91, 96, 32, 53, 96, 0, 53, 85],
+				create_type: trace::CreateType::Create,
 			}),
 			result: trace::Res::Create(trace::CreateResult {
 				gas_used: U256::from(3224),
@@ -874,7 +1131,7 @@ mod tests {
 		let engine = TestEngine::new(0);
 		let mut substate = Substate::new();
 
-		let gas_left = {
+		let FinalizationResult { gas_left, .. } = {
 			let mut ex = Executive::new(&mut state, &info, &engine, &factory);
 			ex.create(params, &mut substate, &mut NoopTracer, &mut NoopVMTracer).unwrap()
 		};
@@ -987,7 +1244,7 @@ mod tests {
 		let engine = TestEngine::new(0);
 		let mut substate = Substate::new();
 
-		let gas_left = {
+		let FinalizationResult { gas_left, .. } = {
 			let mut ex = Executive::new(&mut state, &info, &engine, &factory);
 			ex.call(params, &mut substate, BytesRef::Fixed(&mut []), &mut NoopTracer, &mut NoopVMTracer).unwrap()
 		};
@@ -1032,7 +1289,7 @@ mod tests {
 		let engine = TestEngine::new(0);
 		let mut substate = Substate::new();
 
-		let gas_left = {
+		let FinalizationResult { gas_left, .. } = {
 			let mut ex = Executive::new(&mut state, &info, &engine, &factory);
 			ex.call(params, &mut substate, BytesRef::Fixed(&mut []), &mut NoopTracer, &mut NoopVMTracer).unwrap()
 		};
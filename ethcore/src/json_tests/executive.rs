@@ -21,7 +21,7 @@ use executive::*;
 use engines::Engine;
 use env_info::EnvInfo;
 use evm;
-use evm::{Schedule, Ext, Factory, Finalize, VMType, ContractCreateResult, MessageCallResult};
+use evm::{Schedule, Ext, Factory, Finalize, VMType, ContractCreateResult, MessageCallResult, CreateContractAddress};
 use externalities::*;
 use types::executed::CallType;
 use tests::helpers::*;
@@ -73,7 +73,7 @@ impl<'a, T, V> TestExt<'a, T, V> where T: 'a + Tracer, V: 'a + VMTracer {
 	) -> Self {
 		TestExt {
 			contract_address: contract_address(&address, &state.nonce(&address)),
-			ext: Externalities::new(state, info, engine, vm_factory, depth, origin_info, substate, output, tracer, vm_tracer),
+			ext: Externalities::new(state, info, engine, vm_factory, depth, origin_info, substate, output, tracer, vm_tracer, false),
 			callcreates: vec![]
 		}
 	}
@@ -84,6 +84,10 @@ impl<'a, T, V> Ext for TestExt<'a, T, V> where T: Tracer, V: VMTracer {
 		self.ext.storage_at(key)
 	}
 
+	fn original_storage_at(&self, key: &H256) -> H256 {
+		self.ext.original_storage_at(key)
+	}
+
 	fn set_storage(&mut self, key: H256, value: H256) {
 		self.ext.set_storage(key, value)
 	}
@@ -108,7 +112,7 @@ impl<'a, T, V> Ext for TestExt<'a, T, V> where T: Tracer, V: VMTracer {
 		self.ext.blockhash(number)
 	}
 
-	fn create(&mut self, gas: &U256, value: &U256, code: &[u8]) -> ContractCreateResult {
+	fn create(&mut self, gas: &U256, value: &U256, code: &[u8], _address: CreateContractAddress) -> ContractCreateResult {
 		self.callcreates.push(CallCreate {
 			data: code.to_vec(),
 			destination: None,
@@ -134,7 +138,7 @@ impl<'a, T, V> Ext for TestExt<'a, T, V> where T: Tracer, V: VMTracer {
 			gas_limit: *gas,
 			value: value.unwrap()
 		});
-		MessageCallResult::Success(*gas)
+		MessageCallResult::Success(*gas, vec![])
 	}
 
 	fn extcode(&self, address: &Address) -> Arc<Bytes>  {
@@ -149,8 +153,8 @@ impl<'a, T, V> Ext for TestExt<'a, T, V> where T: Tracer, V: VMTracer {
 		self.ext.log(topics, data)
 	}
 
-	fn ret(self, gas: &U256, data: &[u8]) -> Result<U256, evm::Error> {
-		self.ext.ret(gas, data)
+	fn ret(self, gas: &U256, data: &[u8], apply_state: bool) -> Result<U256, evm::Error> {
+		self.ext.ret(gas, data, apply_state)
 	}
 
 	fn suicide(&mut self, refund_address: &Address) {
@@ -169,8 +173,16 @@ impl<'a, T, V> Ext for TestExt<'a, T, V> where T: Tracer, V: VMTracer {
 		0
 	}
 
-	fn inc_sstore_clears(&mut self) {
-		self.ext.inc_sstore_clears()
+	fn is_static(&self) -> bool {
+		false
+	}
+
+	fn add_sstore_refund(&mut self, value: usize) {
+		self.ext.add_sstore_refund(value)
+	}
+
+	fn sub_sstore_refund(&mut self, value: usize) {
+		self.ext.sub_sstore_refund(value)
 	}
 }
 
@@ -228,7 +240,7 @@ fn do_json_test_for(vm_type: &VMType, json_data: &[u8]) -> Vec<String> {
 			let res = evm.exec(params, &mut ex);
 			// a return in finalize will not alter callcreates
 			let callcreates = ex.callcreates.clone();
-			(res.finalize(ex), callcreates)
+			(res.finalize(ex).map(|r| r.gas_left), callcreates)
 		};
 
 		match res {
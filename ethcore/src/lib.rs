@@ -101,6 +101,8 @@ extern crate bit_set;
 extern crate rlp;
 extern crate ethcore_bloom_journal as bloom_journal;
 extern crate byteorder;
+extern crate num;
+extern crate bn;
 extern crate transient_hashmap;
 extern crate linked_hash_map;
 
@@ -118,6 +120,12 @@ extern crate lru_cache;
 
 #[cfg(feature = "jit" )]
 extern crate evmjit;
+#[cfg(feature = "wasm")]
+extern crate wasmi;
+#[cfg(feature = "wasm")]
+extern crate parity_wasm;
+#[cfg(feature = "wasm")]
+extern crate pwasm_utils;
 
 pub extern crate ethstore;
 
@@ -23,9 +23,9 @@ use account_provider::{AccountProvider, Error as AccountError};
 use views::{BlockView, HeaderView};
 use header::Header;
 use state::{State, CleanupMode};
-use client::{MiningBlockChainClient, Executive, Executed, EnvInfo, TransactOptions, BlockId, CallAnalytics, TransactionId};
+use client::{MiningBlockChainClient, Executive, Executed, EnvInfo, TransactOptions, BlockId, CallAnalytics, StateOverride, TransactionId};
 use client::TransactionImportResult;
-use executive::contract_address;
+use executive::{self, contract_address};
 use block::{ClosedBlock, SealedBlock, IsBlock, Block};
 use error::*;
 use transaction::{Action, SignedTransaction};
@@ -227,6 +227,7 @@ pub struct Miner {
 	accounts: Option<Arc<AccountProvider>>,
 	work_poster: Option<WorkPoster>,
 	gas_pricer: Mutex<GasPricer>,
+	transaction_listener: RwLock<Vec<Box<Fn(&[H256]) + Send + Sync>>>,
 }
 
 impl Miner {
@@ -269,9 +270,15 @@ impl Miner {
 			engine: spec.engine.clone(),
 			work_poster: work_poster,
 			gas_pricer: Mutex::new(gas_pricer),
+			transaction_listener: RwLock::new(Vec::new()),
 		}
 	}
 
+	/// Set a callback to be notified about imported transactions' hashes.
+	pub fn add_transactions_listener(&self, f: Box<Fn(&[H256]) + Send + Sync>) {
+		self.transaction_listener.write().push(f);
+	}
+
 	/// Creates new instance of miner with accounts and with given spec.
 	pub fn with_spec_and_accounts(spec: &Spec, accounts: Option<Arc<AccountProvider>>) -> Miner {
 		Miner::new_raw(Default::default(), GasPricer::new_fixed(20_000_000_000u64.into()), spec, accounts)
@@ -583,8 +590,10 @@ impl Miner {
 		let schedule = chain.latest_schedule();
 		let gas_required = |tx: &SignedTransaction| tx.gas_required(&schedule).into();
 		let best_block_header: Header = ::rlp::decode(&chain.best_block_header());
-		transactions.into_iter()
+		let mut inserted = Vec::with_capacity(transactions.len());
+		let results = transactions.into_iter()
 			.map(|tx| {
+				let hash = tx.hash();
 				if chain.transaction_block(TransactionId::Hash(tx.hash())).is_some() {
 					debug!(target: "miner", "Rejected tx {:?}: already in the blockchain", tx.hash());
 					return Err(Error::Transaction(TransactionError::AlreadyImported));
@@ -602,23 +611,49 @@ impl Miner {
 							})
 						}).unwrap_or(default_origin);
 
-						match origin {
+						let result = match origin {
 							TransactionOrigin::Local | TransactionOrigin::RetractedBlock => {
 								transaction_queue.add(tx, origin, &fetch_account, &gas_required)
 							},
 							TransactionOrigin::External => {
 								transaction_queue.add_with_banlist(tx, &fetch_account, &gas_required)
 							}
+						};
+						if result.is_ok() {
+							inserted.push(hash);
 						}
+						result
 					},
 				}
 			})
-			.collect()
+			.collect();
+
+		if !inserted.is_empty() {
+			for listener in &*self.transaction_listener.read() {
+				listener(&inserted);
+			}
+		}
+
+		results
 	}
 
 	/// Are we allowed to do a non-mandatory reseal?
 	fn tx_reseal_allowed(&self) -> bool { Instant::now() > *self.next_allowed_reseal.lock() }
 
+	// environment for calls on top of the pending block with given header.
+	// TODO: merge this code with client.rs's fn call somwhow.
+	fn pending_env_info(chain: &MiningBlockChainClient, header: &Header) -> EnvInfo {
+		EnvInfo {
+			number: header.number(),
+			author: *header.author(),
+			timestamp: header.timestamp(),
+			difficulty: *header.difficulty(),
+			last_hashes: Arc::new(chain.last_hashes()),
+			gas_used: U256::zero(),
+			gas_limit: U256::max_value(),
+		}
+	}
+
 	#[cfg_attr(feature="dev", allow(wrong_self_convention))]
 	#[cfg_attr(feature="dev", allow(redundant_closure))]
 	fn from_pending_block<H, F, G>(&self, latest_block_number: BlockNumber, from_chain: F, map_block: G) -> H
@@ -660,26 +695,16 @@ impl MinerService for Miner {
 		}
 	}
 
-	fn call(&self, chain: &MiningBlockChainClient, t: &SignedTransaction, analytics: CallAnalytics) -> Result<Executed, CallError> {
+	fn call(&self, chain: &MiningBlockChainClient, t: &SignedTransaction, analytics: CallAnalytics, state_override: &StateOverride) -> Result<Executed, CallError> {
 		let sealing_work = self.sealing_work.lock();
 		match sealing_work.queue.peek_last_ref() {
 			Some(work) => {
 				let block = work.block();
 
-				// TODO: merge this code with client.rs's fn call somwhow.
-				let header = block.header();
-				let last_hashes = Arc::new(chain.last_hashes());
-				let env_info = EnvInfo {
-					number: header.number(),
-					author: *header.author(),
-					timestamp: header.timestamp(),
-					difficulty: *header.difficulty(),
-					last_hashes: last_hashes,
-					gas_used: U256::zero(),
-					gas_limit: U256::max_value(),
-				};
+				let env_info = Self::pending_env_info(chain, block.header());
 				// that's just a copy of the state.
 				let mut state = block.state().clone();
+				state.apply_override(state_override);
 				let original_state = if analytics.state_diffing { Some(state.clone()) } else { None };
 
 				let sender = try!(t.sender().map_err(|e| {
@@ -701,7 +726,28 @@ impl MinerService for Miner {
 				Ok(ret)
 			},
 			None => {
-				chain.call(t, BlockId::Latest, analytics)
+				chain.call(t, BlockId::Latest, analytics, state_override)
+			}
+		}
+	}
+
+	fn estimate_gas(&self, chain: &MiningBlockChainClient, t: &SignedTransaction, state_override: &StateOverride) -> Result<U256, CallError> {
+		// the search executes the transaction many times, so don't hold the sealing lock for it.
+		let pending = {
+			let sealing_work = self.sealing_work.lock();
+			sealing_work.queue.peek_last_ref().map(|work| {
+				let block = work.block();
+				(block.state().clone(), Self::pending_env_info(chain, block.header()), *block.header().gas_limit())
+			})
+		};
+
+		match pending {
+			Some((mut state, env_info, cap)) => {
+				state.apply_override(state_override);
+				executive::estimate_gas(state, &env_info, &*self.engine, chain.vm_factory(), t, cap)
+			},
+			None => {
+				chain.estimate_gas(t, BlockId::Latest, state_override)
 			}
 		}
 	}
@@ -1278,6 +1324,26 @@ mod tests {
 		assert!(miner.prepare_work_sealing(&client));
 	}
 
+	#[test]
+	fn should_notify_listeners_about_imported_transactions() {
+		// given
+		let client = TestBlockChainClient::default();
+		let miner = miner();
+		let transaction = transaction();
+		let hash = transaction.hash();
+		let notified = Arc::new(Mutex::new(Vec::new()));
+		let n = notified.clone();
+		miner.add_transactions_listener(Box::new(move |hashes| n.lock().extend_from_slice(hashes)));
+
+		// when
+		miner.import_external_transactions(&client, vec![transaction.clone()]).pop().unwrap().unwrap();
+		// already imported, so listeners are not notified again
+		assert!(miner.import_external_transactions(&client, vec![transaction]).pop().unwrap().is_err());
+
+		// then
+		assert_eq!(*notified.lock(), vec![hash]);
+	}
+
 	#[test]
 	fn should_not_seal_unless_enabled() {
 		let miner = miner();
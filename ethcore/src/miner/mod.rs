@@ -57,7 +57,7 @@ pub use client::TransactionImportResult;
 
 use std::collections::BTreeMap;
 use util::{H256, U256, Address, Bytes};
-use client::{MiningBlockChainClient, Executed, CallAnalytics};
+use client::{MiningBlockChainClient, Executed, CallAnalytics, StateOverride};
 use block::ClosedBlock;
 use header::BlockNumber;
 use receipt::{RichReceipt, Receipt};
@@ -174,8 +174,12 @@ pub trait MinerService : Send + Sync {
 	/// Latest account balance in pending state.
 	fn balance(&self, chain: &MiningBlockChainClient, address: &Address) -> U256;
 
-	/// Call into contract code using pending state.
-	fn call(&self, chain: &MiningBlockChainClient, t: &SignedTransaction, analytics: CallAnalytics) -> Result<Executed, CallError>;
+	/// Call into contract code using pending state with `state_override` applied.
+	fn call(&self, chain: &MiningBlockChainClient, t: &SignedTransaction, analytics: CallAnalytics, state_override: &StateOverride) -> Result<Executed, CallError>;
+
+	/// Estimates the gas needed by given transaction on top of the pending state altered by `state_override`,
+	/// searching between its intrinsic gas and the gas limit of the pending block.
+	fn estimate_gas(&self, chain: &MiningBlockChainClient, t: &SignedTransaction, state_override: &StateOverride) -> Result<U256, CallError>;
 
 	/// Get storage value in pending state.
 	fn storage_at(&self, chain: &MiningBlockChainClient, address: &Address, position: &H256) -> H256;
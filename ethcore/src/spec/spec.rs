@@ -18,6 +18,7 @@
 
 use util::*;
 use builtin::Builtin;
+use evm::Schedule;
 use engines::{Engine, NullEngine, InstantSeal, BasicAuthority, AuthorityRound};
 use pod_state::*;
 use account_db::*;
@@ -44,6 +45,63 @@ pub struct CommonParams {
 	pub min_gas_limit: U256,
 	/// Fork block to check.
 	pub fork_block: Option<(BlockNumber, H256)>,
+	/// Number of first block where EIP-150 rules begin.
+	pub eip150_transition: BlockNumber,
+	/// Number of first block where EIP-155 rules begin.
+	pub eip155_transition: BlockNumber,
+	/// Number of first block where EIP-160 rules begin.
+	pub eip160_transition: BlockNumber,
+	/// Number of first block where EIP-161.abc begin.
+	pub eip161abc_transition: BlockNumber,
+	/// Number of first block where EIP-161.d begins.
+	pub eip161d_transition: BlockNumber,
+	/// Maximum amount of code that can be deploying into a contract (EIP-170).
+	pub max_code_size: u64,
+	/// Number of first block where EIP-140 (`REVERT`) rules begin.
+	pub eip140_transition: BlockNumber,
+	/// Number of first block where EIP-211 (`RETURNDATASIZE`/`RETURNDATACOPY`) rules begin.
+	pub eip211_transition: BlockNumber,
+	/// Number of first block where EIP-214 (`STATICCALL`) rules begin.
+	pub eip214_transition: BlockNumber,
+	/// Number of first block where EIP-145 (`SHL`/`SHR`/`SAR`) rules begin.
+	pub eip145_transition: BlockNumber,
+	/// Number of first block where EIP-1014 (`CREATE2`) rules begin.
+	pub eip1014_transition: BlockNumber,
+	/// Number of first block where EIP-1283 (`SSTORE` net gas metering) rules begin.
+	pub eip1283_transition: BlockNumber,
+	/// Number of first block where wasm contracts can be deployed and executed.
+	pub wasm_activation_transition: BlockNumber,
+}
+
+impl CommonParams {
+	/// Build the EVM schedule for the given block number from the transition table.
+	pub fn schedule(&self, block_number: u64) -> Schedule {
+		let mut schedule = if block_number < self.eip150_transition {
+			Schedule::new_homestead()
+		} else {
+			Schedule::new_post_eip150(
+				self.max_code_size as usize,
+				block_number >= self.eip160_transition,
+				block_number >= self.eip161abc_transition,
+				block_number >= self.eip161d_transition
+			)
+		};
+		self.update_schedule(block_number, &mut schedule);
+		schedule
+	}
+
+	/// Apply common spec config parameters to the schedule.
+	pub fn update_schedule(&self, block_number: u64, schedule: &mut Schedule) {
+		schedule.have_revert = block_number >= self.eip140_transition;
+		schedule.have_return_data = block_number >= self.eip211_transition;
+		schedule.have_static_call = block_number >= self.eip214_transition;
+		schedule.have_bitwise_shifting = block_number >= self.eip145_transition;
+		schedule.have_create2 = block_number >= self.eip1014_transition;
+		schedule.sstore_net_metering = block_number >= self.eip1283_transition;
+		if block_number >= self.wasm_activation_transition {
+			schedule.wasm = Some(Default::default());
+		}
+	}
 }
 
 impl From<ethjson::spec::Params> for CommonParams {
@@ -55,10 +113,58 @@ impl From<ethjson::spec::Params> for CommonParams {
 			subprotocol_name: p.subprotocol_name.unwrap_or_else(|| "eth".to_owned()),
 			min_gas_limit: p.min_gas_limit.into(),
 			fork_block: if let (Some(n), Some(h)) = (p.fork_block, p.fork_hash) { Some((n.into(), h.into())) } else { None },
+			eip150_transition: p.eip150_transition.map_or(0, Into::into),
+			eip155_transition: p.eip155_transition.map_or(0, Into::into),
+			eip160_transition: p.eip160_transition.map_or(0, Into::into),
+			eip161abc_transition: p.eip161abc_transition.map_or(0, Into::into),
+			eip161d_transition: p.eip161d_transition.map_or(BlockNumber::max_value(), Into::into),
+			max_code_size: p.max_code_size.map_or(u64::max_value(), Into::into),
+			eip140_transition: p.eip140_transition.map_or(BlockNumber::max_value(), Into::into),
+			eip211_transition: p.eip211_transition.map_or(BlockNumber::max_value(), Into::into),
+			eip214_transition: p.eip214_transition.map_or(BlockNumber::max_value(), Into::into),
+			eip145_transition: p.eip145_transition.map_or(BlockNumber::max_value(), Into::into),
+			eip1014_transition: p.eip1014_transition.map_or(BlockNumber::max_value(), Into::into),
+			eip1283_transition: p.eip1283_transition.map_or(BlockNumber::max_value(), Into::into),
+			wasm_activation_transition: p.wasm_activation_transition.map_or(BlockNumber::max_value(), Into::into),
 		}
 	}
 }
 
+/// Fill in the EIP-150 to EIP-161 transitions and the code size limit not set in `params`.
+/// Ethash specs used to keep them in the engine parameters, which are still honoured; for
+/// the other engines the defaults reproduce the rules they used to hard-code.
+fn engine_params(mut p: ethjson::spec::Params, engine: &ethjson::spec::Engine) -> ethjson::spec::Params {
+	let never = Some(ethjson::uint::Uint(U256::from(BlockNumber::max_value())));
+	match *engine {
+		ethjson::spec::Engine::Ethash(ref ethash) => {
+			let legacy = &ethash.params;
+			p.eip150_transition = p.eip150_transition.or(legacy.eip150_transition);
+			p.eip155_transition = p.eip155_transition.or(legacy.eip155_transition);
+			p.eip160_transition = p.eip160_transition.or(legacy.eip160_transition);
+			p.eip161abc_transition = p.eip161abc_transition.or(legacy.eip161abc_transition);
+			p.eip161d_transition = p.eip161d_transition.or(legacy.eip161d_transition);
+			p.max_code_size = p.max_code_size.or(legacy.max_code_size);
+		},
+		ethjson::spec::Engine::Null | ethjson::spec::Engine::BasicAuthority(_) => {
+			// Homestead rules
+			p.eip150_transition = p.eip150_transition.or(never);
+			p.eip155_transition = p.eip155_transition.or(never);
+		},
+		ethjson::spec::Engine::InstantSeal => {
+			// EIP-150 rules without EIP-160 and EIP-161
+			p.eip155_transition = p.eip155_transition.or(never);
+			p.eip160_transition = p.eip160_transition.or(never);
+			p.eip161abc_transition = p.eip161abc_transition.or(never);
+		},
+		ethjson::spec::Engine::AuthorityRound(_) => {
+			// EIP-150, EIP-160 and EIP-161 rules
+			p.eip155_transition = p.eip155_transition.or(never);
+			p.eip161d_transition = p.eip161d_transition.or(Some(ethjson::uint::Uint(U256::zero())));
+		},
+	}
+	p
+}
+
 /// Parameters for a block chain; includes both those intrinsic to the design of the
 /// chain and those to be interpreted by the active chain engine.
 pub struct Spec {
@@ -108,7 +214,7 @@ impl From<ethjson::spec::Spec> for Spec {
 		let builtins = s.accounts.builtins().into_iter().map(|p| (p.0.into(), From::from(p.1))).collect();
 		let g = Genesis::from(s.genesis);
 		let seal: GenericSeal = g.seal.into();
-		let params = CommonParams::from(s.params);
+		let params = CommonParams::from(engine_params(s.params, &s.engine));
 		Spec {
 			name: s.name.into(),
 			params: params.clone(),
@@ -222,6 +328,11 @@ impl Spec {
 		self.state_root_memo = RwLock::new(g.state_root);
 	}
 
+	/// Get the genesis state.
+	pub fn genesis_state(&self) -> &PodState {
+		&self.genesis_state
+	}
+
 	/// Alter the value of the genesis state.
 	pub fn set_genesis_state(&mut self, s: PodState) {
 		self.genesis_state = s;
@@ -283,6 +394,7 @@ mod tests {
 	use util::hash::*;
 	use util::sha3::*;
 	use views::*;
+	use env_info::EnvInfo;
 	use super::*;
 
 	// https://github.com/ethcore/parity/issues/1840
@@ -299,4 +411,141 @@ mod tests {
 		let genesis = test_spec.genesis_block();
 		assert_eq!(BlockView::new(&genesis).header_view().sha3(), H256::from_str("0cd786a2425d16f152c658316c423e6ce1181e15c3295826d7c9904cba9ce303").unwrap());
 	}
+
+	#[test]
+	fn legacy_ethash_transitions() {
+		let spec = Spec::load(r#"{
+			"name": "Legacy (Test)",
+			"engine": {
+				"Ethash": {
+					"params": {
+						"gasLimitBoundDivisor": "0x0400",
+						"minimumDifficulty": "0x020000",
+						"difficultyBoundDivisor": "0x0800",
+						"durationLimit": "0x0d",
+						"blockReward": "0x4563918244F40000",
+						"homesteadTransition": "0x05",
+						"eip150Transition": "0x0a",
+						"eip155Transition": "0x14",
+						"eip160Transition": "0x14",
+						"eip161abcTransition": "0x14",
+						"eip161dTransition": "0x1e",
+						"maxCodeSize": "0x6000"
+					}
+				}
+			},
+			"params": {
+				"accountStartNonce": "0x00",
+				"maximumExtraDataSize": "0x20",
+				"minGasLimit": "0x1388",
+				"networkID" : "0x1",
+				"eip161dTransition": "0x28"
+			},
+			"genesis": {
+				"seal": {
+					"ethereum": {
+						"nonce": "0x0000000000000042",
+						"mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000"
+					}
+				},
+				"difficulty": "0x400000000",
+				"author": "0x0000000000000000000000000000000000000000",
+				"timestamp": "0x00",
+				"parentHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+				"extraData": "0x",
+				"gasLimit": "0x1388"
+			},
+			"accounts": {}
+		}"#.as_bytes()).unwrap();
+
+		assert_eq!(spec.params.eip150_transition, 10);
+		assert_eq!(spec.params.eip155_transition, 20);
+		assert_eq!(spec.params.eip160_transition, 20);
+		assert_eq!(spec.params.eip161abc_transition, 20);
+		assert_eq!(spec.params.eip161d_transition, 40);
+		assert_eq!(spec.params.max_code_size, 24576);
+
+		let engine = &spec.engine;
+		let info = |number| EnvInfo { number: number, ..Default::default() };
+		assert_eq!(engine.schedule(&info(9)).call_gas, 40);
+		assert_eq!(engine.schedule(&info(10)).call_gas, 700);
+		assert!(!engine.schedule(&info(19)).no_empty);
+		assert!(engine.schedule(&info(20)).no_empty);
+		assert!(!engine.schedule(&info(39)).kill_empty);
+		assert!(engine.schedule(&info(40)).kill_empty);
+		assert_eq!(engine.signing_network_id(&info(19)), None);
+		assert_eq!(engine.signing_network_id(&info(20)), Some(1));
+	}
+
+	#[test]
+	fn engine_default_transitions() {
+		let info = EnvInfo { number: 1_000_000, ..Default::default() };
+
+		// Homestead
+		let engine = Spec::new_null().engine;
+		assert_eq!(engine.schedule(&info).call_gas, 40);
+		assert_eq!(engine.signing_network_id(&info), None);
+
+		// EIP-150 without EIP-160 and EIP-161
+		let engine = Spec::new_instant().engine;
+		let schedule = engine.schedule(&info);
+		assert_eq!(schedule.call_gas, 700);
+		assert_eq!(schedule.exp_byte_gas, 10);
+		assert!(!schedule.no_empty);
+		assert!(!schedule.kill_empty);
+		assert_eq!(engine.signing_network_id(&info), None);
+
+		// EIP-150, EIP-160 and EIP-161
+		let engine = Spec::new_test_round().engine;
+		let schedule = engine.schedule(&info);
+		assert_eq!(schedule.call_gas, 700);
+		assert_eq!(schedule.exp_byte_gas, 50);
+		assert!(schedule.no_empty);
+		assert!(schedule.kill_empty);
+		assert_eq!(engine.signing_network_id(&info), None);
+	}
+
+	#[test]
+	fn schedule_follows_transitions() {
+		let params = CommonParams {
+			eip150_transition: 10,
+			eip160_transition: 20,
+			eip161abc_transition: 20,
+			eip161d_transition: 30,
+			max_code_size: 24576,
+			eip140_transition: 30,
+			eip211_transition: BlockNumber::max_value(),
+			eip214_transition: BlockNumber::max_value(),
+			eip145_transition: BlockNumber::max_value(),
+			eip1014_transition: BlockNumber::max_value(),
+			eip1283_transition: 30,
+			wasm_activation_transition: 30,
+			..Default::default()
+		};
+
+		let schedule = params.schedule(9);
+		assert_eq!(schedule.call_gas, 40);
+		assert!(!schedule.no_empty);
+
+		let schedule = params.schedule(10);
+		assert_eq!(schedule.call_gas, 700);
+		assert_eq!(schedule.create_data_limit, 24576);
+		assert_eq!(schedule.exp_byte_gas, 10);
+		assert!(!schedule.no_empty);
+
+		let schedule = params.schedule(20);
+		assert_eq!(schedule.exp_byte_gas, 50);
+		assert!(schedule.no_empty);
+		assert!(!schedule.kill_empty);
+		assert!(!schedule.have_revert);
+		assert!(!schedule.sstore_net_metering);
+		assert!(schedule.wasm.is_none());
+
+		let schedule = params.schedule(30);
+		assert!(schedule.kill_empty);
+		assert!(schedule.have_revert);
+		assert!(!schedule.have_create2);
+		assert!(schedule.sstore_net_metering);
+		assert!(schedule.wasm.is_some());
+	}
 }
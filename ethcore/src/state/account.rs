@@ -166,6 +166,19 @@ impl Account {
 		if let Some(value) = self.cached_storage_at(key) {
 			return value;
 		}
+		self.committed_storage_at(db, key)
+	}
+
+	/// Get (and cache) the contents of the trie's storage at `key` as it was last committed.
+	/// Ignores modified storage, so this is the value the slot had before the current transaction.
+	pub fn original_storage_at(&self, db: &HashDB, key: &H256) -> H256 {
+		if let Some(value) = self.storage_cache.borrow_mut().get_mut(key) {
+			return value.clone()
+		}
+		self.committed_storage_at(db, key)
+	}
+
+	fn committed_storage_at(&self, db: &HashDB, key: &H256) -> H256 {
 		let db = SecTrieDB::new(db, &self.storage_root)
 			.expect("Account storage_root initially set to zero (valid) and only altered by SecTrieDBMut. \
 			SecTrieDBMut would not set it to an invalid state root. Therefore the root is valid and DB creation \
@@ -325,6 +338,11 @@ impl Account {
 		self.nonce = self.nonce + U256::from(1u8);
 	}
 
+	/// Set the nonce of the account.
+	pub fn set_nonce(&mut self, nonce: U256) {
+		self.nonce = nonce;
+	}
+
 	/// Increase account balance.
 	pub fn add_balance(&mut self, x: &U256) {
 		self.balance = self.balance + *x;
@@ -445,15 +463,20 @@ impl Account {
 	/// Returns a merkle proof of the storage trie node with all nodes before `from_level`
 	/// omitted.
 	pub fn prove_storage(&self, db: &HashDB, storage_key: H256, from_level: u32) -> Result<Vec<Bytes>, Box<TrieError>> {
+		self.prove_storage_value(db, storage_key, from_level).map(|(_, proof)| proof)
+	}
+
+	/// Like `prove_storage`, but also returns the value stored under the key.
+	pub fn prove_storage_value(&self, db: &HashDB, storage_key: H256, from_level: u32) -> Result<(H256, Vec<Bytes>), Box<TrieError>> {
 		use util::trie::{Trie, TrieDB};
 		use util::trie::recorder::{Recorder, BasicRecorder as TrieRecorder};
 
 		let mut recorder = TrieRecorder::with_depth(from_level);
 
 		let trie = try!(TrieDB::new(db, &self.storage_root));
-		let _ = try!(trie.get_recorded(&storage_key, &mut recorder));
+		let value: U256 = try!(trie.get_recorded(&storage_key, &mut recorder)).map_or_else(U256::zero, |v| decode(&*v));
 
-		Ok(recorder.drain().into_iter().map(|r| r.data).collect())
+		Ok((value.into(), recorder.drain().into_iter().map(|r| r.data).collect()))
 	}
 }
 
@@ -499,6 +522,22 @@ mod tests {
 		assert_eq!(a.storage_at(&db.immutable(), &H256::from(&U256::from(0x01u64))), H256::new());
 	}
 
+	#[test]
+	fn original_storage_at() {
+		let mut db = MemoryDB::new();
+		let mut db = AccountDBMut::new(&mut db, &Address::new());
+		let mut a = Account::new_contract(69.into(), 0.into());
+		a.set_storage(0.into(), 0x1234.into());
+		assert_eq!(a.original_storage_at(&db.immutable(), &0.into()), H256::new());
+		a.commit_storage(&Default::default(), &mut db);
+
+		a.set_storage(0.into(), 0x5678.into());
+		a.set_storage(1.into(), 0x5678.into());
+		assert_eq!(a.storage_at(&db.immutable(), &0.into()), 0x5678.into());
+		assert_eq!(a.original_storage_at(&db.immutable(), &0.into()), 0x1234.into());
+		assert_eq!(a.original_storage_at(&db.immutable(), &1.into()), H256::new());
+	}
+
 	#[test]
 	fn note_code() {
 		let mut db = MemoryDB::new();
@@ -27,6 +27,8 @@ use trace::FlatTrace;
 use pod_account::*;
 use pod_state::{self, PodState};
 use types::state_diff::StateDiff;
+use types::state_override::StateOverride;
+use types::account_proof::{AccountProof, StorageProof};
 use transaction::SignedTransaction;
 use state_db::StateDB;
 
@@ -437,6 +439,15 @@ impl State {
 		r
 	}
 
+	/// Get the value of storage at a specific position as it was committed before the current transaction,
+	/// ignoring any uncommitted changes.
+	pub fn original_storage_at(&self, address: &Address, key: &H256) -> H256 {
+		self.ensure_cached(address, RequireCache::None, true, |a| a.map_or(H256::new(), |a| {
+			let account_db = self.factories.accountdb.readonly(self.db.as_hashdb(), a.address_hash(address));
+			a.original_storage_at(account_db.as_hashdb(), key)
+		}))
+	}
+
 	/// Get accounts' code.
 	pub fn code(&self, a: &Address) -> Option<Arc<Bytes>> {
 		self.ensure_cached(a, RequireCache::Code, true,
@@ -508,6 +519,47 @@ impl State {
 		self.require_or_from(a, true, || Account::new_contract(0.into(), self.account_start_nonce), |_|{}).reset_code(code);
 	}
 
+	/// Override the state of given accounts. Meant for non-persistent calls only; the
+	/// resulting state should never be committed.
+	pub fn apply_override(&mut self, state_override: &StateOverride) {
+		for (address, account) in &state_override.accounts {
+			if let Some(ref storage) = account.state {
+				// start from an account with empty storage, keeping the rest.
+				let balance = self.balance(address);
+				let nonce = self.nonce(address);
+				let code = self.code(address);
+				self.insert_cache(address, AccountEntry::new_dirty(Some(Account::new_contract(balance, nonce))));
+				if let Some(code) = code {
+					if !code.is_empty() {
+						self.init_code(address, (*code).clone());
+					}
+				}
+				for (key, value) in storage {
+					self.set_storage(address, key.clone(), value.clone());
+				}
+			}
+			if let Some(ref storage) = account.state_diff {
+				for (key, value) in storage {
+					self.set_storage(address, key.clone(), value.clone());
+				}
+			}
+			if let Some(ref balance) = account.balance {
+				let current = self.balance(address);
+				if *balance > current {
+					self.add_balance(address, &(*balance - current), CleanupMode::ForceCreate);
+				} else {
+					self.sub_balance(address, &(current - *balance));
+				}
+			}
+			if let Some(ref nonce) = account.nonce {
+				self.require(address, false).set_nonce(nonce.clone());
+			}
+			if let Some(ref code) = account.code {
+				self.reset_code(address, code.clone());
+			}
+		}
+	}
+
 	/// Execute a given transaction.
 	/// This will change the state accordingly.
 	pub fn apply(&mut self, env_info: &EnvInfo, engine: &Engine, t: &SignedTransaction, tracing: bool) -> ApplyResult {
@@ -795,6 +847,46 @@ impl State {
 		acc.prove_storage(account_db.as_hashdb(), storage_key, from_level)
 	}
 
+	/// Prove an account and the values of some of its storage keys, looked up in the secure trie.
+	/// Everything is read from the committed state, so the proofs are relative to `root()`.
+	/// Returns the encountered trie error if any node is missing.
+	pub fn prove_account_with_storage(&self, address: &Address, storage_keys: &[H256]) -> Result<AccountProof, Box<TrieError>> {
+		let account_key = address.sha3();
+		let mut recorder = TrieRecorder::with_depth(0);
+		let trie = try!(TrieDB::new(self.db.as_hashdb(), &self.root));
+		let account = try!(trie.get_recorded(&account_key, &mut recorder)).map(|rlp| Account::from_rlp(&rlp));
+		let proof = recorder.drain().into_iter().map(|r| r.data).collect();
+
+		let account_db = self.factories.accountdb.readonly(self.db.as_hashdb(), account_key);
+		let storage = try!(storage_keys.iter().map(|key| -> Result<StorageProof, Box<TrieError>> {
+			// the account proof already shows that a missing account has no storage.
+			let (value, proof) = match account {
+				Some(ref account) => try!(account.prove_storage_value(account_db.as_hashdb(), key.sha3(), 0)),
+				None => (H256::zero(), Vec::new()),
+			};
+			Ok(StorageProof { key: key.clone(), value: value, proof: proof })
+		}).collect::<Result<Vec<_>, _>>());
+
+		Ok(match account {
+			Some(account) => AccountProof {
+				balance: *account.balance(),
+				nonce: *account.nonce(),
+				code_hash: account.code_hash(),
+				storage_root: account.storage_root().cloned().expect("account read from the trie has no storage changes; qed"),
+				proof: proof,
+				storage: storage,
+			},
+			None => AccountProof {
+				balance: U256::zero(),
+				nonce: self.account_start_nonce,
+				code_hash: SHA3_EMPTY,
+				storage_root: SHA3_NULL_RLP,
+				proof: proof,
+				storage: storage,
+			},
+		})
+	}
+
 	/// Get code by address hash.
 	/// Only works when backed by a secure trie.
 	pub fn code_by_address_hash(&self, account_key: H256) -> Result<Option<Bytes>, Box<TrieError>> {
@@ -843,6 +935,7 @@ mod tests {
 
 	use std::sync::Arc;
 	use std::str::FromStr;
+	use std::collections::BTreeMap;
 	use rustc_serialize::hex::FromHex;
 	use super::*;
 	use util::{U256, H256, FixedHash, Address, Hashable};
@@ -854,6 +947,7 @@ mod tests {
 	use util::log::init_log;
 	use trace::{FlatTrace, TraceError, trace};
 	use types::executed::CallType;
+	use types::state_override::{StateOverride, AccountOverride};
 
 	#[test]
 	fn should_apply_create_transaction() {
@@ -885,6 +979,7 @@ mod tests {
 				value: 100.into(),
 				gas: 77412.into(),
 				init: vec![96, 16, 128, 96, 12, 96, 0, 57, 96, 0, 243, 0, 96, 0, 53, 84, 21, 96, 9, 87, 0, 91, 96, 32, 53, 96, 0, 53, 85],
+				create_type: trace::CreateType::Create,
 			}),
 			result: trace::Res::Create(trace::CreateResult {
 				gas_used: U256::from(3224),
@@ -944,6 +1039,7 @@ mod tests {
 				value: 100.into(),
 				gas: 78792.into(),
 				init: vec![91, 96, 0, 86],
+				create_type: trace::CreateType::Create,
 			}),
 			result: trace::Res::FailedCreate(TraceError::OutOfGas),
 			subtraces: 0
@@ -1944,4 +2040,46 @@ mod tests {
 		new_state.diff_from(state);
 	}
 
+	#[test]
+	fn should_apply_state_override() {
+		let mut state_result = get_temp_state();
+		let mut state = state_result.reference_mut();
+
+		let a: Address = 0xa.into();
+		let b: Address = 0xb.into();
+		state.init_code(&a, vec![1, 2, 3]);
+		state.add_balance(&a, &256.into(), CleanupMode::NoEmpty);
+		state.set_storage(&a, 1.into(), 1.into());
+		state.set_storage(&a, 2.into(), 2.into());
+		state.add_balance(&b, &256.into(), CleanupMode::NoEmpty);
+		state.set_storage(&b, 1.into(), 1.into());
+		state.commit().unwrap();
+
+		let mut accounts = BTreeMap::new();
+		accounts.insert(a.clone(), AccountOverride {
+			state: Some(vec![(H256::from(3), H256::from(3))].into_iter().collect()),
+			..Default::default()
+		});
+		accounts.insert(b.clone(), AccountOverride {
+			balance: Some(1.into()),
+			nonce: Some(5.into()),
+			code: Some(vec![4, 5]),
+			state_diff: Some(vec![(H256::from(2), H256::from(2))].into_iter().collect()),
+			..Default::default()
+		});
+		state.apply_override(&StateOverride { accounts: accounts });
+
+		assert_eq!(state.balance(&a), 256.into());
+		assert_eq!(state.code(&a), Some(Arc::new(vec![1, 2, 3])));
+		assert_eq!(state.storage_at(&a, &1.into()), H256::zero());
+		assert_eq!(state.storage_at(&a, &2.into()), H256::zero());
+		assert_eq!(state.storage_at(&a, &3.into()), 3.into());
+
+		assert_eq!(state.balance(&b), 1.into());
+		assert_eq!(state.nonce(&b), 5.into());
+		assert_eq!(state.code(&b), Some(Arc::new(vec![4, 5])));
+		assert_eq!(state.storage_at(&b, &1.into()), 1.into());
+		assert_eq!(state.storage_at(&b, &2.into()), 2.into());
+	}
+
 }
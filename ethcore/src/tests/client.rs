@@ -15,7 +15,7 @@
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
 use io::IoChannel;
-use client::{BlockChainClient, MiningBlockChainClient, Client, ClientConfig, BlockId};
+use client::{BlockChainClient, MiningBlockChainClient, ProvingBlockChainClient, Client, ClientConfig, BlockId, TransactionId, CallAnalytics};
 use state::CleanupMode;
 use ethereum;
 use block::IsBlock;
@@ -28,6 +28,10 @@ use rlp::{Rlp, View};
 use spec::Spec;
 use views::BlockView;
 use util::stats::Histogram;
+use ethkey::KeyPair;
+use transaction::{Transaction, Action};
+use trace::trace::Res;
+use contract_address;
 
 #[test]
 fn imports_from_empty() {
@@ -284,3 +288,217 @@ fn change_history_size() {
 	let client = Client::new(config, &test_spec, dir.as_path(), Arc::new(Miner::with_spec(&test_spec)), IoChannel::disconnected(), &db_config).unwrap();
 	assert_eq!(client.state().balance(&address), 100.into());
 }
+
+#[test]
+fn call_many_accumulates_state() {
+	let client_result = generate_dummy_client(1);
+	let client = client_result.reference();
+	let sender = Address::from(1);
+
+	// stores 0x2a and deploys code returning its first storage slot
+	let create = Transaction {
+		nonce: 0.into(),
+		gas_price: 0.into(),
+		gas: 1_000_000.into(),
+		action: Action::Create,
+		value: 0.into(),
+		data: "602a600055600b6011600039600b6000f360005460005260206000f3".from_hex().unwrap(),
+	}.fake_sign(sender);
+	let call = Transaction {
+		nonce: 1.into(),
+		gas_price: 0.into(),
+		gas: 1_000_000.into(),
+		action: Action::Call(contract_address(&sender, &0.into())),
+		value: 0.into(),
+		data: vec![],
+	}.fake_sign(sender);
+
+	let results = client.call_many(vec![create, call], vec![], BlockId::Latest).unwrap();
+	assert_eq!(results.len(), 2);
+	assert_eq!(results[1].output, H256::from(0x2a).to_vec());
+}
+
+#[test]
+fn replay_block_transactions_accumulates_state() {
+	let client_result = generate_dummy_client_with_data(2, 2, &[0.into()]);
+	let client = client_result.reference();
+	let sender = KeyPair::from_secret("".sha3()).unwrap().address();
+
+	let analytics = CallAnalytics { transaction_tracing: true, ..Default::default() };
+	let results = client.replay_block_transactions(BlockId::Number(2), analytics).unwrap();
+	let created: Vec<_> = results.iter().map(|executed| match executed.trace[0].result {
+		Res::Create(ref create) => create.address,
+		ref res => panic!("Unexpected trace result: {:?}", res),
+	}).collect();
+
+	// the second transaction sees the nonce bumped by the first one
+	assert_eq!(created, vec![contract_address(&sender, &2.into()), contract_address(&sender, &3.into())]);
+}
+
+#[test]
+fn replay_block_transactions_matches_receipts_with_net_gas_metering() {
+	// the contract at 0xaa increments its first storage slot.
+	let spec = Spec::load(r#"{
+		"name": "Net gas metering (Test)",
+		"engine": {
+			"Null": null
+		},
+		"params": {
+			"accountStartNonce": "0x0",
+			"maximumExtraDataSize": "0x20",
+			"minGasLimit": "0x1388",
+			"networkID" : "0x2",
+			"eip1283Transition": "0x0"
+		},
+		"genesis": {
+			"seal": {
+				"ethereum": {
+					"nonce": "0x00006d6f7264656e",
+					"mixHash": "0x00000000000000000000000000000000000000647572616c65787365646c6578"
+				}
+			},
+			"difficulty": "0x20000",
+			"author": "0x0000000000000000000000000000000000000000",
+			"timestamp": "0x00",
+			"parentHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+			"extraData": "0x",
+			"gasLimit": "0x2fefd8"
+		},
+		"accounts": {
+			"00000000000000000000000000000000000000aa": { "balance": "0", "nonce": "0", "code": "0x600054600101600055" },
+			"9cce34f7ab185c7aba1b7c8140d620b4bda941d6": { "balance": "1606938044258990275541962092341162602522202993782792835301376", "nonce": "0" }
+		}
+	}"#.as_bytes()).unwrap();
+	let dir = RandomTempPath::new();
+	let db_config = DatabaseConfig::with_columns(::db::NUM_COLUMNS);
+	let client = Client::new(ClientConfig::default(), &spec, dir.as_path(), Arc::new(Miner::with_spec(&spec)), IoChannel::disconnected(), &db_config).unwrap();
+	let kp = KeyPair::from_secret("".sha3()).unwrap();
+
+	let mut b = client.prepare_open_block(Address::default(), (3141562.into(), 31415620.into()), vec![]);
+	for nonce in 0..2 {
+		b.push_transaction(Transaction {
+			nonce: nonce.into(),
+			gas_price: 0.into(),
+			gas: 100000.into(),
+			action: Action::Call(Address::from(0xaa)),
+			value: 0.into(),
+			data: vec![],
+		}.sign(kp.secret(), None), None).unwrap();
+	}
+	let b = b.close_and_lock().seal(&*spec.engine, vec![]).unwrap();
+	client.import_sealed_block(b).unwrap();
+
+	let receipts = client.localized_block_receipts(BlockId::Latest).unwrap();
+	// the second transaction resets an already stored slot instead of setting a fresh one.
+	assert_eq!(receipts[0].gas_used - receipts[1].gas_used, 15000.into());
+
+	let results = client.replay_block_transactions(BlockId::Latest, Default::default()).unwrap();
+	assert_eq!(
+		results.iter().map(|executed| executed.gas_used).collect::<Vec<_>>(),
+		receipts.iter().map(|receipt| receipt.gas_used).collect::<Vec<_>>()
+	);
+
+	let second = client.replay(TransactionId::Hash(receipts[1].transaction_hash.clone()), Default::default()).unwrap();
+	assert_eq!(second.gas_used, receipts[1].gas_used);
+}
+
+#[test]
+fn rebuild_traces_of_untraced_blocks() {
+	let source_result = generate_dummy_client_with_data(2, 2, &[0.into()]);
+	let source = source_result.reference();
+	let sender = KeyPair::from_secret("".sha3()).unwrap().address();
+
+	let dir = RandomTempPath::new();
+	let spec = Spec::new_null();
+	let db_config = DatabaseConfig::with_columns(::db::NUM_COLUMNS);
+	{
+		let client = Client::new(ClientConfig::default(), &spec, dir.as_path(), Arc::new(Miner::with_spec(&spec)), IoChannel::disconnected(), &db_config).unwrap();
+		for number in 1..3 {
+			client.import_block(source.block(BlockId::Number(number)).unwrap()).unwrap();
+		}
+		client.flush_queue();
+		client.import_verified_blocks();
+		assert_eq!(client.chain_info().best_block_number, 2);
+	}
+
+	let mut config = ClientConfig::default();
+	config.tracing.enabled = true;
+	let client = Client::new(config, &spec, dir.as_path(), Arc::new(Miner::with_spec(&spec)), IoChannel::disconnected(), &db_config).unwrap();
+	assert_eq!(client.block_traces(BlockId::Number(2)), None);
+
+	client.rebuild_traces(1, 2).unwrap();
+
+	for (number, nonces) in vec![(1, [0u64, 1]), (2, [2, 3])] {
+		let traces = client.block_traces(BlockId::Number(number)).unwrap();
+		let created: Vec<_> = traces.iter().map(|trace| match trace.result {
+			Res::Create(ref create) => create.address,
+			ref res => panic!("Unexpected trace result: {:?}", res),
+		}).collect();
+		assert_eq!(created, nonces.iter().map(|n| contract_address(&sender, &(*n).into())).collect::<Vec<_>>());
+		assert!(traces.iter().all(|trace| trace.block_number == number));
+	}
+}
+
+#[test]
+fn proves_account_with_storage() {
+	let client_result = generate_dummy_client(0);
+	let client = client_result.reference();
+	let test_spec = get_test_spec();
+	let address = Address::from(0x10);
+	let key = H256::from(1);
+
+	let mut b = client.prepare_open_block(Address::default(), (3141562.into(), 31415620.into()), vec![]);
+	b.block_mut().fields_mut().state.add_balance(&address, &5.into(), CleanupMode::NoEmpty);
+	b.block_mut().fields_mut().state.set_storage(&address, key, H256::from(0x2a));
+	b.block_mut().fields_mut().state.commit().unwrap();
+	let b = b.close_and_lock().seal(&*test_spec.engine, vec![]).unwrap();
+	client.import_sealed_block(b).unwrap();
+
+	let state_root = BlockView::new(&client.block(BlockId::Latest).unwrap()).header().state_root().clone();
+	let proof = client.prove_account_with_storage(&address, &[key, H256::from(2)], BlockId::Latest).unwrap().unwrap();
+	assert_eq!(proof.balance, 5.into());
+	assert_eq!(proof.storage[0].value, H256::from(0x2a));
+	assert_eq!(proof.storage[1].value, H256::zero());
+
+	// the proof nodes alone must be enough to look the account up from the state root.
+	let mut db = MemoryDB::new();
+	for node in &proof.proof {
+		db.insert(node);
+	}
+	let account = TrieDB::new(&db, &state_root).unwrap().get(&address.sha3()).unwrap().unwrap();
+	let account = Rlp::new(&account);
+	assert_eq!(account.val_at::<U256>(1), proof.balance);
+	assert_eq!(account.val_at::<H256>(2), proof.storage_root);
+
+	let mut db = MemoryDB::new();
+	for node in &proof.storage[0].proof {
+		db.insert(node);
+	}
+	let value = TrieDB::new(&db, &proof.storage_root).unwrap().get(&key.sha3()).unwrap().unwrap();
+	assert_eq!(Rlp::new(&value).as_val::<U256>(), 0x2a.into());
+
+	assert!(client.prove_account_with_storage(&address, &[], BlockId::Number(2)).is_none());
+}
+
+#[test]
+fn localizes_block_receipts() {
+	let client_result = generate_dummy_client_with_data(2, 2, &[0.into()]);
+	let client = client_result.reference();
+	let sender = KeyPair::from_secret("".sha3()).unwrap().address();
+
+	let receipts = client.localized_block_receipts(BlockId::Number(2)).unwrap();
+	assert_eq!(receipts.len(), 2);
+	assert!(!receipts[0].gas_used.is_zero());
+	assert_eq!(receipts[0].cumulative_gas_used, receipts[0].gas_used);
+	assert_eq!(receipts[1].cumulative_gas_used, receipts[0].gas_used + receipts[1].gas_used);
+	assert_eq!(
+		receipts.iter().map(|receipt| receipt.contract_address.clone()).collect::<Vec<_>>(),
+		vec![Some(contract_address(&sender, &2.into())), Some(contract_address(&sender, &3.into()))]
+	);
+
+	// must match the receipts looked up one by one.
+	for receipt in receipts {
+		assert_eq!(client.transaction_receipt(TransactionId::Hash(receipt.transaction_hash.clone())), Some(receipt));
+	}
+	assert!(client.localized_block_receipts(BlockId::Number(3)).is_none());
+}
@@ -49,13 +49,19 @@ pub enum ChainEra {
 pub struct TestEngine {
 	engine: Arc<Engine>,
 	max_depth: usize,
+	schedule: Schedule,
 }
 
 impl TestEngine {
 	pub fn new(max_depth: usize) -> TestEngine {
+		TestEngine::new_with_schedule(max_depth, Schedule::new_frontier())
+	}
+
+	pub fn new_with_schedule(max_depth: usize, schedule: Schedule) -> TestEngine {
 		TestEngine {
 			engine: ethereum::new_frontier_test().engine,
 			max_depth: max_depth,
+			schedule: schedule,
 		}
 	}
 }
@@ -74,7 +80,7 @@ impl Engine for TestEngine {
 	}
 
 	fn schedule(&self, _env_info: &EnvInfo) -> Schedule {
-		let mut schedule = Schedule::new_frontier();
+		let mut schedule = self.schedule.clone();
 		schedule.max_depth = self.max_depth;
 		schedule
 	}
@@ -440,13 +446,7 @@ pub fn get_default_ethash_params() -> EthashParams{
 		difficulty_hardfork_transition: u64::max_value(),
 		difficulty_hardfork_bound_divisor: U256::from(0),
 		bomb_defuse_transition: u64::max_value(),
-		eip150_transition: u64::max_value(),
-		eip155_transition: u64::max_value(),
-		eip160_transition: u64::max_value(),
-		eip161abc_transition: u64::max_value(),
-		eip161d_transition: u64::max_value(),
 		ecip1010_pause_transition: u64::max_value(),
 		ecip1010_continue_transition: u64::max_value(),
-		max_code_size: u64::max_value(),
 	}
 }
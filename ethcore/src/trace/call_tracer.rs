@@ -0,0 +1,204 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+
+//! Call tree tracer.
+
+use util::{Bytes, Address, U256};
+use action_params::ActionParams;
+use trace::trace::{Call, Create, RewardType};
+use trace::{Tracer, CallFrame, FrameKind, TraceError};
+
+/// Builds a nested call tree of all calls, creates and suicides.
+#[derive(Default)]
+pub struct CallTracer {
+	frames: Vec<CallFrame>,
+}
+
+fn call_frame(call: Option<Call>, gas_used: U256, output: Bytes, error: Option<TraceError>, subs: Vec<CallFrame>) -> CallFrame {
+	let call = call.expect("self.prepare_trace_call().is_some(): so we must be tracing: qed");
+	CallFrame {
+		kind: FrameKind::Call(call.call_type),
+		from: call.from,
+		to: Some(call.to),
+		value: call.value,
+		gas: call.gas,
+		gas_used: gas_used,
+		input: call.input,
+		output: output,
+		error: error,
+		calls: subs,
+	}
+}
+
+fn create_frame(create: Option<Create>, address: Option<Address>, gas_used: U256, output: Bytes, error: Option<TraceError>, subs: Vec<CallFrame>) -> CallFrame {
+	let create = create.expect("self.prepare_trace_create().is_some(): so we must be tracing: qed");
+	CallFrame {
+		kind: FrameKind::Create(create.create_type),
+		from: create.from,
+		to: address,
+		value: create.value,
+		gas: create.gas,
+		gas_used: gas_used,
+		input: create.init,
+		output: output,
+		error: error,
+		calls: subs,
+	}
+}
+
+impl Tracer for CallTracer {
+	type Output = CallFrame;
+
+	fn prepare_trace_call(&self, params: &ActionParams) -> Option<Call> {
+		Some(Call::from(params.clone()))
+	}
+
+	fn prepare_trace_create(&self, params: &ActionParams) -> Option<Create> {
+		Some(Create::from(params.clone()))
+	}
+
+	fn prepare_trace_output(&self) -> Option<Bytes> {
+		Some(vec![])
+	}
+
+	fn trace_call(&mut self, call: Option<Call>, gas_used: U256, output: Option<Bytes>, subs: Vec<CallFrame>) {
+		let output = output.expect("self.prepare_trace_output().is_some(): so we must be tracing: qed");
+		self.frames.push(call_frame(call, gas_used, output, None, subs));
+	}
+
+	fn trace_create(&mut self, create: Option<Create>, gas_used: U256, code: Option<Bytes>, address: Address, subs: Vec<CallFrame>) {
+		let code = code.expect("self.prepare_trace_output().is_some(): so we must be tracing: qed");
+		self.frames.push(create_frame(create, Some(address), gas_used, code, None, subs));
+	}
+
+	fn trace_failed_call(&mut self, call: Option<Call>, subs: Vec<CallFrame>, error: TraceError) {
+		// a failed call consumes all of its gas.
+		let gas = call.as_ref().map_or_else(U256::zero, |c| c.gas);
+		self.frames.push(call_frame(call, gas, vec![], Some(error), subs));
+	}
+
+	fn trace_failed_create(&mut self, create: Option<Create>, subs: Vec<CallFrame>, error: TraceError) {
+		let gas = create.as_ref().map_or_else(U256::zero, |c| c.gas);
+		self.frames.push(create_frame(create, None, gas, vec![], Some(error), subs));
+	}
+
+	fn trace_reverted_call(&mut self, call: Option<Call>, gas_used: U256, output: &[u8], subs: Vec<CallFrame>) {
+		self.frames.push(call_frame(call, gas_used, output.to_vec(), Some(TraceError::Reverted), subs));
+	}
+
+	fn trace_reverted_create(&mut self, create: Option<Create>, gas_used: U256, output: &[u8], subs: Vec<CallFrame>) {
+		self.frames.push(create_frame(create, None, gas_used, output.to_vec(), Some(TraceError::Reverted), subs));
+	}
+
+	fn trace_suicide(&mut self, address: Address, balance: U256, refund_address: Address) {
+		self.frames.push(CallFrame {
+			kind: FrameKind::Suicide,
+			from: address,
+			to: Some(refund_address),
+			value: balance,
+			gas: U256::zero(),
+			gas_used: U256::zero(),
+			input: vec![],
+			output: vec![],
+			error: None,
+			calls: vec![],
+		});
+	}
+
+	// rewards are not part of any call tree and are only traced by engines closing a block,
+	// which never use this tracer.
+	fn trace_reward(&mut self, _author: Address, _value: U256, _reward_type: RewardType) {
+	}
+
+	fn subtracer(&self) -> Self {
+		CallTracer::default()
+	}
+
+	fn traces(self) -> Vec<CallFrame> {
+		self.frames
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use util::{U256, Address};
+	use action_params::ActionParams;
+	use types::executed::CallType;
+	use trace::{Tracer, CallFrame, FrameKind, TraceError};
+	use super::CallTracer;
+
+	fn params(from: u64, to: u64) -> ActionParams {
+		let mut params = ActionParams::default();
+		params.sender = Address::from(from);
+		params.address = Address::from(to);
+		params.gas = U256::from(1000);
+		params.data = Some(vec![0x12]);
+		params.call_type = CallType::Call;
+		params
+	}
+
+	#[test]
+	fn builds_nested_frames() {
+		let mut tracer = CallTracer::default();
+		let outer = params(1, 2);
+		let inner = params(2, 3);
+
+		let mut subtracer = tracer.subtracer();
+		let info = subtracer.prepare_trace_call(&inner);
+		subtracer.trace_reverted_call(info, U256::from(10), &[0xff], vec![]);
+
+		let info = tracer.prepare_trace_call(&outer);
+		tracer.trace_call(info, U256::from(100), Some(vec![0x34]), subtracer.traces());
+
+		let frames = tracer.traces();
+		assert_eq!(frames, vec![CallFrame {
+			kind: FrameKind::Call(CallType::Call),
+			from: Address::from(1),
+			to: Some(Address::from(2)),
+			value: U256::zero(),
+			gas: U256::from(1000),
+			gas_used: U256::from(100),
+			input: vec![0x12],
+			output: vec![0x34],
+			error: None,
+			calls: vec![CallFrame {
+				kind: FrameKind::Call(CallType::Call),
+				from: Address::from(2),
+				to: Some(Address::from(3)),
+				value: U256::zero(),
+				gas: U256::from(1000),
+				gas_used: U256::from(10),
+				input: vec![0x12],
+				output: vec![0xff],
+				error: Some(TraceError::Reverted),
+				calls: vec![],
+			}],
+		}]);
+	}
+
+	#[test]
+	fn failed_call_uses_all_gas() {
+		let mut tracer = CallTracer::default();
+		let info = tracer.prepare_trace_call(&params(1, 2));
+		tracer.trace_failed_call(info, vec![], TraceError::OutOfGas);
+
+		let frames = tracer.traces();
+		assert_eq!(frames.len(), 1);
+		assert_eq!(frames[0].gas_used, U256::from(1000));
+		assert_eq!(frames[0].error, Some(TraceError::OutOfGas));
+	}
+}
@@ -0,0 +1,165 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tracer building flat traces and a call tree in a single run.
+
+use util::{Bytes, Address, U256};
+use action_params::ActionParams;
+use trace::trace::{Call, Create, RewardType};
+use trace::{Tracer, ExecutiveTracer, CallTracer, FlatTrace, CallFrame, TraceError};
+
+/// Feeds every traced event to both `ExecutiveTracer` and `CallTracer`.
+///
+/// Each level of execution produces a single pair of flat traces and call frames.
+#[derive(Default)]
+pub struct CombinedTracer {
+	flat: ExecutiveTracer,
+	calls: CallTracer,
+}
+
+impl CombinedTracer {
+	/// Splits the output of `traces` into flat traces and call frames.
+	pub fn split(traces: Vec<(Vec<FlatTrace>, Vec<CallFrame>)>) -> (Vec<FlatTrace>, Vec<CallFrame>) {
+		traces.into_iter().fold((vec![], vec![]), |(mut flat, mut calls), (sub_flat, sub_calls)| {
+			flat.extend(sub_flat);
+			calls.extend(sub_calls);
+			(flat, calls)
+		})
+	}
+}
+
+impl Tracer for CombinedTracer {
+	type Output = (Vec<FlatTrace>, Vec<CallFrame>);
+
+	// both tracers prepare the same data, so it's taken from the flat one.
+	fn prepare_trace_call(&self, params: &ActionParams) -> Option<Call> {
+		self.flat.prepare_trace_call(params)
+	}
+
+	fn prepare_trace_create(&self, params: &ActionParams) -> Option<Create> {
+		self.flat.prepare_trace_create(params)
+	}
+
+	fn prepare_trace_output(&self) -> Option<Bytes> {
+		self.flat.prepare_trace_output()
+	}
+
+	fn trace_call(&mut self, call: Option<Call>, gas_used: U256, output: Option<Bytes>, subs: Vec<Self::Output>) {
+		let (flat_subs, call_subs) = Self::split(subs);
+		self.flat.trace_call(call.clone(), gas_used, output.clone(), flat_subs);
+		self.calls.trace_call(call, gas_used, output, call_subs);
+	}
+
+	fn trace_create(&mut self, create: Option<Create>, gas_used: U256, code: Option<Bytes>, address: Address, subs: Vec<Self::Output>) {
+		let (flat_subs, call_subs) = Self::split(subs);
+		self.flat.trace_create(create.clone(), gas_used, code.clone(), address, flat_subs);
+		self.calls.trace_create(create, gas_used, code, address, call_subs);
+	}
+
+	fn trace_failed_call(&mut self, call: Option<Call>, subs: Vec<Self::Output>, error: TraceError) {
+		let (flat_subs, call_subs) = Self::split(subs);
+		self.flat.trace_failed_call(call.clone(), flat_subs, error.clone());
+		self.calls.trace_failed_call(call, call_subs, error);
+	}
+
+	fn trace_failed_create(&mut self, create: Option<Create>, subs: Vec<Self::Output>, error: TraceError) {
+		let (flat_subs, call_subs) = Self::split(subs);
+		self.flat.trace_failed_create(create.clone(), flat_subs, error.clone());
+		self.calls.trace_failed_create(create, call_subs, error);
+	}
+
+	fn trace_reverted_call(&mut self, call: Option<Call>, gas_used: U256, output: &[u8], subs: Vec<Self::Output>) {
+		let (flat_subs, call_subs) = Self::split(subs);
+		self.flat.trace_reverted_call(call.clone(), gas_used, output, flat_subs);
+		self.calls.trace_reverted_call(call, gas_used, output, call_subs);
+	}
+
+	fn trace_reverted_create(&mut self, create: Option<Create>, gas_used: U256, output: &[u8], subs: Vec<Self::Output>) {
+		let (flat_subs, call_subs) = Self::split(subs);
+		self.flat.trace_reverted_create(create.clone(), gas_used, output, flat_subs);
+		self.calls.trace_reverted_create(create, gas_used, output, call_subs);
+	}
+
+	fn trace_suicide(&mut self, address: Address, balance: U256, refund_address: Address) {
+		self.flat.trace_suicide(address, balance, refund_address);
+		self.calls.trace_suicide(address, balance, refund_address);
+	}
+
+	// rewards have no place in a call tree.
+	fn trace_reward(&mut self, author: Address, value: U256, reward_type: RewardType) {
+		self.flat.trace_reward(author, value, reward_type);
+	}
+
+	fn subtracer(&self) -> Self {
+		CombinedTracer::default()
+	}
+
+	fn traces(self) -> Vec<Self::Output> {
+		vec![(self.flat.traces(), self.calls.traces())]
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use util::{U256, Address};
+	use action_params::ActionParams;
+	use types::executed::CallType;
+	use trace::{Tracer, ExecutiveTracer, CallTracer, TraceError};
+	use super::CombinedTracer;
+
+	fn params(from: u64, to: u64) -> ActionParams {
+		let mut params = ActionParams::default();
+		params.sender = Address::from(from);
+		params.address = Address::from(to);
+		params.gas = U256::from(1000);
+		params.data = Some(vec![0x12]);
+		params.call_type = CallType::Call;
+		params
+	}
+
+	#[test]
+	fn matches_separate_tracers() {
+		let outer = params(1, 2);
+		let inner = params(2, 3);
+
+		let mut combined = CombinedTracer::default();
+		let mut subtracer = combined.subtracer();
+		let info = subtracer.prepare_trace_call(&inner);
+		subtracer.trace_reverted_call(info, U256::from(10), &[0xff], vec![]);
+		let info = combined.prepare_trace_call(&outer);
+		combined.trace_call(info, U256::from(100), Some(vec![0x34]), subtracer.traces());
+		let (flat, calls) = CombinedTracer::split(combined.traces());
+
+		let mut flat_tracer = ExecutiveTracer::default();
+		let mut subtracer = flat_tracer.subtracer();
+		let info = subtracer.prepare_trace_call(&inner);
+		subtracer.trace_reverted_call(info, U256::from(10), &[0xff], vec![]);
+		let info = flat_tracer.prepare_trace_call(&outer);
+		flat_tracer.trace_call(info, U256::from(100), Some(vec![0x34]), subtracer.traces());
+
+		let mut call_tracer = CallTracer::default();
+		let mut subtracer = call_tracer.subtracer();
+		let info = subtracer.prepare_trace_call(&inner);
+		subtracer.trace_reverted_call(info, U256::from(10), &[0xff], vec![]);
+		let info = call_tracer.prepare_trace_call(&outer);
+		call_tracer.trace_call(info, U256::from(100), Some(vec![0x34]), subtracer.traces());
+
+		assert_eq!(flat.len(), 2);
+		assert_eq!(flat, flat_tracer.traces());
+		assert_eq!(calls, call_tracer.traces());
+		assert_eq!(calls[0].calls[0].error, Some(TraceError::Reverted));
+	}
+}
@@ -15,12 +15,14 @@
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
 //! Trace database.
-use std::ops::Deref;
+use std::cmp;
+use std::ops::{Deref, Range};
 use std::collections::HashMap;
 use std::sync::Arc;
-use bloomchain::{Number, Config as BloomConfig};
+use bloomchain::{Number, Bloom, Config as BloomConfig, Filter as BloomFilter};
 use bloomchain::group::{BloomGroupDatabase, BloomGroupChain, GroupPosition, BloomGroup};
 use util::{H256, H264, Database, DBTransaction, RwLock, HeapSizeOf};
+use rlp::{encode, decode};
 use header::BlockNumber;
 use trace::{LocalizedTrace, Config, Filter, Database as TraceDatabase, ImportRequest, DatabaseExtras};
 use db::{self, Key, Writable, Readable, CacheUpdatePolicy};
@@ -29,6 +31,7 @@ use super::flat::{FlatTrace, FlatBlockTraces, FlatTransactionTraces};
 use cache_manager::CacheManager;
 
 const TRACE_DB_VER: &'static [u8] = b"1.0";
+const REBUILD_PROGRESS_KEY: &'static [u8] = b"rebuild_progress";
 
 #[derive(Debug, Copy, Clone)]
 #[cfg_attr(feature="dev", allow(enum_variant_names))]
@@ -145,6 +148,23 @@ impl<T> TraceDB<T> where T: DatabaseExtras {
 		}
 	}
 
+	/// Returns the number of the last block whose traces were rebuilt,
+	/// if a rebuild has been started but not finished.
+	pub fn rebuild_progress(&self) -> Option<BlockNumber> {
+		self.tracesdb.get(db::COL_TRACE, REBUILD_PROGRESS_KEY)
+			.expect("Low level database error. Some issue with disk?")
+			.map(|progress| decode(&progress))
+	}
+
+	/// Records the number of the last block whose traces were rebuilt.
+	/// `None` marks the rebuild as finished.
+	pub fn set_rebuild_progress(&self, batch: &mut DBTransaction, progress: Option<BlockNumber>) {
+		match progress {
+			Some(number) => batch.put(db::COL_TRACE, REBUILD_PROGRESS_KEY, &encode(&number)),
+			None => batch.delete(db::COL_TRACE, REBUILD_PROGRESS_KEY),
+		}
+	}
+
 	fn cache_size(&self) -> usize {
 		let traces = self.traces.read().heap_size_of_children();
 		let blooms = self.blooms.read().heap_size_of_children();
@@ -191,6 +211,21 @@ impl<T> TraceDB<T> where T: DatabaseExtras {
 		self.traces(block_hash).map(Into::into)
 	}
 
+	/// Returns number and hash of the transaction which produced traces at given position.
+	/// Block and uncle rewards are traced as an extra entry after the block's transactions,
+	/// so they have neither.
+	fn transaction_position(&self, block_number: BlockNumber, tx_position: usize) -> (Option<usize>, Option<H256>) {
+		let transactions_count = self.extras.transactions_count(block_number)
+			.expect("Expected to find block body. Database is probably corrupted");
+		if tx_position == transactions_count {
+			return (None, None);
+		}
+
+		let tx_hash = self.extras.transaction_hash(block_number, tx_position)
+			.expect("Expected to find transaction hash. Database is probably corrupted");
+		(Some(tx_position), Some(tx_hash))
+	}
+
 	fn matching_block_traces(
 		&self,
 		filter: &Filter,
@@ -215,8 +250,7 @@ impl<T> TraceDB<T> where T: DatabaseExtras {
 		block_number: BlockNumber,
 		tx_number: usize
 	) -> Vec<LocalizedTrace> {
-		let tx_hash = self.extras.transaction_hash(block_number, tx_number)
-			.expect("Expected to find transaction hash. Database is probably corrupted");
+		let (tx_number, tx_hash) = self.transaction_position(block_number, tx_number);
 
 		let flat_traces: Vec<FlatTrace> = traces.into();
 		flat_traces.into_iter()
@@ -313,15 +347,14 @@ impl<T> TraceDatabase for TraceDB<T> where T: DatabaseExtras {
 				// this may and should be optimized
 				.and_then(|traces| traces.into_iter().find(|trace| trace.trace_address == trace_position_deq))
 				.map(|trace| {
-					let tx_hash = self.extras.transaction_hash(block_number, tx_position)
-						.expect("Expected to find transaction hash. Database is probably corrupted");
+					let (tx_number, tx_hash) = self.transaction_position(block_number, tx_position);
 
 					LocalizedTrace {
 						action: trace.action,
 						result: trace.result,
 						subtraces: trace.subtraces,
 						trace_address: trace.trace_address.into_iter().collect(),
-						transaction_number: tx_position,
+						transaction_number: tx_number,
 						transaction_hash: tx_hash,
 						block_number: block_number,
 						block_hash: block_hash,
@@ -336,8 +369,7 @@ impl<T> TraceDatabase for TraceDB<T> where T: DatabaseExtras {
 				.and_then(|traces| traces.into_iter().nth(tx_position))
 				.map(Into::<Vec<FlatTrace>>::into)
 				.map(|traces| {
-					let tx_hash = self.extras.transaction_hash(block_number, tx_position)
-						.expect("Expected to find transaction hash. Database is probably corrupted");
+					let (tx_number, tx_hash) = self.transaction_position(block_number, tx_position);
 
 					traces.into_iter()
 					.map(|trace| LocalizedTrace {
@@ -345,7 +377,7 @@ impl<T> TraceDatabase for TraceDB<T> where T: DatabaseExtras {
 						result: trace.result,
 						subtraces: trace.subtraces,
 						trace_address: trace.trace_address.into_iter().collect(),
-						transaction_number: tx_position,
+						transaction_number: tx_number,
 						transaction_hash: tx_hash.clone(),
 						block_number: block_number,
 						block_hash: block_hash
@@ -363,8 +395,7 @@ impl<T> TraceDatabase for TraceDB<T> where T: DatabaseExtras {
 						.map(Into::<Vec<FlatTrace>>::into)
 						.enumerate()
 						.flat_map(|(tx_position, traces)| {
-							let tx_hash = self.extras.transaction_hash(block_number, tx_position)
-								.expect("Expected to find transaction hash. Database is probably corrupted");
+							let (tx_number, tx_hash) = self.transaction_position(block_number, tx_position);
 
 							traces.into_iter()
 								.map(|trace| LocalizedTrace {
@@ -372,7 +403,7 @@ impl<T> TraceDatabase for TraceDB<T> where T: DatabaseExtras {
 									result: trace.result,
 									subtraces: trace.subtraces,
 									trace_address: trace.trace_address.into_iter().collect(),
-									transaction_number: tx_position,
+									transaction_number: tx_number,
 									transaction_hash: tx_hash.clone(),
 									block_number: block_number,
 									block_hash: block_hash,
@@ -386,20 +417,60 @@ impl<T> TraceDatabase for TraceDB<T> where T: DatabaseExtras {
 
 	fn filter(&self, filter: &Filter) -> Vec<LocalizedTrace> {
 		let chain = BloomGroupChain::new(self.bloom_config, self);
-		let numbers = chain.filter(filter);
-		numbers.into_iter()
-			.flat_map(|n| {
+		let after = filter.after.unwrap_or(0);
+		let count = filter.count.unwrap_or(usize::max_value());
+		let wanted = after.saturating_add(count);
+
+		// blooms are looked up one top level bloom at a time, so that later blocks
+		// are not searched once enough traces are found.
+		let window = self.bloom_config.elements_per_index.pow(self.bloom_config.levels as u32 - 1);
+		let mut traces = Vec::new();
+		let mut start = filter.range.start;
+		while start <= filter.range.end && traces.len() < wanted {
+			// filter ranges are inclusive
+			let end = cmp::min(start - start % window + window - 1, filter.range.end);
+			let numbers = chain.filter(&FilterWindow { filter: filter, range: start..end });
+			for n in numbers {
 				let number = n as BlockNumber;
 				let hash = self.extras.block_hash(number)
 					.expect("Expected to find block hash. Extras db is probably corrupted");
-				let traces = self.traces(&hash)
+				let block_traces = self.traces(&hash)
 					.expect("Expected to find a trace. Db is probably corrupted.");
-				self.matching_block_traces(filter, traces, hash, number)
-			})
+				traces.extend(self.matching_block_traces(filter, block_traces, hash, number));
+				if traces.len() >= wanted {
+					break;
+				}
+			}
+
+			if end == filter.range.end {
+				break;
+			}
+			start = end + 1;
+		}
+
+		traces.into_iter()
+			.skip(after)
+			.take(count)
 			.collect()
 	}
 }
 
+/// Part of the trace filter's range.
+struct FilterWindow<'a> {
+	filter: &'a Filter,
+	range: Range<Number>,
+}
+
+impl<'a> BloomFilter for FilterWindow<'a> {
+	fn bloom_possibilities(&self) -> Vec<Bloom> {
+		BloomFilter::bloom_possibilities(self.filter)
+	}
+
+	fn range(&self) -> Range<Number> {
+		self.range.clone()
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use std::collections::HashMap;
@@ -409,7 +480,7 @@ mod tests {
 	use header::BlockNumber;
 	use trace::{Config, TraceDB, Database as TraceDatabase, DatabaseExtras, ImportRequest};
 	use trace::{Filter, LocalizedTrace, AddressesFilter, TraceError};
-	use trace::trace::{Call, Action, Res};
+	use trace::trace::{Call, Action, Res, Reward, RewardType};
 	use trace::flat::{FlatTrace, FlatBlockTraces, FlatTransactionTraces};
 	use types::executed::CallType;
 
@@ -427,6 +498,10 @@ mod tests {
 		fn transaction_hash(&self, _block_number: BlockNumber, _tx_position: usize) -> Option<H256> {
 			unimplemented!();
 		}
+
+		fn transactions_count(&self, _block_number: BlockNumber) -> Option<usize> {
+			unimplemented!();
+		}
 	}
 
 	#[derive(Clone)]
@@ -453,6 +528,10 @@ mod tests {
 			self.transaction_hashes.get(&block_number)
 				.and_then(|hashes| hashes.iter().cloned().nth(tx_position))
 		}
+
+		fn transactions_count(&self, block_number: BlockNumber) -> Option<usize> {
+			self.transaction_hashes.get(&block_number).map(Vec::len)
+		}
 	}
 
 	fn new_db(path: &str) -> Arc<Database> {
@@ -546,13 +625,33 @@ mod tests {
 			result: Res::FailedCall(TraceError::OutOfGas),
 			trace_address: vec![],
 			subtraces: 0,
-			transaction_number: 0,
-			transaction_hash: tx_hash,
+			transaction_number: Some(0),
+			transaction_hash: Some(tx_hash),
 			block_number: block_number,
 			block_hash: block_hash,
 		}
 	}
 
+	#[test]
+	fn test_rebuild_progress() {
+		let temp = RandomTempPath::new();
+		let db = new_db(temp.as_str());
+		let mut config = Config::default();
+		config.enabled = true;
+		let tracedb = TraceDB::new(config, db.clone(), Arc::new(NoopExtras));
+		assert_eq!(tracedb.rebuild_progress(), None);
+
+		let mut batch = DBTransaction::new(&db);
+		tracedb.set_rebuild_progress(&mut batch, Some(42));
+		db.write(batch).unwrap();
+		assert_eq!(tracedb.rebuild_progress(), Some(42));
+
+		let mut batch = DBTransaction::new(&db);
+		tracedb.set_rebuild_progress(&mut batch, None);
+		db.write(batch).unwrap();
+		assert_eq!(tracedb.rebuild_progress(), None);
+	}
+
 	#[test]
 	fn test_import_non_canon_traces() {
 		let temp = RandomTempPath::new();
@@ -613,6 +712,8 @@ mod tests {
 			range: (1..1),
 			from_address: AddressesFilter::from(vec![Address::from(1)]),
 			to_address: AddressesFilter::from(vec![]),
+			after: None,
+			count: None,
 		};
 
 		let traces = tracedb.filter(&filter);
@@ -629,6 +730,8 @@ mod tests {
 			range: (1..2),
 			from_address: AddressesFilter::from(vec![Address::from(1)]),
 			to_address: AddressesFilter::from(vec![]),
+			after: None,
+			count: None,
 		};
 
 		let traces = tracedb.filter(&filter);
@@ -636,6 +739,18 @@ mod tests {
 		assert_eq!(traces[0], create_simple_localized_trace(1, block_1.clone(), tx_1.clone()));
 		assert_eq!(traces[1], create_simple_localized_trace(2, block_2.clone(), tx_2.clone()));
 
+		let filter = Filter {
+			range: (1..2),
+			from_address: AddressesFilter::from(vec![Address::from(1)]),
+			to_address: AddressesFilter::from(vec![]),
+			after: Some(1),
+			count: Some(1),
+		};
+
+		let traces = tracedb.filter(&filter);
+		assert_eq!(traces.len(), 1);
+		assert_eq!(traces[0], create_simple_localized_trace(2, block_2.clone(), tx_2.clone()));
+
 		assert!(tracedb.block_traces(0).is_some(), "Genesis trace should be always present.");
 
 		let traces = tracedb.block_traces(1).unwrap();
@@ -715,4 +830,104 @@ mod tests {
 
 		assert_eq!(traces.len(), 0);
 	}
+
+	#[test]
+	fn query_reward_traces() {
+		let temp = RandomTempPath::new();
+		let db = new_db(temp.as_str());
+		let mut config = Config::default();
+		let mut extras = Extras::default();
+		let block_1 = H256::from(0xa1);
+		let tx_0 = H256::from(0xff);
+
+		extras.block_hashes.insert(0, H256::default());
+		extras.transaction_hashes.insert(0, vec![]);
+		extras.block_hashes.insert(1, block_1.clone());
+		extras.transaction_hashes.insert(1, vec![tx_0.clone()]);
+
+		// set tracing on
+		config.enabled = true;
+
+		let tracedb = TraceDB::new(config, db.clone(), Arc::new(extras));
+
+		// import block 1 with a transaction followed by the reward entry
+		let mut request = create_simple_import_request(1, block_1.clone());
+		let mut traces: Vec<FlatTransactionTraces> = request.traces.into();
+		traces.push(FlatTransactionTraces::from(vec![FlatTrace {
+			trace_address: Default::default(),
+			subtraces: 0,
+			action: Action::Reward(Reward {
+				author: 5.into(),
+				value: 6.into(),
+				reward_type: RewardType::Block,
+			}),
+			result: Res::None,
+		}]));
+		request.traces = traces.into();
+		let mut batch = DBTransaction::new(&db);
+		tracedb.import(&mut batch, request);
+		db.write(batch).unwrap();
+
+		let traces = tracedb.block_traces(1).unwrap();
+		assert_eq!(traces.len(), 2);
+		assert_eq!(traces[0], create_simple_localized_trace(1, block_1.clone(), tx_0));
+		assert_eq!(traces[1].transaction_number, None);
+		assert_eq!(traces[1].transaction_hash, None);
+
+		let reward = tracedb.trace(1, 1, vec![]).unwrap();
+		assert_eq!(reward.transaction_hash, None);
+		assert_eq!(reward.action, traces[1].action);
+	}
+
+	#[test]
+	fn filter_stops_after_requested_page() {
+		let temp = RandomTempPath::new();
+		let db = new_db(temp.as_str());
+		let mut config = Config::default();
+		let mut extras = Extras::default();
+		let block_1 = H256::from(0xa1);
+		let block_257 = H256::from(0xa2);
+		let tx_1 = H256::from(0xff);
+		let tx_257 = H256::from(0xfe);
+
+		extras.block_hashes.insert(0, H256::default());
+		extras.block_hashes.insert(1, block_1.clone());
+		extras.block_hashes.insert(257, block_257.clone());
+		extras.transaction_hashes.insert(1, vec![tx_1.clone()]);
+		extras.transaction_hashes.insert(257, vec![tx_257.clone()]);
+
+		// set tracing on
+		config.enabled = true;
+
+		{
+			let tracedb = TraceDB::new(config.clone(), db.clone(), Arc::new(extras.clone()));
+			for (number, hash) in vec![(1, block_1.clone()), (257, block_257.clone())] {
+				let request = create_simple_import_request(number, hash);
+				let mut batch = DBTransaction::new(&db);
+				tracedb.import(&mut batch, request);
+				db.write(batch).unwrap();
+			}
+		}
+
+		let filter = |count| Filter {
+			range: (1..300),
+			from_address: AddressesFilter::from(vec![Address::from(1)]),
+			to_address: AddressesFilter::from(vec![]),
+			after: None,
+			count: count,
+		};
+
+		let paged = TraceDB::new(config.clone(), db.clone(), Arc::new(extras.clone()));
+		assert_eq!(paged.filter(&filter(Some(1))), vec![create_simple_localized_trace(1, block_1.clone(), tx_1.clone())]);
+
+		let full = TraceDB::new(config, db.clone(), Arc::new(extras));
+		assert_eq!(full.filter(&filter(None)), vec![
+			create_simple_localized_trace(1, block_1.clone(), tx_1.clone()),
+			create_simple_localized_trace(257, block_257.clone(), tx_257.clone()),
+		]);
+
+		// block 257 is covered by the second top level bloom, which is not searched once the page is full.
+		assert!(!paged.traces.read().contains_key(&block_257));
+		assert!(paged.blooms.read().len() < full.blooms.read().len());
+	}
 }
@@ -32,10 +32,20 @@ pub enum Error {
 	/// Returned when tracing is enabled,
 	/// but database does not contain traces of old transactions.
 	ResyncRequired,
+	/// Returned when traces are rebuilt, but tracing is disabled.
+	TracingDisabled,
+	/// Returned when traces of a block cannot be rebuilt,
+	/// because the block or the state of its parent is not available.
+	BlockUnavailable(u64),
 }
 
 impl Display for Error {
 	fn fmt(&self, f: &mut Formatter) -> Result<(), FmtError> {
-		write!(f, "{}", RESYNC_ERR)
+		match *self {
+			Error::ResyncRequired => write!(f, "{}", RESYNC_ERR),
+			Error::TracingDisabled => write!(f, "Tracing is disabled."),
+			Error::BlockUnavailable(number) => write!(f, "Block #{} or the state of its parent is not available. \
+				Traces can only be rebuilt from blocks whose state has not been pruned.", number),
+		}
 	}
 }
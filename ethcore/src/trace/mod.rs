@@ -23,6 +23,9 @@ mod error;
 mod executive_tracer;
 mod import;
 mod noop_tracer;
+mod struct_log_tracer;
+mod call_tracer;
+mod combined_tracer;
 
 pub use types::trace_types::{filter, flat, localized, trace};
 pub use types::trace_types::error::Error as TraceError;
@@ -33,16 +36,24 @@ pub use types::trace_types::trace::{VMTrace, VMOperation, VMExecutedOperation, M
 pub use types::trace_types::flat::{FlatTrace, FlatTransactionTraces, FlatBlockTraces};
 pub use self::noop_tracer::{NoopTracer, NoopVMTracer};
 pub use self::executive_tracer::{ExecutiveTracer, ExecutiveVMTracer};
+pub use self::struct_log_tracer::StructLogTracer;
+pub use self::call_tracer::CallTracer;
+pub use self::combined_tracer::CombinedTracer;
+pub use types::trace_types::struct_log::{StructLog, StructLogOptions, StructLogTrace};
+pub use types::trace_types::call_tree::{CallFrame, FrameKind};
 pub use types::trace_types::filter::{Filter, AddressesFilter};
 pub use self::import::ImportRequest;
 pub use self::localized::LocalizedTrace;
 use util::{Bytes, Address, U256, H256, DBTransaction};
-use self::trace::{Call, Create};
+use self::trace::{Call, Create, RewardType};
 use action_params::ActionParams;
 use header::BlockNumber;
 
 /// This trait is used by executive to build traces.
 pub trait Tracer: Send {
+	/// Data produced by the tracer.
+	type Output;
+
 	/// Prepares call trace for given params. Noop tracer should return None.
 	fn prepare_trace_call(&self, params: &ActionParams) -> Option<Call>;
 
@@ -58,7 +69,7 @@ pub trait Tracer: Send {
 		call: Option<Call>,
 		gas_used: U256,
 		output: Option<Bytes>,
-		subs: Vec<FlatTrace>,
+		subs: Vec<Self::Output>,
 	);
 
 	/// Stores trace create info.
@@ -68,31 +79,50 @@ pub trait Tracer: Send {
 		gas_used: U256,
 		code: Option<Bytes>,
 		address: Address,
-		subs: Vec<FlatTrace>
+		subs: Vec<Self::Output>
 	);
 
 	/// Stores failed call trace.
-	fn trace_failed_call(&mut self, call: Option<Call>, subs: Vec<FlatTrace>, error: TraceError);
+	fn trace_failed_call(&mut self, call: Option<Call>, subs: Vec<Self::Output>, error: TraceError);
 
 	/// Stores failed create trace.
-	fn trace_failed_create(&mut self, create: Option<Create>, subs: Vec<FlatTrace>, error: TraceError);
+	fn trace_failed_create(&mut self, create: Option<Create>, subs: Vec<Self::Output>, error: TraceError);
+
+	/// Stores trace of a call reverted with given output. Traced as a failed call by default.
+	fn trace_reverted_call(&mut self, call: Option<Call>, _gas_used: U256, _output: &[u8], subs: Vec<Self::Output>) {
+		self.trace_failed_call(call, subs, TraceError::Reverted);
+	}
+
+	/// Stores trace of a create reverted with given output. Traced as a failed create by default.
+	fn trace_reverted_create(&mut self, create: Option<Create>, _gas_used: U256, _output: &[u8], subs: Vec<Self::Output>) {
+		self.trace_failed_create(create, subs, TraceError::Reverted);
+	}
 
 	/// Stores suicide info.
 	fn trace_suicide(&mut self, address: Address, balance: U256, refund_address: Address);
 
+	/// Stores reward info.
+	fn trace_reward(&mut self, author: Address, value: U256, reward_type: RewardType);
+
 	/// Spawn subtracer which will be used to trace deeper levels of execution.
 	fn subtracer(&self) -> Self where Self: Sized;
 
 	/// Consumes self and returns all traces.
-	fn traces(self) -> Vec<FlatTrace>;
+	fn traces(self) -> Vec<Self::Output>;
 }
 
 /// Used by executive to build VM traces.
 pub trait VMTracer: Send {
+	/// Data produced by the tracer.
+	type Output;
+
 	/// Trace the preparation to execute a single instruction.
-	/// @returns true if `trace_executed` should be called.
+	/// @returns true if `trace_state` and `trace_executed` should be called.
 	fn trace_prepare_execute(&mut self, _pc: usize, _instruction: u8, _gas_cost: &U256) -> bool { false }
 
+	/// Trace the state of the VM right before execution of the prepared instruction.
+	fn trace_state(&mut self, _gas_left: &U256, _stack: &[U256], _memory: &[u8]) {}
+
 	/// Trace the finalised execution of a single instruction.
 	fn trace_executed(&mut self, _gas_used: U256, _stack_push: &[U256], _mem_diff: Option<(usize, &[u8])>, _store_diff: Option<(U256, U256)>) {}
 
@@ -103,7 +133,7 @@ pub trait VMTracer: Send {
 	fn done_subtrace(&mut self, sub: Self) where Self: Sized;
 
 	/// Consumes self and returns the VM trace.
-	fn drain(self) -> Option<VMTrace>;
+	fn drain(self) -> Option<Self::Output>;
 }
 
 /// `DbExtras` provides an interface to query extra data which is not stored in tracesdb,
@@ -114,6 +144,9 @@ pub trait DatabaseExtras {
 
 	/// Returns hash of transaction at given position.
 	fn transaction_hash(&self, block_number: BlockNumber, tx_position: usize) -> Option<H256>;
+
+	/// Returns number of transactions in given block.
+	fn transactions_count(&self, block_number: BlockNumber) -> Option<usize>;
 }
 
 /// Db provides an interface to query tracesdb.
@@ -134,5 +167,7 @@ pub trait Database {
 	fn block_traces(&self, block_number: BlockNumber) -> Option<Vec<LocalizedTrace>>;
 
 	/// Filter traces matching given filter.
+	///
+	/// The search stops once `after` + `count` matching traces are found.
 	fn filter(&self, filter: &Filter) -> Vec<LocalizedTrace>;
 }
@@ -0,0 +1,181 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tracer recording the state of the VM before each executed instruction.
+
+use std::collections::BTreeMap;
+use util::{U256, H256};
+use trace::{VMTracer, StructLog, StructLogOptions};
+
+/// Records the state of the VM before each executed instruction, like geth's struct logger.
+pub struct StructLogTracer {
+	options: StructLogOptions,
+	depth: usize,
+	storage: BTreeMap<H256, H256>,
+	logs: Vec<StructLog>,
+}
+
+impl StructLogTracer {
+	/// Create a new top-level instance.
+	pub fn toplevel(options: StructLogOptions) -> Self {
+		StructLogTracer {
+			options: options,
+			depth: 0,
+			storage: BTreeMap::new(),
+			logs: vec![],
+		}
+	}
+}
+
+impl VMTracer for StructLogTracer {
+	type Output = Vec<StructLog>;
+
+	fn trace_prepare_execute(&mut self, pc: usize, instruction: u8, gas_cost: &U256) -> bool {
+		self.logs.push(StructLog {
+			pc: pc,
+			instruction: instruction,
+			gas: U256::zero(),
+			gas_cost: gas_cost.clone(),
+			depth: self.depth,
+			stack: None,
+			memory: None,
+			storage: None,
+		});
+		true
+	}
+
+	fn trace_state(&mut self, gas_left: &U256, stack: &[U256], memory: &[u8]) {
+		let log = self.logs.last_mut().expect("trace_state is always called after a trace_prepare_execute; qed");
+		log.gas = gas_left.clone();
+		if !self.options.disable_stack {
+			log.stack = Some(stack.to_vec());
+		}
+		if !self.options.disable_memory {
+			log.memory = Some(memory.to_vec());
+		}
+		if !self.options.disable_storage {
+			log.storage = Some(self.storage.clone());
+		}
+	}
+
+	fn trace_executed(&mut self, _gas_used: U256, _stack_push: &[U256], _mem_diff: Option<(usize, &[u8])>, store_diff: Option<(U256, U256)>) {
+		if self.options.disable_storage {
+			return;
+		}
+
+		// like geth, the log of `SSTORE` already contains the written value.
+		if let Some((location, value)) = store_diff {
+			self.storage.insert(location.into(), value.into());
+			let log = self.logs.last_mut().expect("trace_executed is always called after a trace_prepare_execute; qed");
+			log.storage = Some(self.storage.clone());
+		}
+	}
+
+	fn prepare_subtrace(&self, _code: &[u8]) -> Self {
+		StructLogTracer {
+			options: self.options,
+			depth: self.depth + 1,
+			storage: BTreeMap::new(),
+			logs: vec![],
+		}
+	}
+
+	fn done_subtrace(&mut self, sub: Self) {
+		self.logs.extend(sub.logs);
+	}
+
+	fn drain(self) -> Option<Vec<StructLog>> { Some(self.logs) }
+}
+
+#[cfg(test)]
+mod tests {
+	use util::{U256, H256};
+	use trace::{VMTracer, StructLogOptions};
+	use super::StructLogTracer;
+
+	#[test]
+	fn should_record_state_before_instruction() {
+		let mut tracer = StructLogTracer::toplevel(Default::default());
+		let mut sub = tracer.prepare_subtrace(&[]);
+
+		// PUSH1 0x2a PUSH1 0x01 SSTORE
+		assert!(sub.trace_prepare_execute(0, 0x60, &3.into()));
+		sub.trace_state(&100.into(), &[], &[]);
+		sub.trace_executed(97.into(), &[0x2a.into()], None, None);
+		assert!(sub.trace_prepare_execute(2, 0x60, &3.into()));
+		sub.trace_state(&97.into(), &[0x2a.into()], &[]);
+		sub.trace_executed(94.into(), &[1.into()], None, None);
+		assert!(sub.trace_prepare_execute(4, 0x55, &20000.into()));
+		sub.trace_state(&94.into(), &[0x2a.into(), 1.into()], &[]);
+		tracer.done_subtrace(sub);
+
+		let logs = tracer.drain().unwrap();
+		assert_eq!(logs.len(), 3);
+		assert_eq!(logs[1].pc, 2);
+		assert_eq!(logs[1].gas, U256::from(97));
+		assert_eq!(logs[1].gas_cost, U256::from(3));
+		assert_eq!(logs[1].depth, 1);
+		assert_eq!(logs[1].stack, Some(vec![0x2a.into()]));
+		assert_eq!(logs[2].storage, Some(Default::default()));
+	}
+
+	#[test]
+	fn should_track_storage_of_current_call() {
+		let mut tracer = StructLogTracer::toplevel(Default::default());
+		let mut sub = tracer.prepare_subtrace(&[]);
+
+		sub.trace_prepare_execute(4, 0x55, &20000.into());
+		sub.trace_state(&94.into(), &[0x2a.into(), 1.into()], &[]);
+		sub.trace_executed(74.into(), &[], None, Some((1.into(), 0x2a.into())));
+
+		let mut nested = sub.prepare_subtrace(&[]);
+		nested.trace_prepare_execute(0, 0x00, &0.into());
+		nested.trace_state(&50.into(), &[], &[]);
+		sub.done_subtrace(nested);
+
+		sub.trace_prepare_execute(5, 0x00, &0.into());
+		sub.trace_state(&20.into(), &[], &[]);
+		tracer.done_subtrace(sub);
+
+		let logs = tracer.drain().unwrap();
+		let expected: ::std::collections::BTreeMap<_, _> = vec![(H256::from(1), H256::from(0x2a))].into_iter().collect();
+		assert_eq!(logs.len(), 3);
+		assert_eq!(logs[0].storage, Some(expected.clone()));
+		assert_eq!(logs[1].depth, 2);
+		assert_eq!(logs[1].storage, Some(Default::default()));
+		assert_eq!(logs[2].depth, 1);
+		assert_eq!(logs[2].storage, Some(expected));
+	}
+
+	#[test]
+	fn should_respect_disabled_options() {
+		let options = StructLogOptions {
+			disable_stack: true,
+			disable_memory: true,
+			disable_storage: true,
+		};
+		let mut tracer = StructLogTracer::toplevel(options);
+
+		tracer.trace_prepare_execute(0, 0x55, &20000.into());
+		tracer.trace_state(&94.into(), &[0x2a.into(), 1.into()], &[0; 32]);
+		tracer.trace_executed(74.into(), &[], None, Some((1.into(), 0x2a.into())));
+
+		let logs = tracer.drain().unwrap();
+		assert_eq!(logs[0].stack, None);
+		assert_eq!(logs[0].memory, None);
+		assert_eq!(logs[0].storage, None);
+	}
+}
@@ -0,0 +1,47 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Merkle proofs of an account and its storage.
+
+use util::{U256, H256, Bytes};
+
+/// Value of a single storage key with its proof.
+#[derive(Debug, PartialEq, Clone, Binary)]
+pub struct StorageProof {
+	/// The storage key.
+	pub key: H256,
+	/// The value stored under the key.
+	pub value: H256,
+	/// Raw trie nodes from the account's storage root to the value.
+	pub proof: Vec<Bytes>,
+}
+
+/// Account with its proof, all taken from the same state.
+#[derive(Debug, PartialEq, Clone, Binary)]
+pub struct AccountProof {
+	/// Balance of the account.
+	pub balance: U256,
+	/// Nonce of the account.
+	pub nonce: U256,
+	/// Hash of the account's code.
+	pub code_hash: H256,
+	/// Root of the account's storage trie.
+	pub storage_root: H256,
+	/// Raw trie nodes from the state root to the account.
+	pub proof: Vec<Bytes>,
+	/// Proofs of the requested storage keys, in the order they were requested.
+	pub storage: Vec<StorageProof>,
+}
@@ -25,4 +25,6 @@ pub struct CallAnalytics {
 	pub vm_tracing: bool,
 	/// Make a diff.
 	pub state_diffing: bool,
+	/// Make a nested call tree instead of a flat transaction trace.
+	pub call_tracing: bool,
 }
@@ -18,7 +18,7 @@
 
 use util::{Bytes, U256, Address, U512};
 use rlp::*;
-use trace::{VMTrace, FlatTrace};
+use trace::{VMTrace, FlatTrace, TraceError, CallFrame};
 use types::log_entry::LogEntry;
 use types::state_diff::StateDiff;
 use std::fmt;
@@ -34,6 +34,8 @@ pub enum CallType {
 	CallCode,
 	/// DELEGATECALL.
 	DelegateCall,
+	/// STATICCALL.
+	StaticCall,
 }
 
 impl Encodable for CallType {
@@ -43,6 +45,7 @@ impl Encodable for CallType {
 			CallType::Call => 1,
 			CallType::CallCode => 2,
 			CallType::DelegateCall => 3,
+			CallType::StaticCall => 4,
 		};
 		s.append(&v);
 	}
@@ -55,6 +58,7 @@ impl Decodable for CallType {
 			1 => CallType::Call,
 			2 => CallType::CallCode,
 			3 => CallType::DelegateCall,
+			4 => CallType::StaticCall,
 			_ => return Err(DecoderError::Custom("Invalid value of CallType item")),
 		}))
 	}
@@ -96,8 +100,15 @@ pub struct Executed {
 	pub trace: Vec<FlatTrace>,
 	/// The VM trace of this transaction.
 	pub vm_trace: Option<VMTrace>,
+	/// The call tree of this transaction, if it was traced with the call tracer.
+	pub call_trace: Option<CallFrame>,
 	/// The state diff, if we traced it.
 	pub state_diff: Option<StateDiff>,
+	/// True if the execution was reverted by `REVERT` instruction.
+	/// In that case `output` holds the data passed to `REVERT`.
+	pub reverted: bool,
+	/// The error which halted the execution, if any.
+	pub exception: Option<TraceError>,
 }
 
 /// Result of executing the transaction.
@@ -176,6 +187,8 @@ pub enum CallError {
 	StatePruned,
 	/// Error executing.
 	Execution(ExecutionError),
+	/// The transaction fails even with the given maximal gas limit.
+	GasEstimationFailed(U256),
 }
 
 impl From<ExecutionError> for CallError {
@@ -192,6 +205,7 @@ impl fmt::Display for CallError {
 			TransactionNotFound => "Transaction couldn't be found in the chain".into(),
 			StatePruned => "Couldn't find the transaction block's state in the chain".into(),
 			Execution(ref e) => format!("{}", e),
+			GasEstimationFailed(ref cap) => format!("Transaction fails even with the maximal gas limit of {}", cap),
 		};
 
 		f.write_fmt(format_args!("Transaction execution error ({}).", msg))
@@ -0,0 +1,50 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+
+//! State overrides for non-persistent calls.
+
+use std::collections::BTreeMap;
+use util::{U256, H256, Address, Bytes};
+
+/// Overridden state of a single account. Fields left as `None` keep their actual value.
+#[derive(Debug, PartialEq, Clone, Default, Binary)]
+pub struct AccountOverride {
+	/// Balance of the account.
+	pub balance: Option<U256>,
+	/// Nonce of the account.
+	pub nonce: Option<U256>,
+	/// Code of the account.
+	pub code: Option<Bytes>,
+	/// Replaces the whole storage of the account.
+	pub state: Option<BTreeMap<H256, H256>>,
+	/// Replaces only the given storage entries, applied after `state`.
+	pub state_diff: Option<BTreeMap<H256, H256>>,
+}
+
+/// Accounts to override in the state a call is executed on.
+#[derive(Debug, PartialEq, Clone, Default, Binary)]
+pub struct StateOverride {
+	/// Overrides by account address.
+	pub accounts: BTreeMap<Address, AccountOverride>,
+}
+
+impl StateOverride {
+	/// Returns true if no account is overridden.
+	pub fn is_empty(&self) -> bool {
+		self.accounts.is_empty()
+	}
+}
@@ -29,4 +29,8 @@ pub struct Filter {
 	pub from_address: Vec<Address>,
 	/// To address.
 	pub to_address: Vec<Address>,
+	/// Number of matching traces to skip.
+	pub after: Option<usize>,
+	/// Maximum number of traces to return.
+	pub count: Option<usize>,
 }
@@ -0,0 +1,59 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+
+//! Nested call trees.
+
+use util::{U256, Address, Bytes};
+use types::executed::CallType;
+use super::trace::CreateType;
+use super::error::Error as TraceError;
+
+/// Kind of a call frame.
+#[derive(Debug, PartialEq, Clone, Binary)]
+pub enum FrameKind {
+	/// Message call.
+	Call(CallType),
+	/// Contract creation.
+	Create(CreateType),
+	/// Suicide, transferring the balance of the account.
+	Suicide,
+}
+
+/// A single frame of a call tree, together with the frames it spawned.
+#[derive(Debug, PartialEq, Clone, Binary)]
+pub struct CallFrame {
+	/// Kind of the frame.
+	pub kind: FrameKind,
+	/// The sending account.
+	pub from: Address,
+	/// The destination account. `None` for creations which failed before the address was known.
+	pub to: Option<Address>,
+	/// The value transferred to the destination account.
+	pub value: U256,
+	/// The gas available for the frame.
+	pub gas: U256,
+	/// The gas used by the frame.
+	pub gas_used: U256,
+	/// The call input or the init code.
+	pub input: Bytes,
+	/// The call output or the deployed code. Holds the revert data if the frame was reverted.
+	pub output: Bytes,
+	/// The error which halted the frame, if any.
+	pub error: Option<TraceError>,
+	/// Frames spawned by this frame, in order of execution.
+	pub calls: Vec<CallFrame>,
+}
@@ -34,9 +34,21 @@ pub enum Error {
 	StackUnderflow,
 	/// When execution would exceed defined Stack Limit
 	OutOfStack,
+	/// When return data is accessed out of bounds
+	OutOfBounds,
 	/// Returned on evm internal error. Should never be ignored during development.
 	/// Likely to cause consensus issues.
 	Internal,
+	/// When execution was reverted by `REVERT` instruction
+	Reverted,
+	/// When state modification was attempted inside of a static call
+	MutableCallInStaticContext,
+	/// When a built-in contract failed on the given input
+	BuiltIn,
+	/// When a wasm contract failed to load or trapped during execution
+	Wasm,
+	/// When a contract was created at an address which is already in use
+	ContractCollision,
 }
 
 impl From<EvmError> for Error {
@@ -47,6 +59,11 @@ impl From<EvmError> for Error {
 			EvmError::BadInstruction { .. } => Error::BadInstruction,
 			EvmError::StackUnderflow { .. } => Error::StackUnderflow,
 			EvmError::OutOfStack { .. } => Error::OutOfStack,
+			EvmError::OutOfBounds => Error::OutOfBounds,
+			EvmError::MutableCallInStaticContext => Error::MutableCallInStaticContext,
+			EvmError::BuiltIn(_) => Error::BuiltIn,
+			EvmError::Wasm(_) => Error::Wasm,
+			EvmError::ContractCollision => Error::ContractCollision,
 			EvmError::Internal => Error::Internal,
 		}
 	}
@@ -61,7 +78,13 @@ impl fmt::Display for Error {
 			BadInstruction => "Bad instruction",
 			StackUnderflow => "Stack underflow",
 			OutOfStack => "Out of stack",
+			OutOfBounds => "Return data out of bounds",
 			Internal => "Internal error",
+			Reverted => "Reverted",
+			MutableCallInStaticContext => "Mutable call in static context",
+			BuiltIn => "Built-in failed",
+			Wasm => "Wasm runtime error",
+			ContractCollision => "Contract address collision",
 		};
 		message.fmt(f)
 	}
@@ -77,6 +100,12 @@ impl Encodable for Error {
 			StackUnderflow => 3,
 			OutOfStack => 4,
 			Internal => 5,
+			OutOfBounds => 6,
+			Reverted => 7,
+			MutableCallInStaticContext => 8,
+			BuiltIn => 9,
+			Wasm => 10,
+			ContractCollision => 11,
 		};
 		s.append(&value);
 	}
@@ -93,6 +122,12 @@ impl Decodable for Error {
 			3 => Ok(StackUnderflow),
 			4 => Ok(OutOfStack),
 			5 => Ok(Internal),
+			6 => Ok(OutOfBounds),
+			7 => Ok(Reverted),
+			8 => Ok(MutableCallInStaticContext),
+			9 => Ok(BuiltIn),
+			10 => Ok(Wasm),
+			11 => Ok(ContractCollision),
 			_ => Err(DecoderError::Custom("Invalid error type")),
 		}
 	}
@@ -85,6 +85,12 @@ pub struct Filter {
 
 	/// To address filter.
 	pub to_address: AddressesFilter,
+
+	/// Number of matching traces to skip.
+	pub after: Option<usize>,
+
+	/// Maximum number of traces to return.
+	pub count: Option<usize>,
 }
 
 impl BloomFilter for Filter {
@@ -128,6 +134,10 @@ impl Filter {
 				let from_matches = self.from_address.matches(&suicide.address);
 				let to_matches = self.to_address.matches(&suicide.refund_address);
 				from_matches && to_matches
+			},
+			Action::Reward(ref reward) => {
+				// rewards have no sender, so they only match filters for any sender
+				self.from_address.matches_all() && self.to_address.matches(&reward.author)
 			}
 		}
 	}
@@ -138,7 +148,7 @@ mod tests {
 	use util::Address;
 	use util::sha3::Hashable;
 	use util::bloom::Bloomable;
-	use trace::trace::{Action, Call, Res, Create, CreateResult, Suicide};
+	use trace::trace::{Action, Call, Res, Create, CreateResult, CreateType, Suicide, Reward, RewardType};
 	use trace::flat::FlatTrace;
 	use trace::{Filter, AddressesFilter, TraceError};
 	use types::executed::CallType;
@@ -149,6 +159,8 @@ mod tests {
 			range: (0..0),
 			from_address: AddressesFilter::from(vec![]),
 			to_address: AddressesFilter::from(vec![]),
+			after: None,
+			count: None,
 		};
 
 		let blooms = filter.bloom_possibilities();
@@ -161,6 +173,8 @@ mod tests {
 			range: (0..0),
 			from_address: AddressesFilter::from(vec![Address::from(1)]),
 			to_address: AddressesFilter::from(vec![Address::from(2)]),
+			after: None,
+			count: None,
 		};
 
 		let blooms = filter.bloom_possibilities();
@@ -177,6 +191,8 @@ mod tests {
 			range: (0..0),
 			from_address: AddressesFilter::from(vec![Address::from(1)]),
 			to_address: AddressesFilter::from(vec![]),
+			after: None,
+			count: None,
 		};
 
 		let blooms = filter.bloom_possibilities();
@@ -192,6 +208,8 @@ mod tests {
 			range: (0..0),
 			from_address: AddressesFilter::from(vec![]),
 			to_address: AddressesFilter::from(vec![Address::from(1)]),
+			after: None,
+			count: None,
 		};
 
 		let blooms = filter.bloom_possibilities();
@@ -207,6 +225,8 @@ mod tests {
 			range: (0..0),
 			from_address: AddressesFilter::from(vec![Address::from(1), Address::from(3)]),
 			to_address: AddressesFilter::from(vec![Address::from(2), Address::from(4)]),
+			after: None,
+			count: None,
 		};
 
 		let blooms = filter.bloom_possibilities();
@@ -239,42 +259,56 @@ mod tests {
 			range: (0..0),
 			from_address: AddressesFilter::from(vec![Address::from(1)]),
 			to_address: AddressesFilter::from(vec![]),
+			after: None,
+			count: None,
 		};
 
 		let f1 = Filter {
 			range: (0..0),
 			from_address: AddressesFilter::from(vec![Address::from(3), Address::from(1)]),
 			to_address: AddressesFilter::from(vec![]),
+			after: None,
+			count: None,
 		};
 
 		let f2 = Filter {
 			range: (0..0),
 			from_address: AddressesFilter::from(vec![]),
 			to_address: AddressesFilter::from(vec![]),
+			after: None,
+			count: None,
 		};
 
 		let f3 = Filter {
 			range: (0..0),
 			from_address: AddressesFilter::from(vec![]),
 			to_address: AddressesFilter::from(vec![Address::from(2)]),
+			after: None,
+			count: None,
 		};
 
 		let f4 = Filter {
 			range: (0..0),
 			from_address: AddressesFilter::from(vec![]),
 			to_address: AddressesFilter::from(vec![Address::from(2), Address::from(3)]),
+			after: None,
+			count: None,
 		};
 
 		let f5 = Filter {
 			range: (0..0),
 			from_address: AddressesFilter::from(vec![Address::from(1)]),
 			to_address: AddressesFilter::from(vec![Address::from(2), Address::from(3)]),
+			after: None,
+			count: None,
 		};
 
 		let f6 = Filter {
 			range: (0..0),
 			from_address: AddressesFilter::from(vec![Address::from(1)]),
 			to_address: AddressesFilter::from(vec![Address::from(4)]),
+			after: None,
+			count: None,
 		};
 
 		let trace = FlatTrace {
@@ -305,6 +339,7 @@ mod tests {
 				value: 3.into(),
 				gas: 4.into(),
 				init: vec![0x5],
+				create_type: CreateType::Create,
 			}),
 			result: Res::Create(CreateResult {
 				gas_used: 10.into(),
@@ -341,5 +376,24 @@ mod tests {
 		assert!(f4.matches(&trace));
 		assert!(f5.matches(&trace));
 		assert!(!f6.matches(&trace));
+
+		let trace = FlatTrace {
+			action: Action::Reward(Reward {
+				author: 2.into(),
+				value: 100.into(),
+				reward_type: RewardType::Block,
+			}),
+			result: Res::None,
+			trace_address: vec![].into_iter().collect(),
+			subtraces: 0
+		};
+
+		assert!(!f0.matches(&trace));
+		assert!(!f1.matches(&trace));
+		assert!(f2.matches(&trace));
+		assert!(f3.matches(&trace));
+		assert!(f4.matches(&trace));
+		assert!(!f5.matches(&trace));
+		assert!(!f6.matches(&trace));
 	}
 }
@@ -33,10 +33,10 @@ pub struct LocalizedTrace {
 	///
 	/// [index in root, index in first CALL, index in second CALL, ...]
 	pub trace_address: Vec<usize>,
-	/// Transaction number within the block.
-	pub transaction_number: usize,
-	/// Signed transaction hash.
-	pub transaction_hash: H256,
+	/// Transaction number within the block. `None` for block and uncle rewards.
+	pub transaction_number: Option<usize>,
+	/// Signed transaction hash. `None` for block and uncle rewards.
+	pub transaction_hash: Option<H256>,
 	/// Block number.
 	pub block_number: BlockNumber,
 	/// Block hash.
@@ -21,3 +21,5 @@ pub mod filter;
 pub mod flat;
 pub mod trace;
 pub mod localized;
+pub mod struct_log;
+pub mod call_tree;
@@ -0,0 +1,65 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Per-instruction VM state logs.
+
+use std::collections::BTreeMap;
+use util::{U256, H256, Bytes};
+
+/// Options of the struct log tracer.
+#[derive(Debug, PartialEq, Clone, Copy, Default, Binary)]
+pub struct StructLogOptions {
+	/// Do not record the stack.
+	pub disable_stack: bool,
+	/// Do not record the memory.
+	pub disable_memory: bool,
+	/// Do not record the storage.
+	pub disable_storage: bool,
+}
+
+/// State of the VM before execution of a single instruction.
+#[derive(Debug, PartialEq, Clone, Binary)]
+pub struct StructLog {
+	/// The program counter.
+	pub pc: usize,
+	/// The instruction to be executed.
+	pub instruction: u8,
+	/// Gas left before execution of the instruction.
+	pub gas: U256,
+	/// The gas cost for this instruction.
+	pub gas_cost: U256,
+	/// Call depth, starting with 1 for the transaction itself.
+	pub depth: usize,
+	/// The stack, bottom first. `None` if disabled.
+	pub stack: Option<Vec<U256>>,
+	/// The memory. `None` if disabled.
+	pub memory: Option<Bytes>,
+	/// Storage values written by the current call so far. `None` if disabled.
+	pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+/// Transaction execution traced with struct logs.
+#[derive(Debug, PartialEq, Clone, Binary)]
+pub struct StructLogTrace {
+	/// Gas used by the transaction.
+	pub gas_used: U256,
+	/// True if the execution failed or was reverted.
+	pub failed: bool,
+	/// Transaction output.
+	pub output: Bytes,
+	/// State of the VM for every executed instruction.
+	pub struct_logs: Vec<StructLog>,
+}
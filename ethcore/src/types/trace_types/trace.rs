@@ -23,6 +23,7 @@ use rlp::*;
 
 use action_params::ActionParams;
 use basic_types::LogBloom;
+use evm::CreateContractAddress;
 use types::executed::CallType;
 use super::error::Error;
 
@@ -173,6 +174,8 @@ pub struct Create {
 	pub gas: U256,
 	/// The init code.
 	pub init: Bytes,
+	/// The type of the create instruction.
+	pub create_type: CreateType,
 }
 
 impl From<ActionParams> for Create {
@@ -182,17 +185,19 @@ impl From<ActionParams> for Create {
 			value: p.value.value(),
 			gas: p.gas,
 			init: p.code.map_or_else(Vec::new, |c| (*c).clone()),
+			create_type: p.address_scheme.into(),
 		}
 	}
 }
 
 impl Encodable for Create {
 	fn rlp_append(&self, s: &mut RlpStream) {
-		s.begin_list(4);
+		s.begin_list(5);
 		s.append(&self.from);
 		s.append(&self.value);
 		s.append(&self.gas);
 		s.append(&self.init);
+		s.append(&self.create_type);
 	}
 }
 
@@ -204,12 +209,55 @@ impl Decodable for Create {
 			value: try!(d.val_at(1)),
 			gas: try!(d.val_at(2)),
 			init: try!(d.val_at(3)),
+			// traces stored before `CREATE2` was introduced have only 4 items.
+			create_type: match d.item_count() {
+				4 => CreateType::Create,
+				_ => try!(d.val_at(4)),
+			},
 		};
 
 		Ok(res)
 	}
 }
 
+/// The type of the create-like instruction.
+#[derive(Debug, PartialEq, Clone, Binary)]
+pub enum CreateType {
+	/// CREATE.
+	Create,
+	/// CREATE2.
+	Create2,
+}
+
+impl From<CreateContractAddress> for CreateType {
+	fn from(scheme: CreateContractAddress) -> Self {
+		match scheme {
+			CreateContractAddress::FromSenderAndNonce => CreateType::Create,
+			CreateContractAddress::FromSenderSaltAndCodeHash(_) => CreateType::Create2,
+		}
+	}
+}
+
+impl Encodable for CreateType {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		let v = match *self {
+			CreateType::Create => 0u32,
+			CreateType::Create2 => 1,
+		};
+		s.append(&v);
+	}
+}
+
+impl Decodable for CreateType {
+	fn decode<D>(decoder: &D) -> Result<Self, DecoderError> where D: Decoder {
+		decoder.as_rlp().as_val().and_then(|v| Ok(match v {
+			0u32 => CreateType::Create,
+			1 => CreateType::Create2,
+			_ => return Err(DecoderError::Custom("Invalid value of CreateType item")),
+		}))
+	}
+}
+
 impl Create {
 	/// Returns bloom create action bloom.
 	/// The bloom contains only from address.
@@ -259,6 +307,75 @@ impl Decodable for Suicide {
 	}
 }
 
+/// Type of a reward.
+#[derive(Debug, PartialEq, Clone, Binary)]
+pub enum RewardType {
+	/// Reward for mining a block.
+	Block,
+	/// Reward for including an uncle.
+	Uncle,
+}
+
+impl Encodable for RewardType {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		let v = match *self {
+			RewardType::Block => 0u32,
+			RewardType::Uncle => 1,
+		};
+		s.append(&v);
+	}
+}
+
+impl Decodable for RewardType {
+	fn decode<D>(decoder: &D) -> Result<Self, DecoderError> where D: Decoder {
+		decoder.as_rlp().as_val().and_then(|v| Ok(match v {
+			0u32 => RewardType::Block,
+			1 => RewardType::Uncle,
+			_ => return Err(DecoderError::Custom("Invalid value of RewardType item")),
+		}))
+	}
+}
+
+/// Reward action.
+#[derive(Debug, Clone, PartialEq, Binary)]
+pub struct Reward {
+	/// Author's address.
+	pub author: Address,
+	/// Reward amount.
+	pub value: U256,
+	/// Reward type.
+	pub reward_type: RewardType,
+}
+
+impl Reward {
+	/// Return reward action bloom.
+	pub fn bloom(&self) -> LogBloom {
+		LogBloom::from_bloomed(&self.author.sha3())
+	}
+}
+
+impl Encodable for Reward {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(3);
+		s.append(&self.author);
+		s.append(&self.value);
+		s.append(&self.reward_type);
+	}
+}
+
+impl Decodable for Reward {
+	fn decode<D>(decoder: &D) -> Result<Self, DecoderError> where D: Decoder {
+		let d = decoder.as_rlp();
+		let res = Reward {
+			author: try!(d.val_at(0)),
+			value: try!(d.val_at(1)),
+			reward_type: try!(d.val_at(2)),
+		};
+
+		Ok(res)
+	}
+}
+
 
 /// Description of an action that we trace; will be either a call or a create.
 #[derive(Debug, Clone, PartialEq, Binary)]
@@ -269,6 +386,8 @@ pub enum Action {
 	Create(Create),
 	/// Suicide.
 	Suicide(Suicide),
+	/// Reward for a block or an uncle.
+	Reward(Reward),
 }
 
 impl Encodable for Action {
@@ -286,6 +405,10 @@ impl Encodable for Action {
 			Action::Suicide(ref suicide) => {
 				s.append(&2u8);
 				s.append(suicide);
+			},
+			Action::Reward(ref reward) => {
+				s.append(&3u8);
+				s.append(reward);
 			}
 		}
 	}
@@ -299,6 +422,7 @@ impl Decodable for Action {
 			0 => d.val_at(1).map(Action::Call),
 			1 => d.val_at(1).map(Action::Create),
 			2 => d.val_at(1).map(Action::Suicide),
+			3 => d.val_at(1).map(Action::Reward),
 			_ => Err(DecoderError::Custom("Invalid action type.")),
 		}
 	}
@@ -311,6 +435,7 @@ impl Action {
 			Action::Call(ref call) => call.bloom(),
 			Action::Create(ref create) => create.bloom(),
 			Action::Suicide(ref suicide) => suicide.bloom(),
+			Action::Reward(ref reward) => reward.bloom(),
 		}
 	}
 }
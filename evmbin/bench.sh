@@ -21,3 +21,6 @@ echo "^^^^ ethvm"
 echo "^^^^ usize"
 ./target/release/evm stats --code $CODE2
 echo "^^^^ U256"
+
+# BUILT-IN CASES
+./target/release/evm bench
@@ -0,0 +1,128 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Repeatable time and gas measurements of the EVM.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use util::{U256, Uint, Bytes};
+use ethcore::evm::VMType;
+use ethcore::action_params::ActionParams;
+use {run_vm_with, Failure};
+
+/// Built-in benchmark case.
+pub struct Case {
+	/// Name of the case.
+	pub name: &'static str,
+	/// Contract code as hex.
+	pub code: &'static str,
+}
+
+/// Built-in benchmark cases, also used by `cargo bench`.
+pub const CASES: &'static [Case] = &[
+	Case {
+		name: "loop",
+		code: "606060405260005b620042408112156019575b6001016007565b600081905550600680602b6000396000f3606060405200",
+	},
+	Case {
+		name: "rng",
+		code: "6060604052600360056007600b60005b62004240811215607f5767ffe7649d5eca84179490940267f47ed85c4b9a6379019367f8e5dd9a5c994bba9390930267f91d87e4b8b74e55019267ff97f6f3b29cda529290920267f393ada8dd75c938019167fe8d437c45bb3735830267f47d9a7b5428ffec019150600101600f565b838518831882186000555050505050600680609a6000396000f3606060405200",
+	},
+];
+
+/// Returns the built-in case with given name.
+pub fn case(name: &str) -> Option<&'static Case> {
+	CASES.iter().find(|case| case.name == name)
+}
+
+/// Statistics of the measured runs.
+pub struct Stats {
+	runs: usize,
+	gas_used: U256,
+	mean: f64,
+	median: f64,
+	stddev: f64,
+}
+
+impl Stats {
+	fn new(gas_used: U256, times: &[Duration]) -> Self {
+		let mut nanos: Vec<f64> = times.iter()
+			.map(|t| t.as_secs() as f64 * 1e9 + t.subsec_nanos() as f64)
+			.collect();
+		nanos.sort_by(|a, b| a.partial_cmp(b).expect("durations are never NaN; qed"));
+
+		let runs = nanos.len();
+		let mean = nanos.iter().sum::<f64>() / runs as f64;
+		let median = match runs % 2 {
+			0 => (nanos[runs / 2 - 1] + nanos[runs / 2]) / 2.0,
+			_ => nanos[runs / 2],
+		};
+		let variance = nanos.iter().map(|n| (n - mean) * (n - mean)).sum::<f64>() / runs as f64;
+
+		Stats {
+			runs: runs,
+			gas_used: gas_used,
+			mean: mean,
+			median: median,
+			stddev: variance.sqrt(),
+		}
+	}
+
+	/// Gas executed per second, based on the mean time of a run.
+	pub fn gas_per_second(&self) -> f64 {
+		self.gas_used.low_u64() as f64 * 1e9 / self.mean
+	}
+}
+
+impl fmt::Display for Stats {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} runs, mean {:.3}ms, median {:.3}ms, stddev {:.3}ms, gas used {}, {:.3} Mgas/s",
+			self.runs,
+			self.mean / 1e6,
+			self.median / 1e6,
+			self.stddev / 1e6,
+			self.gas_used,
+			self.gas_per_second() / 1e6
+		)
+	}
+}
+
+/// Executes the code `warmup` times, then measures `runs` executions of it.
+pub fn run(code: Bytes, data: Option<Bytes>, gas: U256, vm_type: VMType, warmup: usize, runs: usize) -> Result<Stats, Failure> {
+	let code = Arc::new(code);
+	let params = || {
+		let mut params = ActionParams::default();
+		params.gas = gas;
+		params.code = Some(code.clone());
+		params.data = data.clone();
+		params
+	};
+
+	for _ in 0..warmup {
+		try!(run_vm_with(params(), vm_type.clone()));
+	}
+
+	let mut gas_used = U256::zero();
+	let mut times = Vec::with_capacity(runs);
+	for _ in 0..runs {
+		let success = try!(run_vm_with(params(), vm_type.clone()));
+		gas_used = success.gas_used;
+		times.push(success.time);
+	}
+
+	Ok(Stats::new(gas_used, &times))
+}
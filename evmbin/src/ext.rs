@@ -20,11 +20,13 @@ use std::sync::Arc;
 use std::collections::HashMap;
 use util::{U256, H256, Address, Bytes, FixedHash};
 use ethcore::client::EnvInfo;
-use ethcore::evm::{self, Ext, ContractCreateResult, MessageCallResult, Schedule, CallType};
+use ethcore::evm::{self, Ext, ContractCreateResult, MessageCallResult, Schedule, CallType, CreateContractAddress};
 
 pub struct FakeExt {
 	schedule: Schedule,
 	store: HashMap<H256, H256>,
+	original_store: HashMap<H256, H256>,
+	sstore_clears: i64,
 	depth: usize,
 }
 
@@ -33,6 +35,8 @@ impl Default for FakeExt {
 		FakeExt {
 			schedule: Schedule::new_homestead_gas_fix(),
 			store: HashMap::new(),
+			original_store: HashMap::new(),
+			sstore_clears: 0,
 			depth: 1,
 		}
 	}
@@ -43,6 +47,10 @@ impl Ext for FakeExt {
 		self.store.get(key).unwrap_or(&H256::new()).clone()
 	}
 
+	fn original_storage_at(&self, key: &H256) -> H256 {
+		self.original_store.get(key).unwrap_or(&H256::new()).clone()
+	}
+
 	fn set_storage(&mut self, key: H256, value: H256) {
 		self.store.insert(key, value);
 	}
@@ -67,7 +75,7 @@ impl Ext for FakeExt {
 		unimplemented!();
 	}
 
-	fn create(&mut self, _gas: &U256, _value: &U256, _code: &[u8]) -> ContractCreateResult {
+	fn create(&mut self, _gas: &U256, _value: &U256, _code: &[u8], _address: CreateContractAddress) -> ContractCreateResult {
 		unimplemented!();
 	}
 
@@ -95,7 +103,7 @@ impl Ext for FakeExt {
 		unimplemented!();
 	}
 
-	fn ret(self, gas: &U256, _data: &[u8]) -> evm::Result<U256> {
+	fn ret(self, gas: &U256, _data: &[u8], _apply_state: bool) -> evm::Result<U256> {
 		Ok(*gas)
 	}
 
@@ -115,8 +123,15 @@ impl Ext for FakeExt {
 		self.depth
 	}
 
-	fn inc_sstore_clears(&mut self) {
-		unimplemented!();
-		// self.sstore_clears += 1;
+	fn is_static(&self) -> bool {
+		false
+	}
+
+	fn add_sstore_refund(&mut self, value: usize) {
+		self.sstore_clears += value as i64;
+	}
+
+	fn sub_sstore_refund(&mut self, value: usize) {
+		self.sstore_clears -= value as i64;
 	}
 }
@@ -0,0 +1,80 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! VM tracer printing executed instructions as JSON.
+
+use std::collections::BTreeMap;
+use serde_json::{self, Value};
+use util::{U256, Uint};
+use ethcore::evm::instruction_name;
+use ethcore::trace::VMTracer;
+
+/// Formats a number as hex, the way other clients print it in their JSON traces.
+pub fn hex(value: &U256) -> String {
+	if value.is_zero() {
+		"0x0".into()
+	} else {
+		format!("{:x}", value)
+	}
+}
+
+/// Serializes given fields as a single line JSON object.
+pub fn json_line(fields: BTreeMap<String, Value>) -> String {
+	serde_json::to_string(&Value::Object(fields)).expect("Serialization of a json value cannot fail; qed")
+}
+
+/// Prints a single JSON line with the state of the VM before each executed instruction.
+#[derive(Default)]
+pub struct JsonInformant {
+	depth: usize,
+	pc: usize,
+	instruction: u8,
+	gas_cost: U256,
+}
+
+impl VMTracer for JsonInformant {
+	type Output = ();
+
+	fn trace_prepare_execute(&mut self, pc: usize, instruction: u8, gas_cost: &U256) -> bool {
+		self.pc = pc;
+		self.instruction = instruction;
+		self.gas_cost = gas_cost.clone();
+		true
+	}
+
+	fn trace_state(&mut self, gas_left: &U256, stack: &[U256], memory: &[u8]) {
+		let mut fields = BTreeMap::new();
+		fields.insert("pc".to_owned(), Value::U64(self.pc as u64));
+		fields.insert("op".to_owned(), Value::String(instruction_name(self.instruction).to_owned()));
+		fields.insert("gas".to_owned(), Value::String(hex(gas_left)));
+		fields.insert("gasCost".to_owned(), Value::String(hex(&self.gas_cost)));
+		fields.insert("depth".to_owned(), Value::U64(self.depth as u64));
+		fields.insert("stackTop".to_owned(), stack.last().map_or(Value::Null, |top| Value::String(hex(top))));
+		fields.insert("memSize".to_owned(), Value::U64(memory.len() as u64));
+		println!("{}", json_line(fields));
+	}
+
+	fn prepare_subtrace(&self, _code: &[u8]) -> Self {
+		JsonInformant {
+			depth: self.depth + 1,
+			..Default::default()
+		}
+	}
+
+	fn done_subtrace(&mut self, _sub: Self) {}
+
+	fn drain(self) -> Option<()> { None }
+}
@@ -19,21 +19,33 @@
 #![warn(missing_docs)]
 #![allow(dead_code)]
 extern crate ethcore;
+extern crate ethjson;
 extern crate rustc_serialize;
 extern crate docopt;
+extern crate serde_json;
 #[macro_use]
 extern crate ethcore_util as util;
 
+pub mod bench;
 mod ext;
+mod informant;
+mod state_test;
 
 use std::sync::Arc;
+use std::collections::BTreeMap;
 use std::time::{Instant, Duration};
 use std::fmt;
+use std::fs::File;
 use std::str::FromStr;
 use docopt::Docopt;
-use util::{U256, FromHex, Uint, Bytes};
+use serde_json::Value;
+use rustc_serialize::hex::ToHex;
+use util::{U256, FromHex, Uint, Bytes, Address};
+use ethcore::client::{EvmTestClient, EvmTestError, EnvInfo};
+use ethcore::ethereum;
 use ethcore::evm::{self, Factory, VMType, Finalize};
 use ethcore::action_params::ActionParams;
+use ethcore::spec::Spec;
 
 const USAGE: &'static str = r#"
 EVM implementation for Parity.
@@ -41,13 +53,34 @@ EVM implementation for Parity.
 
 Usage:
     evmbin stats [options]
+    evmbin trace [options]
+    evmbin bench [options]
+    evmbin state-test <file> [--only NAME --fork NAME]
     evmbin [-h | --help]
 
+State test options:
+    --only NAME        Runs only a single test matching the name.
+    --fork NAME        Only run the post states of given fork,
+                       e.g. Frontier, Homestead, EIP150, EIP158, Byzantium
+                       or Constantinople.
+
 Transaction options:
     --code CODE        Contract code as hex (without 0x)
     --input DATA       Input data as hex (without 0x)
     --gas GAS          Supplied gas as hex (without 0x)
 
+Trace options:
+    --from ADDRESS     Sender address as hex (without 0x)
+    --to ADDRESS       Address of the executed code as hex (without 0x)
+    --chain CHAIN      Chain spec JSON file. Its genesis accounts are the
+                       pre-state, so the code may call other contracts.
+
+Bench options:
+    --case NAME        Run a built-in case (loop or rng) instead of --code.
+                       All built-in cases are run if neither is given.
+    --runs N           Number of measured runs [default: 100].
+    --warmup N         Number of runs before measuring [default: 10].
+
 General options:
     -h, --help         Display this message and exit.
 "#;
@@ -56,6 +89,85 @@ General options:
 fn main() {
 	let args: Args = Docopt::new(USAGE).and_then(|d| d.decode()).unwrap_or_else(|e| e.exit());
 
+	if args.cmd_state_test {
+		run_state_test(args)
+	} else if args.cmd_trace {
+		run_trace(args)
+	} else if args.cmd_bench {
+		run_bench(args)
+	} else {
+		run_stats(args)
+	}
+}
+
+fn run_state_test(args: Args) {
+	let only = args.flag_only.as_ref().map(String::as_str);
+	let fork = args.flag_fork.as_ref().map(String::as_str);
+	match state_test::run(&args.arg_file, only, fork) {
+		Ok(0) => {},
+		Ok(_) => ::std::process::exit(1),
+		Err(err) => die(err),
+	}
+}
+
+fn run_trace(args: Args) {
+	let spec = args.spec();
+	let mut client = EvmTestClient::new(&spec).unwrap_or_else(|e| die(e));
+
+	let mut params = ActionParams::default();
+	params.sender = args.from();
+	params.origin = args.from();
+	params.address = args.to();
+	params.code_address = args.to();
+	params.gas = args.gas();
+	params.code = Some(Arc::new(args.code()));
+	params.data = args.data();
+
+	let initial_gas = params.gas;
+	let mut informant = informant::JsonInformant::default();
+	let (output, gas_used, error) = match client.call(&EnvInfo::default(), params, &mut informant) {
+		Ok(ref result) if result.apply_state => (result.return_data.to_hex(), initial_gas - result.gas_left, None),
+		Ok(result) => (result.return_data.to_hex(), initial_gas - result.gas_left, Some("Reverted".to_owned())),
+		Err(EvmTestError::Evm(err)) => (String::new(), initial_gas, Some(format!("{:?}", err))),
+		Err(err) => die(err),
+	};
+
+	let mut fields = BTreeMap::new();
+	fields.insert("output".to_owned(), Value::String(format!("0x{}", output)));
+	fields.insert("gasUsed".to_owned(), Value::String(informant::hex(&gas_used)));
+	if let Some(error) = error {
+		fields.insert("error".to_owned(), Value::String(error));
+	}
+	println!("{}", informant::json_line(fields));
+}
+
+fn run_bench(args: Args) {
+	if args.flag_runs == 0 {
+		die("At least one run is required.");
+	}
+
+	let cases = match (args.flag_code.is_some(), args.flag_case.as_ref()) {
+		(true, _) => vec![("code".to_owned(), args.code())],
+		(false, Some(name)) => {
+			let case = bench::case(name).unwrap_or_else(|| die(format!("Unknown case: {}", name)));
+			vec![(case.name.to_owned(), case.code.from_hex().expect("built-in cases are valid hex; qed"))]
+		},
+		(false, None) => bench::CASES.iter()
+			.map(|case| (case.name.to_owned(), case.code.from_hex().expect("built-in cases are valid hex; qed")))
+			.collect(),
+	};
+
+	for (name, code) in cases {
+		for vm_type in VMType::all() {
+			match bench::run(code.clone(), args.data(), args.gas(), vm_type.clone(), args.flag_warmup, args.flag_runs) {
+				Ok(stats) => println!("{} [{}]: {}", name, vm_type, stats),
+				Err(failure) => die(failure),
+			}
+		}
+	}
+}
+
+fn run_stats(args: Args) {
 	let mut params = ActionParams::default();
 	params.gas = args.gas();
 	params.code = Some(Arc::new(args.code()));
@@ -70,8 +182,13 @@ fn main() {
 
 /// Execute VM with given `ActionParams`
 pub fn run_vm(params: ActionParams) -> Result<Success, Failure> {
+	run_vm_with(params, VMType::Interpreter)
+}
+
+/// Execute VM of given type with given `ActionParams`
+pub fn run_vm_with(params: ActionParams, vm_type: VMType) -> Result<Success, Failure> {
 	let initial_gas = params.gas;
-	let factory = Factory::new(VMType::Interpreter, 1024);
+	let factory = Factory::new(vm_type, 1024);
 	let mut vm = factory.create(params.gas);
 	let mut ext = ext::FakeExt::default();
 
@@ -80,8 +197,8 @@ pub fn run_vm(params: ActionParams) -> Result<Success, Failure> {
 	let duration = start.elapsed();
 
 	match gas_left {
-		Ok(gas_left) => Ok(Success {
-			gas_used: initial_gas - gas_left,
+		Ok(result) => Ok(Success {
+			gas_used: initial_gas - result.gas_left,
 			// TODO [ToDr] get output from ext
 			output: Vec::new(),
 			time: duration,
@@ -129,9 +246,21 @@ impl fmt::Display for Failure {
 #[derive(Debug, RustcDecodable)]
 struct Args {
 	cmd_stats: bool,
+	cmd_trace: bool,
+	cmd_bench: bool,
+	cmd_state_test: bool,
+	arg_file: String,
+	flag_only: Option<String>,
+	flag_fork: Option<String>,
 	flag_code: Option<String>,
 	flag_gas: Option<String>,
 	flag_input: Option<String>,
+	flag_from: Option<String>,
+	flag_to: Option<String>,
+	flag_chain: Option<String>,
+	flag_case: Option<String>,
+	flag_runs: usize,
+	flag_warmup: usize,
 }
 
 impl Args {
@@ -154,9 +283,31 @@ impl Args {
 			.clone()
 			.and_then(|d| d.from_hex().ok())
 	}
+
+	pub fn from(&self) -> Address {
+		self.flag_from
+			.clone()
+			.map_or_else(Address::default, |a| Address::from_str(&a).unwrap_or_else(|_| die("Invalid sender address.")))
+	}
+
+	pub fn to(&self) -> Address {
+		self.flag_to
+			.clone()
+			.map_or_else(Address::default, |a| Address::from_str(&a).unwrap_or_else(|_| die("Invalid code address.")))
+	}
+
+	pub fn spec(&self) -> Spec {
+		match self.flag_chain {
+			Some(ref path) => {
+				let file = File::open(path).unwrap_or_else(|e| die(format!("Cannot open chain spec {}: {}", path, e)));
+				Spec::load(file).unwrap_or_else(|e| die(e))
+			},
+			None => ethereum::new_eip161_test(),
+		}
+	}
 }
 
-fn die(msg: &'static str) -> ! {
+fn die<T: fmt::Display>(msg: T) -> ! {
 	println!("{}", msg);
 	::std::process::exit(-1)
 }
@@ -0,0 +1,113 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Standard state tests runner.
+
+use std::fs::File;
+use util::H256;
+use ethcore::client::{EvmTestClient, EnvInfo};
+use ethcore::ethereum;
+use ethcore::pod_state::PodState;
+use ethcore::spec::Spec;
+use ethcore::transaction::SignedTransaction;
+use ethjson::state::{GeneralTest, PostStateResult};
+
+/// Returns the chain spec used for tests of given fork, if the fork is supported.
+fn fork_spec(name: &str) -> Option<Spec> {
+	match name {
+		"Frontier" => Some(ethereum::new_frontier_test()),
+		"Homestead" => Some(ethereum::new_homestead_test()),
+		"EIP150" => Some(ethereum::new_eip150_test()),
+		"EIP158" => Some(ethereum::new_eip161_test()),
+		"Byzantium" => Some(ethereum::new_byzantium_test()),
+		"Constantinople" => Some(ethereum::new_constantinople_test()),
+		_ => None,
+	}
+}
+
+/// Runs the state tests in given file and prints the result of each of them.
+/// Returns the number of failed tests.
+pub fn run(path: &str, only: Option<&str>, fork: Option<&str>) -> Result<usize, String> {
+	let file = try!(File::open(path).map_err(|e| format!("Cannot open {}: {}", path, e)));
+	let tests = try!(GeneralTest::load(file).map_err(|e| format!("Cannot parse {}: {}", path, e)));
+
+	let (mut passed, mut failed) = (0, 0);
+	for (name, test) in tests {
+		if only.map_or(false, |only| only != name) {
+			continue;
+		}
+
+		let env_info: EnvInfo = test.env.into();
+		let pre: PodState = test.pre_state.into();
+
+		for (fork_name, results) in test.post_states {
+			if fork.map_or(false, |fork| fork != fork_name) {
+				continue;
+			}
+
+			let spec = match fork_spec(&fork_name) {
+				Some(spec) => spec,
+				None => {
+					// not running the post states must not look like a passing run
+					failed += results.len();
+					println!("   - {} [{}]... FAIL", name, fork_name);
+					println!("!!! Unsupported fork: {}", fork_name);
+					continue;
+				},
+			};
+
+			for (idx, expected) in results.iter().enumerate() {
+				let label = format!("{} [{}:{}]", name, fork_name, idx);
+				let result = match test.transaction.select(&expected.indexes) {
+					Some(transaction) => check(&spec, pre.clone(), &env_info, &transaction.into(), expected),
+					None => Err("Transaction indexes out of range".into()),
+				};
+
+				match result {
+					Ok(()) => {
+						passed += 1;
+						println!("   - {}... ok", label);
+					},
+					Err(err) => {
+						failed += 1;
+						println!("   - {}... FAIL", label);
+						println!("!!! {}", err);
+					},
+				}
+			}
+		}
+	}
+
+	println!("{} tests passed, {} failed.", passed, failed);
+	Ok(failed)
+}
+
+fn check(spec: &Spec, pre: PodState, env_info: &EnvInfo, transaction: &SignedTransaction, expected: &PostStateResult) -> Result<(), String> {
+	let mut client = try!(EvmTestClient::from_pod_state(spec, pre).map_err(|e| e.to_string()));
+	let result = try!(client.transact(env_info, transaction).map_err(|e| e.to_string()));
+
+	let expected_root: H256 = expected.hash.clone().into();
+	if result.state_root != expected_root {
+		return Err(format!("State root mismatch (got: {}, expect: {}), outcome: {:?}", result.state_root, expected_root, result.outcome));
+	}
+
+	let expected_logs: H256 = expected.logs.clone().into();
+	if result.logs_hash != expected_logs {
+		return Err(format!("Logs hash mismatch (got: {}, expect: {})", result.logs_hash, expected_logs));
+	}
+
+	Ok(())
+}
@@ -16,6 +16,8 @@
 
 //! Spec builtin deserialization.
 
+use uint::Uint;
+
 /// Linear pricing.
 #[derive(Debug, PartialEq, Deserialize, Clone)]
 pub struct Linear {
@@ -25,12 +27,34 @@ pub struct Linear {
 	pub word: usize,
 }
 
+/// Pricing for modular exponentiation.
+#[derive(Debug, PartialEq, Deserialize, Clone)]
+pub struct Modexp {
+	/// Price divisor.
+	pub divisor: usize,
+}
+
+/// Pricing for the alt_bn128 pairing check.
+#[derive(Debug, PartialEq, Deserialize, Clone)]
+pub struct AltBn128Pairing {
+	/// Base price.
+	pub base: usize,
+	/// Price per point pair.
+	pub pair: usize,
+}
+
 /// Pricing variants.
 #[derive(Debug, PartialEq, Deserialize, Clone)]
 pub enum Pricing {
 	/// Linear pricing.
 	#[serde(rename="linear")]
 	Linear(Linear),
+	/// Pricing for modular exponentiation.
+	#[serde(rename="modexp")]
+	Modexp(Modexp),
+	/// Pricing for the alt_bn128 pairing check.
+	#[serde(rename="alt_bn128_pairing")]
+	AltBn128Pairing(AltBn128Pairing),
 }
 
 /// Spec builtin.
@@ -40,12 +64,16 @@ pub struct Builtin {
 	pub name: String,
 	/// Builtin pricing.
 	pub pricing: Pricing,
+	/// Block number at which the builtin becomes active. Active from genesis if not specified.
+	pub activate_at: Option<Uint>,
 }
 
 #[cfg(test)]
 mod tests {
 	use serde_json;
-	use spec::builtin::{Builtin, Pricing, Linear};
+	use spec::builtin::{Builtin, Pricing, Linear, Modexp, AltBn128Pairing};
+	use uint::Uint;
+	use util::U256;
 
 	#[test]
 	fn builtin_deserialization() {
@@ -57,4 +85,39 @@ mod tests {
 		assert_eq!(deserialized.name, "ecrecover");
 		assert_eq!(deserialized.pricing, Pricing::Linear(Linear { base: 3000, word: 0 }));
 	}
+
+	#[test]
+	fn builtin_modexp_deserialization() {
+		let s = r#"{
+			"name": "modexp",
+			"pricing": { "modexp": { "divisor": 20 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.name, "modexp");
+		assert_eq!(deserialized.pricing, Pricing::Modexp(Modexp { divisor: 20 }));
+		assert!(deserialized.activate_at.is_none());
+	}
+
+	#[test]
+	fn activate_at() {
+		let s = r#"{
+			"name": "modexp",
+			"activate_at": 100000,
+			"pricing": { "modexp": { "divisor": 20 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.name, "modexp");
+		assert_eq!(deserialized.activate_at, Some(Uint(U256::from(100000))));
+	}
+
+	#[test]
+	fn builtin_alt_bn128_pairing_deserialization() {
+		let s = r#"{
+			"name": "alt_bn128_pairing",
+			"pricing": { "alt_bn128_pairing": { "base": 100000, "pair": 80000 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.name, "alt_bn128_pairing");
+		assert_eq!(deserialized.pricing, Pricing::AltBn128Pairing(AltBn128Pairing { base: 100000, pair: 80000 }));
+	}
 }
@@ -68,35 +68,30 @@ pub struct EthashParams {
 	pub bomb_defuse_transition: Option<Uint>,
 
 	/// See main EthashParams docs.
+	#[serde(rename="ecip1010PauseTransition")]
+	pub ecip1010_pause_transition: Option<Uint>,
+	/// See main EthashParams docs.
+	#[serde(rename="ecip1010ContinueTransition")]
+	pub ecip1010_continue_transition: Option<Uint>,
+
+	/// Deprecated, see `Params` docs. Only used when `Params` does not set it.
 	#[serde(rename="eip150Transition")]
 	pub eip150_transition: Option<Uint>,
-
-	/// See main EthashParams docs.
+	/// Deprecated, see `Params` docs. Only used when `Params` does not set it.
 	#[serde(rename="eip155Transition")]
 	pub eip155_transition: Option<Uint>,
-
-	/// See main EthashParams docs.
+	/// Deprecated, see `Params` docs. Only used when `Params` does not set it.
 	#[serde(rename="eip160Transition")]
 	pub eip160_transition: Option<Uint>,
-
-	/// See main EthashParams docs.
+	/// Deprecated, see `Params` docs. Only used when `Params` does not set it.
 	#[serde(rename="eip161abcTransition")]
 	pub eip161abc_transition: Option<Uint>,
-	/// See main EthashParams docs.
+	/// Deprecated, see `Params` docs. Only used when `Params` does not set it.
 	#[serde(rename="eip161dTransition")]
 	pub eip161d_transition: Option<Uint>,
-
-	/// See main EthashParams docs.
-	#[serde(rename="ecip1010PauseTransition")]
-	pub ecip1010_pause_transition: Option<Uint>,
-	/// See main EthashParams docs.
-	#[serde(rename="ecip1010ContinueTransition")]
-	pub ecip1010_continue_transition: Option<Uint>,
-
-	/// See main EthashParams docs.
+	/// Deprecated, see `Params` docs. Only used when `Params` does not set it.
 	#[serde(rename="maxCodeSize")]
 	pub max_code_size: Option<Uint>,
-
 }
 
 /// Ethash engine deserialization.
@@ -109,6 +104,8 @@ pub struct Ethash {
 #[cfg(test)]
 mod tests {
 	use serde_json;
+	use util::U256;
+	use uint::Uint;
 	use spec::ethash::Ethash;
 
 	#[test]
@@ -157,7 +154,10 @@ mod tests {
 			}
 		}"#;
 
-		let _deserialized: Ethash = serde_json::from_str(s).unwrap();
+		let deserialized: Ethash = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.params.eip150_transition, Some(Uint(U256::from(0x42))));
+		assert_eq!(deserialized.params.eip161d_transition, Some(Uint(U256::from(0x42))));
+		assert_eq!(deserialized.params.max_code_size, None);
 	}
 
 	#[test]
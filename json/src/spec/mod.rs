@@ -29,7 +29,7 @@ pub mod basic_authority;
 pub mod authority_round;
 
 pub use self::account::Account;
-pub use self::builtin::{Builtin, Pricing, Linear};
+pub use self::builtin::{Builtin, Pricing, Linear, Modexp, AltBn128Pairing};
 pub use self::genesis::Genesis;
 pub use self::params::Params;
 pub use self::spec::Spec;
@@ -45,6 +45,46 @@ pub struct Params {
 	/// Expected fork block hash.
 	#[serde(rename="forkCanonHash")]
 	pub fork_hash: Option<H256>,
+
+	/// See `CommonParams` docs.
+	#[serde(rename="eip150Transition")]
+	pub eip150_transition: Option<Uint>,
+	/// See `CommonParams` docs.
+	#[serde(rename="eip155Transition")]
+	pub eip155_transition: Option<Uint>,
+	/// See `CommonParams` docs.
+	#[serde(rename="eip160Transition")]
+	pub eip160_transition: Option<Uint>,
+	/// See `CommonParams` docs.
+	#[serde(rename="eip161abcTransition")]
+	pub eip161abc_transition: Option<Uint>,
+	/// See `CommonParams` docs.
+	#[serde(rename="eip161dTransition")]
+	pub eip161d_transition: Option<Uint>,
+	/// See `CommonParams` docs.
+	#[serde(rename="maxCodeSize")]
+	pub max_code_size: Option<Uint>,
+	/// See `CommonParams` docs.
+	#[serde(rename="eip140Transition")]
+	pub eip140_transition: Option<Uint>,
+	/// See `CommonParams` docs.
+	#[serde(rename="eip211Transition")]
+	pub eip211_transition: Option<Uint>,
+	/// See `CommonParams` docs.
+	#[serde(rename="eip214Transition")]
+	pub eip214_transition: Option<Uint>,
+	/// See `CommonParams` docs.
+	#[serde(rename="eip145Transition")]
+	pub eip145_transition: Option<Uint>,
+	/// See `CommonParams` docs.
+	#[serde(rename="eip1014Transition")]
+	pub eip1014_transition: Option<Uint>,
+	/// See `CommonParams` docs.
+	#[serde(rename="eip1283Transition")]
+	pub eip1283_transition: Option<Uint>,
+	/// See `CommonParams` docs.
+	#[serde(rename="wasmActivationTransition")]
+	pub wasm_activation_transition: Option<Uint>,
 }
 
 #[cfg(test)]
@@ -60,7 +100,20 @@ mod tests {
 			"networkID" : "0x1",
 			"subprotocolName" : "exp",
 			"minGasLimit": "0x1388",
-			"accountStartNonce": "0x00"
+			"accountStartNonce": "0x00",
+			"eip150Transition": "0x10",
+			"eip155Transition": "0x10",
+			"eip160Transition": "0x10",
+			"eip161abcTransition": "0x10",
+			"eip161dTransition": "0x10",
+			"maxCodeSize": "0x6000",
+			"eip140Transition": "0x10",
+			"eip211Transition": "0x10",
+			"eip214Transition": "0x10",
+			"eip145Transition": "0x10",
+			"eip1014Transition": "0x10",
+			"eip1283Transition": "0x10",
+			"wasmActivationTransition": "0x10"
 		}"#;
 
 		let _deserialized: Params = serde_json::from_str(s).unwrap();
@@ -0,0 +1,184 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! General state test deserialization.
+
+use std::collections::BTreeMap;
+use std::io::Read;
+use serde_json;
+use serde_json::Error;
+use bytes::Bytes;
+use hash::{Address, H256};
+use uint::Uint;
+use maybe::MaybeEmpty;
+use state::{Env, AccountState, Transaction};
+
+/// General state test deserializer.
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct GeneralTest(BTreeMap<String, GeneralState>);
+
+impl IntoIterator for GeneralTest {
+	type Item = <BTreeMap<String, GeneralState> as IntoIterator>::Item;
+	type IntoIter = <BTreeMap<String, GeneralState> as IntoIterator>::IntoIter;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+
+impl GeneralTest {
+	/// Loads test from json.
+	pub fn load<R>(reader: R) -> Result<Self, Error> where R: Read {
+		serde_json::from_reader(reader)
+	}
+}
+
+/// General state test deserialization.
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct GeneralState {
+	/// Environment.
+	pub env: Env,
+	/// Pre state.
+	#[serde(rename="pre")]
+	pub pre_state: AccountState,
+	/// Transaction variants.
+	pub transaction: MultiTransaction,
+	/// Expected post states, by fork name.
+	#[serde(rename="post")]
+	pub post_states: BTreeMap<String, Vec<PostStateResult>>,
+}
+
+/// State test transaction with several variants of data, gas limit and value.
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct MultiTransaction {
+	/// Transaction data variants.
+	pub data: Vec<Bytes>,
+	/// Gas limit variants.
+	#[serde(rename="gasLimit")]
+	pub gas_limit: Vec<Uint>,
+	/// Gas price.
+	#[serde(rename="gasPrice")]
+	pub gas_price: Uint,
+	/// Nonce.
+	pub nonce: Uint,
+	/// Secret key.
+	#[serde(rename="secretKey")]
+	pub secret: H256,
+	/// To.
+	pub to: MaybeEmpty<Address>,
+	/// Value variants.
+	pub value: Vec<Uint>,
+}
+
+impl MultiTransaction {
+	/// Build the transaction variant selected by given indexes.
+	/// Returns `None` if any of the indexes is out of range.
+	pub fn select(&self, indexes: &PostStateIndexes) -> Option<Transaction> {
+		match (self.data.get(indexes.data), self.gas_limit.get(indexes.gas), self.value.get(indexes.value)) {
+			(Some(data), Some(gas_limit), Some(value)) => Some(Transaction {
+				data: data.clone(),
+				gas_limit: *gas_limit,
+				gas_price: self.gas_price,
+				nonce: self.nonce,
+				secret: self.secret.clone(),
+				to: self.to.clone(),
+				value: *value,
+			}),
+			_ => None,
+		}
+	}
+}
+
+/// Expected outcome of a single transaction variant.
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct PostStateResult {
+	/// Post state root.
+	pub hash: H256,
+	/// Hash of the RLP-encoded logs.
+	pub logs: H256,
+	/// Indexes of the transaction variant.
+	pub indexes: PostStateIndexes,
+}
+
+/// Indexes into `MultiTransaction` variants.
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct PostStateIndexes {
+	/// Index of the data.
+	pub data: usize,
+	/// Index of the gas limit.
+	pub gas: usize,
+	/// Index of the value.
+	pub value: usize,
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use state::{GeneralState, PostStateIndexes};
+
+	#[test]
+	fn general_state_deserialization() {
+		let s = r#"{
+			"env" : {
+				"currentCoinbase" : "0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba",
+				"currentDifficulty" : "0x020000",
+				"currentGasLimit" : "0x7fffffffffffffff",
+				"currentNumber" : "0x01",
+				"currentTimestamp" : "0x03e8",
+				"previousHash" : "0x5e20a0453cecd065ea59c37ac63e079ee08998b6045136a8ce6635c7912ec0b6"
+			},
+			"post" : {
+				"EIP150" : [
+					{
+						"hash" : "0x3e6dacc1575c6a8c76422255eca03529bbf4c0dda75dfc110b22d6dc4152396f",
+						"indexes" : { "data" : 0, "gas" : 0, "value" : 0 },
+						"logs" : "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347"
+					},
+					{
+						"hash" : "0x99a450d8ce5b987a71346d8a0a1203711f770745c7ef326912e46761f14cd764",
+						"indexes" : { "data" : 0, "gas" : 0, "value" : 1 },
+						"logs" : "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347"
+					}
+				]
+			},
+			"pre" : {
+				"0x1000000000000000000000000000000000000000" : {
+					"balance" : "0x0de0b6b3a7640000",
+					"code" : "0x6040600060406000600173100000000000000000000000000000000000000162055730f1600055",
+					"nonce" : "0x00",
+					"storage" : {
+					}
+				}
+			},
+			"transaction" : {
+				"data" : [ "" ],
+				"gasLimit" : [ "0x0a00000000" ],
+				"gasPrice" : "0x01",
+				"nonce" : "0x00",
+				"secretKey" : "0x45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8",
+				"to" : "0x1000000000000000000000000000000000000000",
+				"value" : [ "0x00", "0x01" ]
+			}
+		}"#;
+		let deserialized: GeneralState = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.post_states["EIP150"].len(), 2);
+
+		let selected = deserialized.transaction.select(&PostStateIndexes { data: 0, gas: 0, value: 1 }).unwrap();
+		let value: u64 = selected.value.into();
+		assert_eq!(value, 1);
+		assert!(deserialized.transaction.select(&PostStateIndexes { data: 1, gas: 0, value: 0 }).is_none());
+	}
+}
@@ -20,10 +20,12 @@ pub mod state;
 pub mod transaction;
 pub mod test;
 pub mod log;
+pub mod general;
 
 pub use self::state::State;
 pub use self::transaction::Transaction;
 pub use self::test::Test;
 pub use self::log::Log;
+pub use self::general::{GeneralTest, GeneralState, MultiTransaction, PostStateResult, PostStateIndexes};
 pub use vm::Env as Env;
 pub use blockchain::State as AccountState;
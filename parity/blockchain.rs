@@ -67,6 +67,7 @@ pub enum BlockchainCmd {
 	Import(ImportBlockchain),
 	Export(ExportBlockchain),
 	ExportState(ExportState),
+	RebuildTraces(RebuildTraces),
 }
 
 #[derive(Debug, PartialEq)]
@@ -126,11 +127,26 @@ pub struct ExportState {
 	pub max_balance: Option<U256>,
 }
 
+#[derive(Debug, PartialEq)]
+pub struct RebuildTraces {
+	pub spec: SpecType,
+	pub cache_config: CacheConfig,
+	pub dirs: Directories,
+	pub pruning: Pruning,
+	pub pruning_history: u64,
+	pub compaction: DatabaseCompactionProfile,
+	pub wal: bool,
+	pub fat_db: Switch,
+	pub from_block: BlockId,
+	pub to_block: BlockId,
+}
+
 pub fn execute(cmd: BlockchainCmd) -> Result<String, String> {
 	match cmd {
 		BlockchainCmd::Import(import_cmd) => execute_import(import_cmd),
 		BlockchainCmd::Export(export_cmd) => execute_export(export_cmd),
 		BlockchainCmd::ExportState(export_cmd) => execute_export_state(export_cmd),
+		BlockchainCmd::RebuildTraces(rebuild_cmd) => execute_rebuild_traces(rebuild_cmd),
 	}
 }
 
@@ -473,6 +489,80 @@ fn execute_export_state(cmd: ExportState) -> Result<String, String> {
 	Ok("Export completed.".into())
 }
 
+fn execute_rebuild_traces(cmd: RebuildTraces) -> Result<String, String> {
+	let timer = Instant::now();
+
+	// Setup panic handler
+	let panic_handler = PanicHandler::new_in_arc();
+
+	// create dirs used by parity
+	try!(cmd.dirs.create_dirs(false, false));
+
+	// load spec file
+	let spec = try!(cmd.spec.spec());
+
+	// load genesis hash
+	let genesis_hash = spec.genesis_header().hash();
+
+	// database paths
+	let db_dirs = cmd.dirs.database(genesis_hash, spec.fork_name.clone());
+
+	// user defaults path
+	let user_defaults_path = db_dirs.user_defaults_path();
+
+	// load user defaults
+	let mut user_defaults = try!(UserDefaults::load(&user_defaults_path));
+
+	fdlimit::raise_fd_limit();
+
+	// select pruning algorithm
+	let algorithm = cmd.pruning.to_algorithm(&user_defaults);
+
+	// check if fatdb is on
+	let fat_db = try!(fatdb_switch_to_bool(cmd.fat_db, &user_defaults, algorithm));
+
+	// prepare client and snapshot paths.
+	let client_path = db_dirs.client_path(algorithm);
+	let snapshot_path = db_dirs.snapshot_path();
+
+	// execute upgrades
+	try!(execute_upgrades(&db_dirs, algorithm, cmd.compaction.compaction_profile(db_dirs.fork_path().as_path())));
+
+	// prepare client config, tracing is always on, since that's what we are here for.
+	let client_config = to_client_config(&cmd.cache_config, Mode::Active, true, fat_db, cmd.compaction, cmd.wal, VMType::default(), "".into(), algorithm, cmd.pruning_history, true);
+
+	// build client
+	let service = try!(ClientService::start(
+		client_config,
+		&spec,
+		&client_path,
+		&snapshot_path,
+		&cmd.dirs.ipc_path(),
+		Arc::new(Miner::with_spec(&spec)),
+	).map_err(|e| format!("Client service error: {:?}", e)));
+
+	// free up the spec in memory.
+	drop(spec);
+
+	panic_handler.forward_from(&service);
+	let client = service.client();
+
+	let from = try!(client.block_number(cmd.from_block).ok_or("From block could not be found"));
+	let to = try!(client.block_number(cmd.to_block).ok_or("To block could not be found"));
+	let best = client.chain_info().best_block_number;
+
+	try!(client.rebuild_traces(from, to).map_err(|e| format!("Cannot rebuild traces: {}", e)));
+
+	// traces of the whole chain are there now, so tracing can be turned on without a resync.
+	if from <= 1 && to == best {
+		user_defaults.tracing = true;
+		try!(user_defaults.save(&user_defaults_path));
+	}
+
+	let ms = timer.elapsed().as_milliseconds();
+	Ok(format!("Traces of blocks #{} to #{} rebuilt in {} seconds.", from, to, ms / 1000))
+}
+
 #[cfg(test)]
 mod test {
 	use super::DataFormat;
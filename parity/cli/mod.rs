@@ -36,6 +36,8 @@ usage! {
 		cmd_ui: bool,
 		cmd_tools: bool,
 		cmd_hash: bool,
+		cmd_db: bool,
+		cmd_rebuild_traces: bool,
 
 		// Arguments
 		arg_pid_file: String,
@@ -161,6 +163,18 @@ usage! {
 		flag_ipc_apis: String = "web3,eth,net,parity,parity_accounts,traces,rpc",
 			or |c: &Config| otry!(c.ipc).apis.clone().map(|vec| vec.join(",")),
 
+		// WS
+		flag_no_ws: bool = false,
+			or |c: &Config| otry!(c.websockets).disable.clone(),
+		flag_ws_port: u16 = 8546u16,
+			or |c: &Config| otry!(c.websockets).port.clone(),
+		flag_ws_interface: String  = "local",
+			or |c: &Config| otry!(c.websockets).interface.clone(),
+		flag_ws_apis: String = "web3,eth,net,parity,traces,rpc",
+			or |c: &Config| otry!(c.websockets).apis.clone().map(|vec| vec.join(",")),
+		flag_ws_origins: String = "none",
+			or |c: &Config| otry!(c.websockets).origins.clone().map(|vec| vec.join(",")),
+
 		// DAPPS
 		flag_no_dapps: bool = false,
 			or |c: &Config| otry!(c.dapps).disable.clone(),
@@ -290,6 +304,7 @@ struct Config {
 	network: Option<Network>,
 	rpc: Option<Rpc>,
 	ipc: Option<Ipc>,
+	websockets: Option<Ws>,
 	dapps: Option<Dapps>,
 	mining: Option<Mining>,
 	footprint: Option<Footprint>,
@@ -362,6 +377,15 @@ struct Ipc {
 	apis: Option<Vec<String>>,
 }
 
+#[derive(Default, Debug, PartialEq, RustcDecodable)]
+struct Ws {
+	disable: Option<bool>,
+	port: Option<u16>,
+	interface: Option<String>,
+	apis: Option<Vec<String>>,
+	origins: Option<Vec<String>>,
+}
+
 #[derive(Default, Debug, PartialEq, RustcDecodable)]
 struct Dapps {
 	disable: Option<bool>,
@@ -512,6 +536,8 @@ mod tests {
 			cmd_ui: false,
 			cmd_tools: false,
 			cmd_hash: false,
+			cmd_db: false,
+			cmd_rebuild_traces: false,
 
 			// Arguments
 			arg_pid_file: "".into(),
@@ -571,6 +597,13 @@ mod tests {
 			flag_ipc_path: "$HOME/.parity/jsonrpc.ipc".into(),
 			flag_ipc_apis: "web3,eth,net,parity,parity_accounts,personal,traces,rpc".into(),
 
+			// WS
+			flag_no_ws: false,
+			flag_ws_port: 8546u16,
+			flag_ws_interface: "local".into(),
+			flag_ws_apis: "web3,eth,net,parity,traces,rpc".into(),
+			flag_ws_origins: "none".into(),
+
 			// DAPPS
 			flag_no_dapps: false,
 			flag_dapps_port: 8080u16,
@@ -744,6 +777,7 @@ mod tests {
 				path: None,
 				apis: Some(vec!["rpc".into(), "eth".into()]),
 			}),
+			websockets: None,
 			dapps: Some(Dapps {
 				disable: None,
 				port: Some(8080),
@@ -27,7 +27,7 @@ use ethcore::client::VMType;
 use ethcore::miner::{MinerOptions, Banning};
 use ethcore::verification::queue::VerifierSettings;
 
-use rpc::{IpcConfiguration, HttpConfiguration};
+use rpc::{IpcConfiguration, HttpConfiguration, WsConfiguration};
 use ethcore_rpc::NetworkSettings;
 use cache::CacheConfig;
 use helpers::{to_duration, to_mode, to_block_id, to_u256, to_pending_set, to_price, replace_home,
@@ -38,7 +38,7 @@ use dir::Directories;
 use dapps::Configuration as DappsConfiguration;
 use signer::{Configuration as SignerConfiguration};
 use run::RunCmd;
-use blockchain::{BlockchainCmd, ImportBlockchain, ExportBlockchain, ExportState, DataFormat};
+use blockchain::{BlockchainCmd, ImportBlockchain, ExportBlockchain, ExportState, RebuildTraces, DataFormat};
 use presale::ImportWallet;
 use account::{AccountCmd, NewAccount, ImportAccounts, ImportFromGethAccounts};
 use snapshot::{self, SnapshotCommand};
@@ -86,6 +86,7 @@ impl Configuration {
 		let logger_config = self.logger_config();
 		let http_conf = try!(self.http_config());
 		let ipc_conf = try!(self.ipc_config());
+		let ws_conf = try!(self.ws_config());
 		let net_conf = try!(self.net_config());
 		let network_id = self.network_id();
 		let cache_config = self.cache_config();
@@ -204,6 +205,20 @@ impl Configuration {
 			} else {
 				unreachable!();
 			}
+		} else if self.args.cmd_db && self.args.cmd_rebuild_traces {
+			let rebuild_cmd = RebuildTraces {
+				spec: spec,
+				cache_config: cache_config,
+				dirs: dirs,
+				pruning: pruning,
+				pruning_history: pruning_history,
+				compaction: compaction,
+				wal: wal,
+				fat_db: fat_db,
+				from_block: try!(to_block_id(&self.args.flag_from)),
+				to_block: try!(to_block_id(&self.args.flag_to)),
+			};
+			Cmd::Blockchain(BlockchainCmd::RebuildTraces(rebuild_cmd))
 		} else if self.args.cmd_snapshot {
 			let snapshot_cmd = SnapshotCommand {
 				cache_config: cache_config,
@@ -256,6 +271,7 @@ impl Configuration {
 				miner_options: miner_options,
 				http_conf: http_conf,
 				ipc_conf: ipc_conf,
+				ws_conf: ws_conf,
 				net_conf: net_conf,
 				network_id: network_id,
 				acc_conf: try!(self.accounts_config()),
@@ -578,6 +594,16 @@ impl Configuration {
 		Some(hosts)
 	}
 
+	fn ws_origins(&self) -> Option<Vec<String>> {
+		match self.args.flag_ws_origins.as_ref() {
+			"none" => return Some(Vec::new()),
+			"all" => return None,
+			_ => {}
+		}
+		let origins = self.args.flag_ws_origins.split(',').map(|o| o.into()).collect();
+		Some(origins)
+	}
+
 	fn ipc_config(&self) -> Result<IpcConfiguration, String> {
 		let conf = IpcConfiguration {
 			enabled: !(self.args.flag_ipcdisable || self.args.flag_ipc_off || self.args.flag_no_ipc),
@@ -610,6 +636,18 @@ impl Configuration {
 		Ok(conf)
 	}
 
+	fn ws_config(&self) -> Result<WsConfiguration, String> {
+		let conf = WsConfiguration {
+			enabled: !self.args.flag_no_ws,
+			interface: self.ws_interface(),
+			port: self.args.flag_ws_port,
+			apis: try!(self.args.flag_ws_apis.parse()),
+			origins: self.ws_origins(),
+		};
+
+		Ok(conf)
+	}
+
 	fn network_settings(&self) -> NetworkSettings {
 		NetworkSettings {
 			name: self.args.flag_identity.clone(),
@@ -691,6 +729,14 @@ impl Configuration {
 		}.into()
 	}
 
+	fn ws_interface(&self) -> String {
+		match self.args.flag_ws_interface.as_str() {
+			"all" => "0.0.0.0",
+			"local" => "127.0.0.1",
+			x => x,
+		}.into()
+	}
+
 	fn dapps_interface(&self) -> String {
 		match self.args.flag_dapps_interface.as_str() {
 			"local" => "127.0.0.1",
@@ -730,12 +776,13 @@ mod tests {
 	use super::*;
 	use cli::Args;
 	use ethcore_rpc::NetworkSettings;
+	use rpc::WsConfiguration;
 	use ethcore::client::{VMType, BlockId};
 	use ethcore::miner::{MinerOptions, PrioritizationStrategy};
 	use helpers::{replace_home, default_network_config};
 	use run::RunCmd;
 	use signer::{Configuration as SignerConfiguration};
-	use blockchain::{BlockchainCmd, ImportBlockchain, ExportBlockchain, DataFormat, ExportState};
+	use blockchain::{BlockchainCmd, ImportBlockchain, ExportBlockchain, DataFormat, ExportState, RebuildTraces};
 	use presale::ImportWallet;
 	use account::{AccountCmd, NewAccount, ImportAccounts};
 	use devtools::{RandomTempPath};
@@ -869,6 +916,24 @@ mod tests {
 		})));
 	}
 
+	#[test]
+	fn test_command_db_rebuild_traces() {
+		let args = vec!["parity", "db", "rebuild-traces", "--from", "100", "--to", "200"];
+		let conf = parse(&args);
+		assert_eq!(conf.into_command().unwrap().cmd, Cmd::Blockchain(BlockchainCmd::RebuildTraces(RebuildTraces {
+			spec: Default::default(),
+			cache_config: Default::default(),
+			dirs: Default::default(),
+			pruning: Default::default(),
+			pruning_history: 64,
+			compaction: Default::default(),
+			wal: true,
+			fat_db: Default::default(),
+			from_block: BlockId::Number(100),
+			to_block: BlockId::Number(200),
+		})));
+	}
+
 	#[test]
 	fn test_command_blockchain_export_with_custom_format() {
 		let args = vec!["parity", "export", "blocks", "--format", "hex", "blockchain.json"];
@@ -920,6 +985,7 @@ mod tests {
 			miner_options: Default::default(),
 			http_conf: Default::default(),
 			ipc_conf: Default::default(),
+			ws_conf: Default::default(),
 			net_conf: default_network_config(),
 			network_id: None,
 			warp_sync: false,
@@ -1035,6 +1101,42 @@ mod tests {
 		assert_eq!(conf3.rpc_hosts(), Some(vec!["ethcore.io".into(), "something.io".into()]));
 	}
 
+	#[test]
+	fn should_parse_ws_config() {
+		// given
+
+		// when
+		let conf0 = parse(&["parity"]);
+		let conf1 = parse(&["parity", "--no-ws"]);
+		let conf2 = parse(&["parity", "--ws-interface", "all", "--ws-port", "8547", "--ws-apis", "eth,net"]);
+
+		// then
+		assert_eq!(conf0.ws_config().unwrap(), Default::default());
+		assert_eq!(conf1.ws_config().unwrap().enabled, false);
+		assert_eq!(conf2.ws_config().unwrap(), WsConfiguration {
+			enabled: true,
+			interface: "0.0.0.0".into(),
+			port: 8547,
+			apis: "eth,net".parse().unwrap(),
+			origins: Some(Vec::new()),
+		});
+	}
+
+	#[test]
+	fn should_parse_ws_origins() {
+		// given
+
+		// when
+		let conf0 = parse(&["parity"]);
+		let conf1 = parse(&["parity", "--ws-origins", "all"]);
+		let conf2 = parse(&["parity", "--ws-origins", "http://localhost:3000,chrome-extension://abc"]);
+
+		// then
+		assert_eq!(conf0.ws_config().unwrap().origins, Some(Vec::new()));
+		assert_eq!(conf1.ws_config().unwrap().origins, None);
+		assert_eq!(conf2.ws_config().unwrap().origins, Some(vec!["http://localhost:3000".into(), "chrome-extension://abc".into()]));
+	}
+
 	#[test]
 	fn should_parse_dapps_hosts() {
 		// given
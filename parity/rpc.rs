@@ -19,12 +19,12 @@ use std::sync::Arc;
 use std::net::SocketAddr;
 use std::io;
 use io::PanicHandler;
-use ethcore_rpc::{RpcServerError, RpcServer as Server, IpcServerError};
+use ethcore_rpc::{RpcServerError, RpcServer as Server, IpcServerError, WsServerError, SubscriptionManager};
 use rpc_apis;
 use rpc_apis::ApiSet;
 use helpers::parity_ipc_path;
 
-pub use ethcore_rpc::{IpcServer, Server as HttpServer};
+pub use ethcore_rpc::{IpcServer, WsServer, Server as HttpServer};
 
 #[derive(Debug, PartialEq)]
 pub struct HttpConfiguration {
@@ -76,9 +76,31 @@ impl fmt::Display for IpcConfiguration {
 	}
 }
 
+#[derive(Debug, PartialEq)]
+pub struct WsConfiguration {
+	pub enabled: bool,
+	pub interface: String,
+	pub port: u16,
+	pub apis: ApiSet,
+	pub origins: Option<Vec<String>>,
+}
+
+impl Default for WsConfiguration {
+	fn default() -> Self {
+		WsConfiguration {
+			enabled: true,
+			interface: "127.0.0.1".into(),
+			port: 8546,
+			apis: ApiSet::UnsafeContext,
+			origins: Some(Vec::new()),
+		}
+	}
+}
+
 pub struct Dependencies {
 	pub panic_handler: Arc<PanicHandler>,
 	pub apis: Arc<rpc_apis::Dependencies>,
+	pub subscriptions: Arc<SubscriptionManager>,
 }
 
 pub fn new_http(conf: HttpConfiguration, deps: &Dependencies) -> Result<Option<HttpServer>, String> {
@@ -129,3 +151,28 @@ pub fn setup_ipc_rpc_server(dependencies: &Dependencies, addr: &str, apis: ApiSe
 		Ok(server) => Ok(server)
 	}
 }
+
+pub fn new_ws(conf: WsConfiguration, deps: &Dependencies) -> Result<Option<WsServer>, String> {
+	if !conf.enabled {
+		return Ok(None);
+	}
+
+	let url = format!("{}:{}", conf.interface, conf.port);
+	let addr = try!(url.parse().map_err(|_| format!("Invalid WebSockets listen host/port given: {}", url)));
+	Ok(Some(try!(setup_ws_rpc_server(deps, &addr, conf.origins, conf.apis))))
+}
+
+pub fn setup_ws_rpc_server(
+	dependencies: &Dependencies,
+	url: &SocketAddr,
+	allowed_origins: Option<Vec<String>>,
+	apis: ApiSet
+) -> Result<WsServer, String> {
+	let server = try!(setup_rpc_server(apis, dependencies));
+	let ph = dependencies.panic_handler.clone();
+	match server.start_ws(url, allowed_origins, dependencies.subscriptions.clone(), ph) {
+		Err(WsServerError::IoError(err)) => Err(format!("WebSockets io error: {}", err)),
+		Err(any_error) => Err(format!("WebSockets error: {:?}", any_error)),
+		Ok(server) => Ok(server),
+	}
+}
@@ -49,6 +49,8 @@ pub enum Api {
 	ParitySet,
 	/// Traces (Safe)
 	Traces,
+	/// Geth-compatible "debug" API (Safe, but expensive)
+	Debug,
 	/// Rpc (Safe)
 	Rpc,
 }
@@ -69,6 +71,7 @@ impl FromStr for Api {
 			"parity_accounts" => Ok(ParityAccounts),
 			"parity_set" => Ok(ParitySet),
 			"traces" => Ok(Traces),
+			"debug" => Ok(Debug),
 			"rpc" => Ok(Rpc),
 			api => Err(format!("Unknown api: {}", api))
 		}
@@ -136,6 +139,7 @@ fn to_modules(apis: &[Api]) -> BTreeMap<String, String> {
 			Api::ParityAccounts => ("parity_accounts", "1.0"),
 			Api::ParitySet => ("parity_set", "1.0"),
 			Api::Traces => ("traces", "1.0"),
+			Api::Debug => ("debug", "1.0"),
 			Api::Rpc => ("rpc", "1.0"),
 		};
 		modules.insert(name.into(), version.into());
@@ -245,6 +249,9 @@ pub fn setup_rpc<T: Extendable>(server: T, deps: Arc<Dependencies>, apis: ApiSet
 			Api::Traces => {
 				server.add_delegate(TracesClient::new(&deps.client, &deps.miner).to_delegate())
 			},
+			Api::Debug => {
+				server.add_delegate(DebugClient::new(&deps.client, &deps.miner).to_delegate())
+			},
 			Api::Rpc => {
 				let modules = to_modules(&apis);
 				server.add_delegate(RpcClient::new(modules).to_delegate());
@@ -269,6 +276,7 @@ mod test {
 		assert_eq!(Api::ParityAccounts, "parity_accounts".parse().unwrap());
 		assert_eq!(Api::ParitySet, "parity_set".parse().unwrap());
 		assert_eq!(Api::Traces, "traces".parse().unwrap());
+		assert_eq!(Api::Debug, "debug".parse().unwrap());
 		assert_eq!(Api::Rpc, "rpc".parse().unwrap());
 		assert!("rp".parse::<Api>().is_err());
 	}
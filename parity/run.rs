@@ -19,6 +19,7 @@ use std::net::{TcpListener};
 use ctrlc::CtrlC;
 use fdlimit::raise_fd_limit;
 use ethcore_rpc::{NetworkSettings, is_major_importing};
+use ethcore_rpc::v1::EthPubSubHandler;
 use ethsync::NetworkConfiguration;
 use util::{Colour, version, RotatingLogger};
 use io::{MayPanic, ForwardPanic, PanicHandler};
@@ -32,7 +33,7 @@ use ethcore::verification::queue::VerifierSettings;
 use ethsync::SyncConfig;
 use informant::Informant;
 
-use rpc::{HttpServer, IpcServer, HttpConfiguration, IpcConfiguration};
+use rpc::{HttpServer, IpcServer, WsServer, HttpConfiguration, IpcConfiguration, WsConfiguration};
 use signer::SignerServer;
 use dapps::WebappServer;
 use io_handler::ClientIoHandler;
@@ -70,6 +71,7 @@ pub struct RunCmd {
 	pub miner_options: MinerOptions,
 	pub http_conf: HttpConfiguration,
 	pub ipc_conf: IpcConfiguration,
+	pub ws_conf: WsConfiguration,
 	pub net_conf: NetworkConfiguration,
 	pub network_id: Option<u64>,
 	pub warp_sync: bool,
@@ -333,14 +335,22 @@ pub fn execute(cmd: RunCmd, logger: Arc<RotatingLogger>) -> Result<(), String> {
 		},
 	});
 
+	// notify subscribers about new blocks and transactions imported to the queue
+	let pubsub = Arc::new(EthPubSubHandler::new(&client, &sync_provider));
+	service.add_notify(pubsub.clone());
+	let pending_pubsub = pubsub.clone();
+	miner.add_transactions_listener(Box::new(move |hashes| pending_pubsub.notify_pending_transactions(hashes)));
+
 	let dependencies = rpc::Dependencies {
 		panic_handler: panic_handler.clone(),
 		apis: deps_for_rpc_apis.clone(),
+		subscriptions: pubsub,
 	};
 
 	// start rpc servers
 	let http_server = try!(rpc::new_http(cmd.http_conf, &dependencies));
 	let ipc_server = try!(rpc::new_ipc(cmd.ipc_conf, &dependencies));
+	let ws_server = try!(rpc::new_ws(cmd.ws_conf, &dependencies));
 
 	let dapps_deps = dapps::Dependencies {
 		panic_handler: panic_handler.clone(),
@@ -418,7 +428,7 @@ pub fn execute(cmd: RunCmd, logger: Arc<RotatingLogger>) -> Result<(), String> {
 	}
 
 	// Handle exit
-	wait_for_exit(panic_handler, http_server, ipc_server, dapps_server, signer_server);
+	wait_for_exit(panic_handler, http_server, ipc_server, ws_server, dapps_server, signer_server);
 
 	// to make sure timer does not spawn requests while shutdown is in progress
 	io_handler.shutdown.store(true, ::std::sync::atomic::Ordering::SeqCst);
@@ -471,6 +481,7 @@ fn wait_for_exit(
 	panic_handler: Arc<PanicHandler>,
 	_http_server: Option<HttpServer>,
 	_ipc_server: Option<IpcServer>,
+	_ws_server: Option<WsServer>,
 	_dapps_server: Option<WebappServer>,
 	_signer_server: Option<SignerServer>
 	) {
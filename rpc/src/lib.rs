@@ -37,6 +37,7 @@ extern crate ethcore_ipc;
 extern crate time;
 extern crate rlp;
 extern crate fetch;
+extern crate ws;
 
 #[macro_use]
 extern crate log;
@@ -55,8 +56,11 @@ use jsonrpc_core::{IoHandler, IoDelegate};
 
 pub use ipc::{Server as IpcServer, Error as IpcServerError};
 pub use jsonrpc_http_server::{ServerBuilder, Server, RpcServerError};
+pub use ws_server::{Server as WsServer, ServerError as WsServerError};
 pub mod v1;
+mod ws_server;
 pub use v1::{SigningQueue, SignerService, ConfirmationsQueue, NetworkSettings};
+pub use v1::pubsub::SubscriptionManager;
 pub use v1::block_import::is_major_importing;
 
 /// An object that can be extended with `IoDelegates`
@@ -119,4 +123,16 @@ impl RpcServer {
 		try!(server.run_async());
 		Ok(server)
 	}
+
+	/// Start `WebSockets` server asynchronously and returns result with `WsServer` handle on success or an error.
+	/// Subscription methods are handled by connection-bound handlers created by `subscriptions`.
+	pub fn start_ws(
+		&self,
+		addr: &SocketAddr,
+		allowed_origins: Option<Vec<String>>,
+		subscriptions: Arc<SubscriptionManager>,
+		panic_handler: Arc<PanicHandler>,
+		) -> Result<WsServer, WsServerError> {
+		WsServer::start(addr, allowed_origins, self.handler.clone(), subscriptions, panic_handler)
+	}
 }
@@ -297,6 +297,52 @@ macro_rules! wrap_with_trailing {
 	}
 }
 
+// special impl for a single parameter followed by two default trailing parameters.
+impl<B, OUT, A, T1, T2> Wrap<B> for fn(&B, A, Trailing<T1>, Trailing<T2>) -> Result<OUT, Error>
+	where B: Send + Sync + 'static, OUT: Serialize, A: Deserialize, T1: Default + Deserialize, T2: Default + Deserialize
+{
+	fn wrap_rpc(&self, base: &B, params: Params) -> Result<Value, Error> {
+		let len = match params {
+			Params::Array(ref v) => v.len(),
+			Params::None => 0,
+			_ => return Err(errors::invalid_params("not an array", "")),
+		};
+
+		let (a, t1, t2) = match len {
+			1 => try!(from_params::<(A,)>(params).map(|(a,)| (a, T1::default(), T2::default()))),
+			2 => try!(from_params::<(A, T1)>(params).map(|(a, t1)| (a, t1, T2::default()))),
+			3 => try!(from_params::<(A, T1, T2)>(params)),
+			_ => return Err(Error::invalid_params()),
+		};
+
+		(self)(base, a, Trailing(t1), Trailing(t2)).map(to_value)
+	}
+}
+
+impl<B, OUT, A, T1, T2> WrapAsync<B> for fn(&B, Ready<OUT>, A, Trailing<T1>, Trailing<T2>)
+	where B: Send + Sync + 'static, OUT: Serialize, A: Deserialize, T1: Default + Deserialize, T2: Default + Deserialize
+{
+	fn wrap_rpc(&self, base: &B, params: Params, ready: ::jsonrpc_core::Ready) {
+		let len = match params {
+			Params::Array(ref v) => v.len(),
+			Params::None => 0,
+			_ => return ready.ready(Err(errors::invalid_params("not an array", ""))),
+		};
+
+		let params = match len {
+			1 => from_params::<(A,)>(params).map(|(a,)| (a, T1::default(), T2::default())),
+			2 => from_params::<(A, T1)>(params).map(|(a, t1)| (a, t1, T2::default())),
+			3 => from_params::<(A, T1, T2)>(params),
+			_ => Err(Error::invalid_params()),
+		};
+
+		match params {
+			Ok((a, t1, t2)) => (self)(base, ready.into(), a, Trailing(t1), Trailing(t2)),
+			Err(e) => ready.ready(Err(e)),
+		}
+	}
+}
+
 wrap!(A, B, C, D, E);
 wrap!(A, B, C, D);
 wrap!(A, B, C);
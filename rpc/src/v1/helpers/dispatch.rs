@@ -20,12 +20,12 @@ use util::bytes::ToPretty;
 
 use ethkey::Signature;
 use ethcore::miner::MinerService;
-use ethcore::client::MiningBlockChainClient;
+use ethcore::client::{BlockChainClient, MiningBlockChainClient};
 use ethcore::transaction::{Action, SignedTransaction, Transaction};
 use ethcore::account_provider::AccountProvider;
 
 use jsonrpc_core::Error;
-use v1::helpers::{errors, TransactionRequest, FilledTransactionRequest, ConfirmationPayload};
+use v1::helpers::{errors, TransactionRequest, FilledTransactionRequest, ConfirmationPayload, CallRequest};
 use v1::types::{
 	H256 as RpcH256, H520 as RpcH520, Bytes as RpcBytes,
 	RichRawTransaction as RpcRichRawTransaction,
@@ -33,6 +33,7 @@ use v1::types::{
 	ConfirmationResponse,
 	SignRequest as RpcSignRequest,
 	DecryptRequest as RpcDecryptRequest,
+	SignTypedDataRequest as RpcSignTypedDataRequest,
 };
 
 pub const DEFAULT_MAC: [u8; 2] = [0, 0];
@@ -61,6 +62,11 @@ pub fn execute<C, M>(client: &C, miner: &M, accounts: &AccountProvider, payload:
 				.map(RpcBytes)
 				.map(ConfirmationResponse::Decrypt)
 		},
+		ConfirmationPayload::SignTypedData(address, hash, _) => {
+			signature(accounts, address, hash, pass)
+				.map(RpcH520::from)
+				.map(ConfirmationResponse::Signature)
+		},
 	}
 }
 
@@ -141,11 +147,26 @@ pub fn fill_optional_fields<C, M>(request: TransactionRequest, client: &C, miner
 }
 
 pub fn default_gas_price<C, M>(client: &C, miner: &M) -> U256
-	where C: MiningBlockChainClient, M: MinerService
+	where C: BlockChainClient, M: MinerService
 {
 	client.gas_price_median(100).unwrap_or_else(|| miner.sensible_gas_price())
 }
 
+/// Fill in the missing fields of a call request and sign it with a fake signature of its sender.
+pub fn sign_call<C, M>(client: &C, miner: &M, request: CallRequest) -> SignedTransaction
+	where C: BlockChainClient, M: MinerService
+{
+	let from = request.from.unwrap_or(Address::zero());
+	Transaction {
+		nonce: request.nonce.unwrap_or_else(|| client.latest_nonce(&from)),
+		action: request.to.map_or(Action::Create, Action::Call),
+		gas: request.gas.unwrap_or(U256::from(50_000_000)),
+		gas_price: request.gas_price.unwrap_or_else(|| default_gas_price(client, miner)),
+		value: request.value.unwrap_or_else(U256::zero),
+		data: request.data.unwrap_or_else(Vec::new),
+	}.fake_sign(from)
+}
+
 pub fn from_rpc<C, M>(payload: RpcConfirmationPayload, client: &C, miner: &M) -> ConfirmationPayload
 	where C: MiningBlockChainClient, M: MinerService {
 
@@ -162,5 +183,8 @@ pub fn from_rpc<C, M>(payload: RpcConfirmationPayload, client: &C, miner: &M) ->
 		RpcConfirmationPayload::Signature(RpcSignRequest { address, hash }) => {
 			ConfirmationPayload::Signature(address.into(), hash.into())
 		},
+		RpcConfirmationPayload::SignTypedData(RpcSignTypedDataRequest { address, hash, data }) => {
+			ConfirmationPayload::SignTypedData(address.into(), hash.into(), data)
+		},
 	}
 }
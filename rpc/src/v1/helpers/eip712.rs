@@ -0,0 +1,405 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! EIP-712 typed structured data hashing.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+use util::{Address, H256, U256, Uint, Hashable};
+use util::common::FromHex;
+
+use jsonrpc_core::Error;
+use v1::helpers::errors;
+use v1::types::{TypedData, TypedValue, MemberType};
+
+const DOMAIN_TYPE: &'static str = "EIP712Domain";
+
+/// Validates typed data and computes the hash to sign:
+/// `keccak256("\x19\x01" ‖ hashStruct(domain) ‖ hashStruct(message))`.
+pub fn hash(data: &TypedData) -> Result<H256, Error> {
+	let encoder = Encoder { types: &data.types };
+	try!(encoder.check_types());
+	if !data.types.contains_key(DOMAIN_TYPE) {
+		return Err(error(format!("missing {} type", DOMAIN_TYPE)));
+	}
+
+	let domain = try!(encoder.hash_struct(DOMAIN_TYPE, &data.domain));
+	let message = try!(encoder.hash_struct(&data.primary_type, &data.message));
+
+	let mut bytes = vec![0x19, 0x01];
+	bytes.extend_from_slice(&*domain);
+	bytes.extend_from_slice(&*message);
+	Ok(bytes.sha3())
+}
+
+fn error(details: String) -> Error {
+	errors::invalid_params("Invalid typed data", details)
+}
+
+/// Strips array suffixes, e.g. `Person[][2]` becomes `Person`.
+fn base_type(kind: &str) -> &str {
+	match kind.find('[') {
+		Some(index) => &kind[..index],
+		None => kind,
+	}
+}
+
+/// Splits an array type `T[]` or `T[n]` into the element type and the fixed length, if any.
+fn array_type(kind: &str) -> Result<Option<(&str, Option<usize>)>, Error> {
+	if !kind.ends_with(']') {
+		return Ok(None);
+	}
+
+	let open = try!(kind.rfind('[').ok_or_else(|| error(format!("invalid type {}", kind))));
+	let len = match &kind[open + 1..kind.len() - 1] {
+		"" => None,
+		len => Some(try!(len.parse::<usize>().map_err(|_| error(format!("invalid array length in type {}", kind))))),
+	};
+	Ok(Some((&kind[..open], len)))
+}
+
+/// Size in bits of `intN` and `uintN` types.
+fn int_bits(kind: &str) -> Option<usize> {
+	let size = if kind.starts_with("uint") {
+		&kind[4..]
+	} else if kind.starts_with("int") {
+		&kind[3..]
+	} else {
+		return None;
+	};
+
+	match size {
+		"" => Some(256),
+		size => size.parse::<usize>().ok().and_then(|n| match n > 0 && n <= 256 && n % 8 == 0 {
+			true => Some(n),
+			false => None,
+		}),
+	}
+}
+
+/// Length of `bytesN` types.
+fn fixed_bytes_len(kind: &str) -> Option<usize> {
+	if !kind.starts_with("bytes") {
+		return None;
+	}
+
+	kind[5..].parse::<usize>().ok().and_then(|n| match n > 0 && n <= 32 {
+		true => Some(n),
+		false => None,
+	})
+}
+
+fn is_atomic(kind: &str) -> bool {
+	match kind {
+		"bool" | "address" | "string" | "bytes" => true,
+		kind => int_bits(kind).is_some() || fixed_bytes_len(kind).is_some(),
+	}
+}
+
+fn parse_hex(kind: &str, value: &TypedValue) -> Result<Vec<u8>, Error> {
+	match *value {
+		TypedValue::String(ref s) if s.starts_with("0x") => {
+			FromHex::from_hex(&s[2..]).map_err(|_| error(format!("invalid hex value for type {}", kind)))
+		},
+		_ => Err(error(format!("expected hex string for type {}", kind))),
+	}
+}
+
+/// Parses an integer given as a JSON number or a decimal or hex string.
+/// Returns the sign and the magnitude.
+fn parse_integer(kind: &str, value: &TypedValue) -> Result<(bool, U256), Error> {
+	let invalid = || error(format!("invalid integer value for type {}", kind));
+	match *value {
+		TypedValue::U64(u) => Ok((false, U256::from(u))),
+		TypedValue::I64(i) if i >= 0 => Ok((false, U256::from(i as u64))),
+		TypedValue::I64(i) => Ok((true, U256::from(!(i as u64)) + U256::one())),
+		TypedValue::String(ref s) => {
+			let (negative, s) = match s.starts_with('-') {
+				true => (true, &s[1..]),
+				false => (false, &s[..]),
+			};
+			let magnitude = if s.starts_with("0x") {
+				let hex = &s[2..];
+				if hex.is_empty() || hex.len() > 64 {
+					return Err(invalid());
+				}
+				try!(U256::from_str(hex).map_err(|_| invalid()))
+			} else {
+				if s.is_empty() {
+					return Err(invalid());
+				}
+				try!(U256::from_dec_str(s).map_err(|_| invalid()))
+			};
+			Ok((negative, magnitude))
+		},
+		_ => Err(invalid()),
+	}
+}
+
+fn encode_integer(kind: &str, bits: usize, value: &TypedValue) -> Result<H256, Error> {
+	let (negative, magnitude) = try!(parse_integer(kind, value));
+	let out_of_range = || error(format!("value out of range for type {}", kind));
+
+	if kind.starts_with("uint") {
+		if negative || magnitude.bits() > bits {
+			return Err(out_of_range());
+		}
+		return Ok(magnitude.into());
+	}
+
+	// signed values lie in [-2^(bits-1), 2^(bits-1))
+	let limit = U256::one() << (bits - 1);
+	if (!negative && magnitude >= limit) || (negative && magnitude > limit) {
+		return Err(out_of_range());
+	}
+
+	match negative {
+		true => Ok((!magnitude).overflowing_add(U256::one()).0.into()),
+		false => Ok(magnitude.into()),
+	}
+}
+
+struct Encoder<'a> {
+	types: &'a BTreeMap<String, Vec<MemberType>>,
+}
+
+impl<'a> Encoder<'a> {
+	/// Makes sure every member refers to an atomic, dynamic or declared struct type.
+	fn check_types(&self) -> Result<(), Error> {
+		for (name, members) in self.types {
+			for member in members {
+				let mut kind = &member.kind[..];
+				while let Some((inner, _)) = try!(array_type(kind)) {
+					kind = inner;
+				}
+				if !is_atomic(kind) && !self.types.contains_key(kind) {
+					return Err(error(format!("unknown type {} of {}.{}", member.kind, name, member.name)));
+				}
+			}
+		}
+		Ok(())
+	}
+
+	fn members(&self, kind: &str) -> Result<&'a Vec<MemberType>, Error> {
+		self.types.get(kind).ok_or_else(|| error(format!("unknown type {}", kind)))
+	}
+
+	/// Collects the struct types referenced by `kind`, including itself.
+	fn dependencies(&self, kind: &str, found: &mut BTreeSet<String>) {
+		if found.contains(kind) {
+			return;
+		}
+
+		if let Some(members) = self.types.get(kind) {
+			found.insert(kind.to_owned());
+			for member in members {
+				self.dependencies(base_type(&member.kind), found);
+			}
+		}
+	}
+
+	/// Encodes the struct type followed by its dependencies sorted by name,
+	/// e.g. `Mail(Person from,Person to,string contents)Person(string name,address wallet)`.
+	fn encode_type(&self, kind: &str) -> Result<String, Error> {
+		let mut deps = BTreeSet::new();
+		self.dependencies(kind, &mut deps);
+		deps.remove(kind);
+
+		let mut encoded = String::new();
+		for name in Some(kind).into_iter().chain(deps.iter().map(|d| &d[..])) {
+			let members = try!(self.members(name));
+			let members = members.iter().map(|m| format!("{} {}", m.kind, m.name)).collect::<Vec<_>>();
+			encoded.push_str(&format!("{}({})", name, members.join(",")));
+		}
+		Ok(encoded)
+	}
+
+	fn hash_struct(&self, kind: &str, value: &TypedValue) -> Result<H256, Error> {
+		let members = try!(self.members(kind));
+		let fields = match *value {
+			TypedValue::Object(ref fields) => fields,
+			_ => return Err(error(format!("expected object for type {}", kind))),
+		};
+
+		if let Some(name) = fields.keys().find(|name| !members.iter().any(|m| &m.name == *name)) {
+			return Err(error(format!("unknown field {} of type {}", name, kind)));
+		}
+
+		let mut encoded = try!(self.encode_type(kind)).as_bytes().sha3().to_vec();
+		for member in members {
+			let value = try!(fields.get(&member.name).ok_or_else(|| error(format!("missing field {} of type {}", member.name, kind))));
+			encoded.extend_from_slice(&*try!(self.encode_value(&member.kind, value)));
+		}
+		Ok(encoded.sha3())
+	}
+
+	fn encode_value(&self, kind: &str, value: &TypedValue) -> Result<H256, Error> {
+		if let Some((inner, len)) = try!(array_type(kind)) {
+			let items = match *value {
+				TypedValue::Array(ref items) => items,
+				_ => return Err(error(format!("expected array for type {}", kind))),
+			};
+			if len.map_or(false, |len| len != items.len()) {
+				return Err(error(format!("invalid array length for type {}", kind)));
+			}
+
+			let mut encoded = Vec::with_capacity(items.len() * 32);
+			for item in items {
+				encoded.extend_from_slice(&*try!(self.encode_value(inner, item)));
+			}
+			return Ok(encoded.sha3());
+		}
+
+		if self.types.contains_key(kind) {
+			return self.hash_struct(kind, value);
+		}
+
+		match (kind, value) {
+			("bool", &TypedValue::Bool(b)) => Ok(H256::from(b as u64)),
+			("string", &TypedValue::String(ref s)) => Ok(s.as_bytes().sha3()),
+			("bytes", value) => Ok(try!(parse_hex(kind, value)).sha3()),
+			("address", value) => {
+				let address = match *value {
+					TypedValue::String(ref s) if s.starts_with("0x") => Address::from_str(&s[2..]).ok(),
+					_ => None,
+				};
+				address.map(H256::from).ok_or_else(|| error(format!("invalid address for type {}", kind)))
+			},
+			(kind, value) => {
+				if let Some(bits) = int_bits(kind) {
+					return encode_integer(kind, bits, value);
+				}
+
+				if let Some(len) = fixed_bytes_len(kind) {
+					let bytes = try!(parse_hex(kind, value));
+					if bytes.len() > len {
+						return Err(error(format!("value too long for type {}", kind)));
+					}
+					let mut encoded = H256::new();
+					encoded[..bytes.len()].copy_from_slice(&bytes);
+					return Ok(encoded);
+				}
+
+				Err(error(format!("invalid value for type {}", kind)))
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::str::FromStr;
+	use serde_json;
+	use util::H256;
+	use v1::types::TypedData;
+	use super::{hash, Encoder};
+
+	const MAIL: &'static str = r#"{
+		"types": {
+			"EIP712Domain": [
+				{"name": "name", "type": "string"},
+				{"name": "version", "type": "string"},
+				{"name": "chainId", "type": "uint256"},
+				{"name": "verifyingContract", "type": "address"}
+			],
+			"Person": [
+				{"name": "name", "type": "string"},
+				{"name": "wallet", "type": "address"}
+			],
+			"Mail": [
+				{"name": "from", "type": "Person"},
+				{"name": "to", "type": "Person"},
+				{"name": "contents", "type": "string"}
+			]
+		},
+		"primaryType": "Mail",
+		"domain": {
+			"name": "Ether Mail",
+			"version": "1",
+			"chainId": 1,
+			"verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+		},
+		"message": {
+			"from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+			"to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+			"contents": "Hello, Bob!"
+		}
+	}"#;
+
+	fn typed_data(s: &str) -> TypedData {
+		serde_json::from_str(s).unwrap()
+	}
+
+	#[test]
+	fn should_encode_type_with_dependencies() {
+		let data = typed_data(MAIL);
+		let encoder = Encoder { types: &data.types };
+
+		assert_eq!(
+			encoder.encode_type("Mail").unwrap(),
+			"Mail(Person from,Person to,string contents)Person(string name,address wallet)".to_owned()
+		);
+	}
+
+	#[test]
+	fn should_hash_mail_example() {
+		let data = typed_data(MAIL);
+
+		assert_eq!(
+			hash(&data).unwrap(),
+			H256::from_str("be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2").unwrap()
+		);
+	}
+
+	#[test]
+	fn should_reject_invalid_typed_data() {
+		// unknown field in message
+		let data = typed_data(&MAIL.replace(r#""contents": "Hello, Bob!""#, r#""contents": "Hello, Bob!", "cc": "Alice""#));
+		assert!(hash(&data).is_err());
+
+		// missing domain type
+		let data = typed_data(&MAIL.replace("EIP712Domain", "Domain"));
+		assert!(hash(&data).is_err());
+
+		// unknown member type
+		let data = typed_data(&MAIL.replace(r#""type": "Person"}"#, r#""type": "Animal"}"#));
+		assert!(hash(&data).is_err());
+
+		// invalid address
+		let data = typed_data(&MAIL.replace("0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB", "0xbBbB"));
+		assert!(hash(&data).is_err());
+	}
+
+	#[test]
+	fn should_check_integer_ranges() {
+		let data = |value: &str| typed_data(&format!(r#"{{
+			"types": {{
+				"EIP712Domain": [],
+				"Message": [{{"name": "value", "type": "int8"}}]
+			}},
+			"primaryType": "Message",
+			"domain": {{}},
+			"message": {{"value": {}}}
+		}}"#, value));
+
+		assert!(hash(&data("127")).is_ok());
+		assert!(hash(&data("-128")).is_ok());
+		assert!(hash(&data(r#""-0x80""#)).is_ok());
+		assert!(hash(&data("128")).is_err());
+		assert!(hash(&data("-129")).is_err());
+		assert!(hash(&data(r#""0xzz""#)).is_err());
+	}
+}
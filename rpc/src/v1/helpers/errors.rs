@@ -22,6 +22,7 @@ macro_rules! rpc_unimplemented {
 
 use std::fmt;
 use rlp::DecoderError;
+use util::U256;
 use ethcore::error::{Error as EthcoreError, CallError, TransactionError};
 use ethcore::account_provider::{Error as AccountError};
 use fetch::FetchError;
@@ -123,6 +124,14 @@ pub fn execution<T: fmt::Debug>(data: T) -> Error {
 	}
 }
 
+pub fn gas_estimation_failed(cap: U256) -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::EXECUTION_ERROR),
+		message: format!("Transaction execution fails even with the maximal gas limit of {}.", cap),
+		data: None,
+	}
+}
+
 pub fn state_pruned() -> Error {
 	Error {
 		code: ErrorCode::ServerError(codes::UNSUPPORTED_REQUEST),
@@ -131,6 +140,14 @@ pub fn state_pruned() -> Error {
 	}
 }
 
+pub fn transaction_not_found() -> Error {
+	Error {
+		code: ErrorCode::InvalidParams,
+		message: "Transaction not found.".into(),
+		data: None
+	}
+}
+
 pub fn no_work() -> Error {
 	Error {
 		code: ErrorCode::ServerError(codes::NO_WORK),
@@ -288,6 +305,7 @@ pub fn from_call_error(error: CallError) -> Error {
 	match error {
 		CallError::StatePruned => state_pruned(),
 		CallError::Execution(e) => execution(e),
+		CallError::GasEstimationFailed(cap) => gas_estimation_failed(cap),
 		CallError::TransactionNotFound => internal("{}, this should not be the case with eth_call, most likely a bug.", CallError::TransactionNotFound),
 	}
 }
@@ -21,8 +21,10 @@ pub mod auto_args;
 pub mod errors;
 
 pub mod dispatch;
+pub mod eip712;
 pub mod params;
 pub mod block_import;
+pub mod pubsub;
 
 mod poll_manager;
 mod poll_filter;
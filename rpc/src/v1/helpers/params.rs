@@ -36,6 +36,14 @@ pub fn params_len(params: &Params) -> usize {
 	}
 }
 
+/// Deserialize request parameters with optional second parameter `BlockNumber` defaulting to `BlockNumber::Latest`.
+pub fn from_params_default_second<F1>(params: Params) -> Result<(F1, BlockNumber, ), Error> where F1: serde::de::Deserialize {
+	match params_len(&params) {
+		1 => from_params::<(F1, )>(params).map(|(f1,)| (f1, BlockNumber::Latest)),
+		_ => from_params::<(F1, BlockNumber)>(params)
+	}
+}
+
 /// Deserialize request parameters with optional third parameter `BlockNumber` defaulting to `BlockNumber::Latest`.
 pub fn from_params_default_third<F1, F2>(params: Params) -> Result<(F1, F2, BlockNumber, ), Error> where F1: serde::de::Deserialize, F2: serde::de::Deserialize {
 	match params_len(&params) {
@@ -44,3 +52,13 @@ pub fn from_params_default_third<F1, F2>(params: Params) -> Result<(F1, F2, Bloc
 	}
 }
 
+/// Deserialize request parameters with optional third parameter `BlockNumber` defaulting to `BlockNumber::Latest`
+/// and optional fourth parameter defaulting to its default value.
+pub fn from_params_default_third_fourth<F1, F2, F4>(params: Params) -> Result<(F1, F2, BlockNumber, F4), Error>
+	where F1: serde::de::Deserialize, F2: serde::de::Deserialize, F4: serde::de::Deserialize + Default {
+	match params_len(&params) {
+		2 => from_params::<(F1, F2, )>(params).map(|(f1, f2)| (f1, f2, BlockNumber::Latest, F4::default())),
+		3 => from_params::<(F1, F2, BlockNumber)>(params).map(|(f1, f2, block)| (f1, f2, block, F4::default())),
+		_ => from_params::<(F1, F2, BlockNumber, F4)>(params)
+	}
+}
@@ -0,0 +1,117 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Transport-independent pub-sub primitives.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use jsonrpc_core::{IoHandler, Request, Call, MethodCall, Notification};
+use serde::Serialize;
+use serde_json::{self, Value};
+
+/// Identifies a single transport connection.
+pub type ConnectionId = usize;
+
+/// Pushes notifications to a connected client.
+pub trait Sink: Send + Sync {
+	/// Sends serialized notification to the client.
+	fn send(&self, notification: String);
+}
+
+/// Manages subscriptions made over long-lived connections.
+pub trait SubscriptionManager: Send + Sync {
+	/// Returns handler of the subscription methods bound to given connection.
+	fn connection_handler(&self, connection: ConnectionId, sink: Arc<Sink>) -> IoHandler;
+
+	/// Removes all subscriptions made by given connection.
+	fn drop_connection(&self, connection: ConnectionId);
+}
+
+/// Methods which have to be handled by the connection-bound handler.
+pub const SUBSCRIPTION_METHODS: &'static [&'static str] = &["eth_subscribe", "eth_unsubscribe"];
+
+/// Serializes `eth_subscription` notification for given subscription.
+pub fn notification<I: Serialize, T: Serialize>(subscription: &I, result: &T) -> String {
+	let mut params = BTreeMap::new();
+	params.insert("subscription".to_owned(), serde_json::to_value(subscription));
+	params.insert("result".to_owned(), serde_json::to_value(result));
+
+	let mut notification = BTreeMap::new();
+	notification.insert("jsonrpc".to_owned(), Value::String("2.0".to_owned()));
+	notification.insert("method".to_owned(), Value::String("eth_subscription".to_owned()));
+	notification.insert("params".to_owned(), Value::Object(params));
+
+	serde_json::to_string(&Value::Object(notification)).expect("Serialization of a json value cannot fail; qed")
+}
+
+/// Response to batches containing subscription methods, the connection-bound handler only handles single calls.
+pub const BATCHED_SUBSCRIPTION_ERROR: &'static str = r#"{"jsonrpc":"2.0","error":{"code":-32600,"message":"Subscription methods cannot be used in batch requests."},"id":null}"#;
+
+/// Describes which handler has to process a request received over a pub-sub transport.
+#[derive(Debug, PartialEq)]
+pub enum Route {
+	/// Single subscription call, handled by the connection-bound handler.
+	Subscription,
+	/// Batch containing a subscription call, which is rejected.
+	BatchedSubscription,
+	/// Any other request, including invalid ones, handled by the regular handler.
+	Regular,
+}
+
+fn is_subscription_call(call: &Call) -> bool {
+	match *call {
+		Call::MethodCall(MethodCall { ref method, .. }) | Call::Notification(Notification { ref method, .. }) =>
+			SUBSCRIPTION_METHODS.contains(&method.as_str()),
+		Call::Invalid(_) => false,
+	}
+}
+
+/// Parses given request once and returns the handler it has to be routed to.
+pub fn route(request: &str) -> Route {
+	match serde_json::from_str::<Request>(request) {
+		Ok(Request::Single(ref call)) if is_subscription_call(call) => Route::Subscription,
+		Ok(Request::Batch(ref calls)) if calls.iter().any(is_subscription_call) => Route::BatchedSubscription,
+		_ => Route::Regular,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{notification, route, Route};
+
+	#[test]
+	fn should_serialize_notification() {
+		assert_eq!(
+			notification(&"0x1", &true),
+			r#"{"jsonrpc":"2.0","method":"eth_subscription","params":{"result":true,"subscription":"0x1"}}"#
+		);
+	}
+
+	#[test]
+	fn should_route_subscription_requests() {
+		assert_eq!(route(r#"{"jsonrpc":"2.0","method":"eth_subscribe","params":["newHeads"],"id":1}"#), Route::Subscription);
+		assert_eq!(route(r#"{"jsonrpc":"2.0","method":"eth_unsubscribe","params":["0x1"],"id":1}"#), Route::Subscription);
+		assert_eq!(route(r#"{"jsonrpc":"2.0","method":"eth_blockNumber","params":[],"id":1}"#), Route::Regular);
+		assert_eq!(route("invalid"), Route::Regular);
+	}
+
+	#[test]
+	fn should_route_batched_subscription_requests() {
+		assert_eq!(route(r#"[{"jsonrpc":"2.0","method":"eth_blockNumber","params":[],"id":1},{"jsonrpc":"2.0","method":"eth_subscribe","params":["newHeads"],"id":2}]"#), Route::BatchedSubscription);
+		assert_eq!(route(r#"[{"jsonrpc":"2.0","method":"eth_subscribe","params":["newHeads"],"id":1}]"#), Route::BatchedSubscription);
+		assert_eq!(route(r#"[{"jsonrpc":"2.0","method":"eth_blockNumber","params":[],"id":1}]"#), Route::Regular);
+	}
+}
@@ -15,6 +15,7 @@
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
 use util::{Address, U256, Bytes, H256};
+use v1::types::TypedData;
 
 /// Transaction request coming from RPC
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
@@ -107,6 +108,8 @@ pub enum ConfirmationPayload {
 	Signature(Address, H256),
 	/// Decrypt request
 	Decrypt(Address, Bytes),
+	/// Sign typed data request, with the EIP-712 hash to sign
+	SignTypedData(Address, H256, TypedData),
 }
 
 impl ConfirmationPayload {
@@ -116,6 +119,7 @@ impl ConfirmationPayload {
 			ConfirmationPayload::SignTransaction(ref request) => request.from,
 			ConfirmationPayload::Signature(ref address, _) => *address,
 			ConfirmationPayload::Decrypt(ref address, _) => *address,
+			ConfirmationPayload::SignTypedData(ref address, _, _) => *address,
 		}
 	}
 }
@@ -0,0 +1,128 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Debug api implementation.
+
+use std::sync::{Weak, Arc};
+use jsonrpc_core::Error;
+use ethcore::client::{BlockChainClient, TransactionId, Executed, CallAnalytics};
+use ethcore::error::CallError;
+use ethcore::miner::MinerService;
+use v1::traits::Debug;
+use v1::helpers::{errors, dispatch};
+use v1::helpers::auto_args::Trailing;
+use v1::types::{BlockNumber, Bytes, CallRequest, H256, TraceOptions, DebugTrace};
+
+fn from_call_error(error: CallError) -> Error {
+	match error {
+		CallError::TransactionNotFound => errors::transaction_not_found(),
+		e => errors::from_call_error(e),
+	}
+}
+
+/// Returns true if the options select the call tracer instead of the struct logger.
+fn call_tracing(options: &TraceOptions) -> Result<bool, Error> {
+	match options.tracer.as_ref().map(String::as_str) {
+		None => Ok(false),
+		Some("callTracer") => Ok(true),
+		Some(tracer) => Err(errors::invalid_params("tracer", tracer)),
+	}
+}
+
+fn call_tree(executed: Executed) -> Result<DebugTrace, Error> {
+	match executed.call_trace {
+		Some(frame) => Ok(DebugTrace::CallTree(frame.into())),
+		None => Err(errors::internal("call tracer returned no trace", "")),
+	}
+}
+
+/// Debug api implementation.
+pub struct DebugClient<C, M> where C: BlockChainClient, M: MinerService {
+	client: Weak<C>,
+	miner: Weak<M>,
+}
+
+impl<C, M> DebugClient<C, M> where C: BlockChainClient, M: MinerService {
+	/// Creates new Debug client.
+	pub fn new(client: &Arc<C>, miner: &Arc<M>) -> Self {
+		DebugClient {
+			client: Arc::downgrade(client),
+			miner: Arc::downgrade(miner),
+		}
+	}
+
+	fn active(&self) -> Result<(), Error> {
+		// TODO: only call every 30s at most.
+		take_weak!(self.client).keep_alive();
+		Ok(())
+	}
+}
+
+impl<C, M> Debug for DebugClient<C, M> where C: BlockChainClient + 'static, M: MinerService + 'static {
+	fn trace_transaction(&self, hash: H256, options: Trailing<TraceOptions>) -> Result<DebugTrace, Error> {
+		try!(self.active());
+
+		let options = options.0;
+		let id = TransactionId::Hash(hash.into());
+		let client = take_weak!(self.client);
+		if try!(call_tracing(&options)) {
+			let analytics = CallAnalytics { call_tracing: true, ..Default::default() };
+			client.replay(id, analytics).map_err(from_call_error).and_then(call_tree)
+		} else {
+			client.replay_struct_logs(id, options.into())
+				.map(|trace| DebugTrace::StructLogs(trace.into()))
+				.map_err(from_call_error)
+		}
+	}
+
+	fn trace_call(&self, request: CallRequest, block: BlockNumber, options: Trailing<TraceOptions>) -> Result<DebugTrace, Error> {
+		try!(self.active());
+
+		let options = options.0;
+		let request = CallRequest::into(request);
+		let client = take_weak!(self.client);
+		let signed = dispatch::sign_call(&*client, &*take_weak!(self.miner), request);
+		if try!(call_tracing(&options)) {
+			let analytics = CallAnalytics { call_tracing: true, ..Default::default() };
+			client.call(&signed, block.into(), analytics, &Default::default()).map_err(from_call_error).and_then(call_tree)
+		} else {
+			client.call_struct_logs(&signed, block.into(), options.into())
+				.map(|trace| DebugTrace::StructLogs(trace.into()))
+				.map_err(from_call_error)
+		}
+	}
+
+	fn raw_block(&self, block: BlockNumber) -> Result<Option<Bytes>, Error> {
+		try!(self.active());
+
+		Ok(take_weak!(self.client).block(block.into()).map(Into::into))
+	}
+
+	fn raw_header(&self, block: BlockNumber) -> Result<Option<Bytes>, Error> {
+		try!(self.active());
+
+		Ok(take_weak!(self.client).block_header(block.into()).map(Into::into))
+	}
+
+	fn raw_receipts(&self, block: BlockNumber) -> Result<Option<Bytes>, Error> {
+		try!(self.active());
+
+		let client = take_weak!(self.client);
+		Ok(client.block_hash(block.into())
+			.and_then(|hash| client.block_receipts(&hash))
+			.map(Into::into))
+	}
+}
@@ -32,12 +32,11 @@ use util::sha3::*;
 use util::{FromHex, Mutex};
 use rlp::{self, UntrustedRlp, View};
 use ethcore::account_provider::AccountProvider;
-use ethcore::client::{MiningBlockChainClient, BlockId, TransactionId, UncleId};
+use ethcore::client::{MiningBlockChainClient, ProvingBlockChainClient, BlockId, TransactionId, UncleId};
 use ethcore::header::{Header as BlockHeader, BlockNumber as EthBlockNumber};
 use ethcore::block::IsBlock;
 use ethcore::views::*;
 use ethcore::ethereum::Ethash;
-use ethcore::transaction::{Transaction as EthTransaction, SignedTransaction, Action};
 use ethcore::log_entry::LogEntry;
 use ethcore::filter::Filter as EthcoreFilter;
 use ethcore::snapshot::SnapshotService;
@@ -45,11 +44,11 @@ use self::ethash::SeedHashCompute;
 use v1::traits::Eth;
 use v1::types::{
 	RichBlock, Block, BlockTransactions, BlockNumber, Bytes, SyncStatus, SyncInfo,
-	Transaction, CallRequest, Index, Filter, Log, Receipt, Work, DappId,
+	Transaction, CallRequest, Index, Filter, Log, Receipt, Work, DappId, AccountProof, StorageProof, StateOverride,
 	H64 as RpcH64, H256 as RpcH256, H160 as RpcH160, U256 as RpcU256,
 };
-use v1::helpers::{CallRequest as CRequest, errors, limit_logs};
-use v1::helpers::dispatch::{dispatch_transaction, default_gas_price};
+use v1::helpers::{errors, limit_logs};
+use v1::helpers::dispatch::{self, dispatch_transaction, default_gas_price};
 use v1::helpers::block_import::is_major_importing;
 use v1::helpers::auto_args::Trailing;
 
@@ -74,7 +73,7 @@ impl Default for EthClientOptions {
 
 /// Eth rpc implementation.
 pub struct EthClient<C, SN: ?Sized, S: ?Sized, M, EM> where
-	C: MiningBlockChainClient,
+	C: MiningBlockChainClient + ProvingBlockChainClient,
 	SN: SnapshotService,
 	S: SyncProvider,
 	M: MinerService,
@@ -91,7 +90,7 @@ pub struct EthClient<C, SN: ?Sized, S: ?Sized, M, EM> where
 }
 
 impl<C, SN: ?Sized, S: ?Sized, M, EM> EthClient<C, SN, S, M, EM> where
-	C: MiningBlockChainClient,
+	C: MiningBlockChainClient + ProvingBlockChainClient,
 	SN: SnapshotService,
 	S: SyncProvider,
 	M: MinerService,
@@ -204,19 +203,6 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM> EthClient<C, SN, S, M, EM> where
 		};
 		Ok(Some(block))
 	}
-
-	fn sign_call(&self, request: CRequest) -> Result<SignedTransaction, Error> {
-		let (client, miner) = (take_weak!(self.client), take_weak!(self.miner));
-		let from = request.from.unwrap_or(Address::zero());
-		Ok(EthTransaction {
-			nonce: request.nonce.unwrap_or_else(|| client.latest_nonce(&from)),
-			action: request.to.map_or(Action::Create, Action::Call),
-			gas: request.gas.unwrap_or(U256::from(50_000_000)),
-			gas_price: request.gas_price.unwrap_or_else(|| default_gas_price(&*client, &*miner)),
-			value: request.value.unwrap_or_else(U256::zero),
-			data: request.data.map_or_else(Vec::new, |d| d.to_vec())
-		}.fake_sign(from))
-	}
 }
 
 pub fn pending_logs<M>(miner: &M, best_block: EthBlockNumber, filter: &EthcoreFilter) -> Vec<Log> where M: MinerService {
@@ -241,7 +227,7 @@ pub fn pending_logs<M>(miner: &M, best_block: EthBlockNumber, filter: &EthcoreFi
 const MAX_QUEUE_SIZE_TO_MINE_ON: usize = 4;	// because uncles go back 6.
 
 impl<C, SN: ?Sized, S: ?Sized, M, EM> EthClient<C, SN, S, M, EM> where
-	C: MiningBlockChainClient + 'static,
+	C: MiningBlockChainClient + ProvingBlockChainClient + 'static,
 	SN: SnapshotService + 'static,
 	S: SyncProvider + 'static,
 	M: MinerService + 'static,
@@ -261,7 +247,7 @@ static SOLC: &'static str = "solc.exe";
 static SOLC: &'static str = "solc";
 
 impl<C, SN: ?Sized, S: ?Sized, M, EM> Eth for EthClient<C, SN, S, M, EM> where
-	C: MiningBlockChainClient + 'static,
+	C: MiningBlockChainClient + ProvingBlockChainClient + 'static,
 	SN: SnapshotService + 'static,
 	S: SyncProvider + 'static,
 	M: MinerService + 'static,
@@ -377,6 +363,40 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM> Eth for EthClient<C, SN, S, M, EM> where
 		}
 	}
 
+	fn proof(&self, address: RpcH160, keys: Vec<RpcU256>, num: Trailing<BlockNumber>) -> Result<AccountProof, Error> {
+		try!(self.active());
+		let id: BlockId = match num.0 {
+			BlockNumber::Pending => return Err(errors::invalid_params("block", "Proofs are not available for the pending block.")),
+			num => num.into(),
+		};
+
+		let address: Address = RpcH160::into(address);
+		let positions: Vec<H256> = keys.iter().map(|key| {
+			let position: U256 = key.clone().into();
+			H256::from(position)
+		}).collect();
+
+		let proof = match take_weak!(self.client).prove_account_with_storage(&address, &positions, id) {
+			Some(Ok(proof)) => proof,
+			Some(Err(e)) => return Err(errors::internal("Could not prove the account.", e)),
+			None => return Err(errors::state_pruned()),
+		};
+
+		Ok(AccountProof {
+			address: address.into(),
+			balance: proof.balance.into(),
+			nonce: proof.nonce.into(),
+			code_hash: proof.code_hash.into(),
+			storage_hash: proof.storage_root.into(),
+			account_proof: proof.proof.into_iter().map(Bytes::new).collect(),
+			storage_proof: keys.into_iter().zip(proof.storage).map(|(key, storage)| StorageProof {
+				key: key,
+				value: storage.value.into(),
+				proof: storage.proof.into_iter().map(Bytes::new).collect(),
+			}).collect(),
+		})
+	}
+
 	fn transaction_count(&self, address: RpcH160, num: Trailing<BlockNumber>) -> Result<RpcU256, Error> {
 		try!(self.active());
 
@@ -494,6 +514,13 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM> Eth for EthClient<C, SN, S, M, EM> where
 		}
 	}
 
+	fn block_receipts(&self, num: BlockNumber) -> Result<Option<Vec<Receipt>>, Error> {
+		try!(self.active());
+
+		let receipts = take_weak!(self.client).localized_block_receipts(num.into());
+		Ok(receipts.map(|receipts| receipts.into_iter().map(Into::into).collect()))
+	}
+
 	fn uncle_by_block_hash_and_index(&self, hash: RpcH256, index: Index) -> Result<Option<RichBlock>, Error> {
 		try!(self.active());
 
@@ -622,15 +649,16 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM> Eth for EthClient<C, SN, S, M, EM> where
 		self.send_raw_transaction(raw)
 	}
 
-	fn call(&self, request: CallRequest, num: Trailing<BlockNumber>) -> Result<Bytes, Error> {
+	fn call(&self, request: CallRequest, num: Trailing<BlockNumber>, state_override: Trailing<StateOverride>) -> Result<Bytes, Error> {
 		try!(self.active());
 
 		let request = CallRequest::into(request);
-		let signed = try!(self.sign_call(request));
+		let signed = dispatch::sign_call(&*take_weak!(self.client), &*take_weak!(self.miner), request);
+		let state_override = state_override.0.into();
 
 		let result = match num.0 {
-			BlockNumber::Pending => take_weak!(self.miner).call(&*take_weak!(self.client), &signed, Default::default()),
-			num => take_weak!(self.client).call(&signed, num.into(), Default::default()),
+			BlockNumber::Pending => take_weak!(self.miner).call(&*take_weak!(self.client), &signed, Default::default(), &state_override),
+			num => take_weak!(self.client).call(&signed, num.into(), Default::default(), &state_override),
 		};
 
 
@@ -639,18 +667,19 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM> Eth for EthClient<C, SN, S, M, EM> where
 			.map_err(errors::from_call_error)
 	}
 
-	fn estimate_gas(&self, request: CallRequest, num: Trailing<BlockNumber>) -> Result<RpcU256, Error> {
+	fn estimate_gas(&self, request: CallRequest, num: Trailing<BlockNumber>, state_override: Trailing<StateOverride>) -> Result<RpcU256, Error> {
 		try!(self.active());
 
 		let request = CallRequest::into(request);
-		let signed = try!(self.sign_call(request));
+		let signed = dispatch::sign_call(&*take_weak!(self.client), &*take_weak!(self.miner), request);
+		let state_override = state_override.0.into();
 		let result = match num.0 {
-			BlockNumber::Pending => take_weak!(self.miner).call(&*take_weak!(self.client), &signed, Default::default()),
-			num => take_weak!(self.client).call(&signed, num.into(), Default::default()),
+			BlockNumber::Pending => take_weak!(self.miner).estimate_gas(&*take_weak!(self.client), &signed, &state_override),
+			num => take_weak!(self.client).estimate_gas(&signed, num.into(), &state_override),
 		};
 
 		result
-			.map(|res| (res.gas_used + res.refunded).into())
+			.map(Into::into)
 			.map_err(errors::from_call_error)
 	}
 
@@ -0,0 +1,322 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Eth PUB-SUB rpc implementation.
+
+use std::thread;
+use std::sync::{mpsc, Arc, Weak};
+use std::collections::HashMap;
+use jsonrpc_core::*;
+use ethcore::client::{BlockChainClient, BlockId, ChainNotify};
+use ethcore::filter::Filter as EthcoreFilter;
+use ethcore::header::Header as EthcoreHeader;
+use ethsync::SyncProvider;
+use util::{H256, Mutex, RwLock};
+use rlp;
+use v1::traits::EthPubSub;
+use v1::types::{Index, SubscriptionKind, SubscriptionParams, SubscriptionResult, SyncInfo, SyncStatus, U256 as RpcU256};
+use v1::helpers::errors;
+use v1::helpers::auto_args::Trailing;
+use v1::helpers::block_import::is_major_importing;
+use v1::helpers::pubsub::{self, ConnectionId, Sink, SubscriptionManager};
+
+enum Subscription {
+	NewHeads,
+	Logs(EthcoreFilter),
+	NewPendingTransactions,
+	Syncing,
+}
+
+struct Subscriber {
+	connection: ConnectionId,
+	sink: Arc<Sink>,
+	subscription: Subscription,
+}
+
+/// Serialized notification together with the sink it has to be sent to.
+type QueuedNotification = (Arc<Sink>, String);
+
+impl Subscriber {
+	fn notification(&self, id: usize, result: &SubscriptionResult) -> QueuedNotification {
+		(self.sink.clone(), pubsub::notification(&RpcU256::from(id), result))
+	}
+}
+
+#[derive(Default)]
+struct Subscribers {
+	next_id: usize,
+	items: HashMap<usize, Subscriber>,
+}
+
+impl Subscribers {
+	fn add(&mut self, subscriber: Subscriber) -> usize {
+		self.next_id += 1;
+		let id = self.next_id;
+		self.items.insert(id, subscriber);
+		id
+	}
+
+	fn remove(&mut self, connection: ConnectionId, id: usize) -> bool {
+		let owned = self.items.get(&id).map_or(false, |s| s.connection == connection);
+		if owned {
+			self.items.remove(&id);
+		}
+		owned
+	}
+
+	fn remove_connection(&mut self, connection: ConnectionId) {
+		self.items.retain(|_, s| s.connection != connection);
+	}
+
+	fn notifications<F>(&self, matches: F, results: &[SubscriptionResult]) -> Vec<QueuedNotification> where F: Fn(&Subscription) -> bool {
+		self.items.iter()
+			.filter(|&(_, s)| matches(&s.subscription))
+			.flat_map(|(id, subscriber)| results.iter().map(move |result| subscriber.notification(*id, result)))
+			.collect()
+	}
+
+	fn has_logs_subscriptions(&self) -> bool {
+		self.items.values().any(|s| match s.subscription {
+			Subscription::Logs(_) => true,
+			_ => false,
+		})
+	}
+}
+
+/// Eth PUB-SUB rpc implementation bound to a single connection.
+struct EthPubSubClient {
+	subscribers: Arc<RwLock<Subscribers>>,
+	connection: ConnectionId,
+	sink: Arc<Sink>,
+}
+
+impl EthPubSub for EthPubSubClient {
+	fn subscribe(&self, kind: SubscriptionKind, params: Trailing<SubscriptionParams>) -> Result<RpcU256, Error> {
+		let subscription = match (kind, params.0) {
+			(SubscriptionKind::Logs, SubscriptionParams::Logs(filter)) => Subscription::Logs(filter.into()),
+			(SubscriptionKind::Logs, SubscriptionParams::None) => Subscription::Logs(EthcoreFilter {
+				from_block: BlockId::Latest,
+				to_block: BlockId::Latest,
+				address: None,
+				topics: vec![None, None, None, None],
+				limit: None,
+			}),
+			(_, SubscriptionParams::Logs(_)) => {
+				return Err(errors::invalid_params("filter", "Only `logs` subscription accepts a filter."));
+			},
+			(SubscriptionKind::NewHeads, SubscriptionParams::None) => Subscription::NewHeads,
+			(SubscriptionKind::NewPendingTransactions, SubscriptionParams::None) => Subscription::NewPendingTransactions,
+			(SubscriptionKind::Syncing, SubscriptionParams::None) => Subscription::Syncing,
+		};
+
+		let id = self.subscribers.write().add(Subscriber {
+			connection: self.connection,
+			sink: self.sink.clone(),
+			subscription: subscription,
+		});
+		Ok(id.into())
+	}
+
+	fn unsubscribe(&self, id: Index) -> Result<bool, Error> {
+		Ok(self.subscribers.write().remove(self.connection, id.value()))
+	}
+}
+
+/// Keeps track of subscriptions made over all connections and notifies subscribers
+/// about new blocks, pending transactions and changes of the syncing status.
+///
+/// Notifications are sent from a separate thread, so that slow connections don't hold up
+/// block and transaction import.
+pub struct EthPubSubHandler<C, S: ?Sized> where
+	C: BlockChainClient,
+	S: SyncProvider {
+
+	client: Weak<C>,
+	sync: Weak<S>,
+	is_syncing: Mutex<bool>,
+	subscribers: Arc<RwLock<Subscribers>>,
+	notifier: Mutex<mpsc::Sender<Vec<QueuedNotification>>>,
+}
+
+impl<C, S: ?Sized> EthPubSubHandler<C, S> where
+	C: BlockChainClient,
+	S: SyncProvider {
+
+	/// Creates new Eth PUB-SUB handler.
+	pub fn new(client: &Arc<C>, sync: &Arc<S>) -> Self {
+		let (notifier, notifications) = mpsc::channel::<Vec<QueuedNotification>>();
+		// the thread stops once the handler, and with it the sending end of the channel, is dropped.
+		thread::Builder::new().name("pubsub".into()).spawn(move || {
+			for (sink, notification) in notifications.into_iter().flat_map(|n| n) {
+				sink.send(notification);
+			}
+		}).expect("Error creating pub-sub notifier thread");
+
+		EthPubSubHandler {
+			client: Arc::downgrade(client),
+			sync: Arc::downgrade(sync),
+			is_syncing: Mutex::new(false),
+			subscribers: Default::default(),
+			notifier: Mutex::new(notifier),
+		}
+	}
+
+	fn send(&self, notifications: Vec<QueuedNotification>) {
+		if notifications.is_empty() {
+			return;
+		}
+		if let Err(e) = self.notifier.lock().send(notifications) {
+			warn!(target: "rpc", "Pub-sub notifier has stopped: {:?}", e);
+		}
+	}
+
+	/// Notifies `newPendingTransactions` subscribers about transactions imported to the queue.
+	pub fn notify_pending_transactions(&self, hashes: &[H256]) {
+		let results = hashes.iter()
+			.map(|hash| SubscriptionResult::TransactionHash(hash.clone().into()))
+			.collect::<Vec<_>>();
+		let notifications = self.subscribers.read().notifications(|s| match *s {
+			Subscription::NewPendingTransactions => true,
+			_ => false,
+		}, &results);
+		self.send(notifications);
+	}
+
+	fn notify_heads(&self, client: &C, enacted: &[H256]) {
+		let headers = enacted.iter()
+			.filter_map(|hash| client.block_header(BlockId::Hash(hash.clone())))
+			.map(|bytes| SubscriptionResult::Header(rlp::decode::<EthcoreHeader>(&bytes).into()))
+			.collect::<Vec<_>>();
+		let notifications = self.subscribers.read().notifications(|s| match *s {
+			Subscription::NewHeads => true,
+			_ => false,
+		}, &headers);
+		self.send(notifications);
+	}
+
+	/// Logs of retracted blocks are not announced again with `removed: true`,
+	/// subscribers learn about reorganisations from `newHeads`.
+	fn notify_logs(&self, client: &C, enacted: &[H256]) {
+		if !self.subscribers.read().has_logs_subscriptions() {
+			return;
+		}
+
+		// logs of each block are looked up once and matched against all subscriptions.
+		let logs = enacted.iter()
+			.flat_map(|hash| client.logs(EthcoreFilter {
+				from_block: BlockId::Hash(hash.clone()),
+				to_block: BlockId::Hash(hash.clone()),
+				address: None,
+				topics: vec![None, None, None, None],
+				limit: None,
+			}))
+			.collect::<Vec<_>>();
+
+		let notifications = {
+			let subscribers = self.subscribers.read();
+			let mut notifications = Vec::new();
+			for (id, subscriber) in &subscribers.items {
+				if let Subscription::Logs(ref filter) = subscriber.subscription {
+					notifications.extend(logs.iter()
+						.filter(|log| filter.matches(&log.entry))
+						.map(|log| subscriber.notification(*id, &SubscriptionResult::Log(log.clone().into()))));
+				}
+			}
+			notifications
+		};
+		self.send(notifications);
+	}
+
+	fn notify_syncing(&self, client: &C) {
+		let sync = match self.sync.upgrade() {
+			Some(sync) => sync,
+			None => return,
+		};
+		let status = sync.status();
+		let is_syncing = is_major_importing(Some(status.state), client.queue_info());
+		{
+			let mut was_syncing = self.is_syncing.lock();
+			if *was_syncing == is_syncing {
+				return;
+			}
+			*was_syncing = is_syncing;
+		}
+
+		let status = match is_syncing {
+			true => SyncStatus::Info(SyncInfo {
+				starting_block: status.start_block_number.into(),
+				current_block: client.chain_info().best_block_number.into(),
+				highest_block: status.highest_block_number.unwrap_or(status.start_block_number).into(),
+				warp_chunks_amount: None,
+				warp_chunks_processed: None,
+				block_gap: None,
+			}),
+			false => SyncStatus::None,
+		};
+		let notifications = self.subscribers.read().notifications(|s| match *s {
+			Subscription::Syncing => true,
+			_ => false,
+		}, &[SubscriptionResult::SyncState(status)]);
+		self.send(notifications);
+	}
+}
+
+impl<C, S: ?Sized> ChainNotify for EthPubSubHandler<C, S> where
+	C: BlockChainClient + 'static,
+	S: SyncProvider + 'static {
+
+	fn new_blocks(
+		&self,
+		_imported: Vec<H256>,
+		_invalid: Vec<H256>,
+		enacted: Vec<H256>,
+		_retracted: Vec<H256>,
+		_sealed: Vec<H256>,
+		_duration: u64)
+	{
+		if self.subscribers.read().items.is_empty() {
+			return;
+		}
+
+		let client = match self.client.upgrade() {
+			Some(client) => client,
+			None => return,
+		};
+
+		self.notify_heads(&*client, &enacted);
+		self.notify_logs(&*client, &enacted);
+		self.notify_syncing(&*client);
+	}
+}
+
+impl<C, S: ?Sized> SubscriptionManager for EthPubSubHandler<C, S> where
+	C: BlockChainClient + 'static,
+	S: SyncProvider + 'static {
+
+	fn connection_handler(&self, connection: ConnectionId, sink: Arc<Sink>) -> IoHandler {
+		let handler = IoHandler::new();
+		handler.add_delegate(EthPubSubClient {
+			subscribers: self.subscribers.clone(),
+			connection: connection,
+			sink: sink,
+		}.to_delegate());
+		handler
+	}
+
+	fn drop_connection(&self, connection: ConnectionId) {
+		self.subscribers.write().remove_connection(connection);
+	}
+}
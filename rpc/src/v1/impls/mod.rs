@@ -25,8 +25,10 @@ macro_rules! take_weak {
 	}
 }
 
+mod debug;
 mod eth;
 mod eth_filter;
+mod eth_pubsub;
 mod net;
 mod parity;
 mod parity_accounts;
@@ -40,8 +42,10 @@ mod traces;
 mod web3;
 
 pub use self::web3::Web3Client;
+pub use self::debug::DebugClient;
 pub use self::eth::{EthClient, EthClientOptions};
 pub use self::eth_filter::EthFilterClient;
+pub use self::eth_pubsub::EthPubSubHandler;
 pub use self::net::NetClient;
 pub use self::parity::ParityClient;
 pub use self::parity_accounts::ParityAccountsClient;
@@ -27,7 +27,7 @@ use ethcore::client::MiningBlockChainClient;
 use jsonrpc_core::Error;
 use v1::helpers::auto_args::Ready;
 use v1::helpers::{
-	errors, dispatch,
+	errors, dispatch, eip712,
 	SigningQueue, ConfirmationPromise, ConfirmationResult, ConfirmationPayload, SignerService
 };
 use v1::traits::{EthSigning, ParitySigning};
@@ -36,6 +36,7 @@ use v1::types::{
 	Either as RpcEither,
 	RichRawTransaction as RpcRichRawTransaction,
 	TransactionRequest as RpcTransactionRequest,
+	TypedData as RpcTypedData,
 	ConfirmationPayload as RpcConfirmationPayload,
 	ConfirmationResponse as RpcConfirmationResponse
 };
@@ -192,6 +193,18 @@ impl<C: 'static, M: 'static> EthSigning for SigningQueueClient<C, M> where
 		});
 	}
 
+	fn sign_typed_data(&self, ready: Ready<RpcH520>, address: RpcH160, data: RpcTypedData) {
+		let hash = self.active().and_then(|_| eip712::hash(&data));
+		let res = hash.and_then(|hash| self.dispatch(RpcConfirmationPayload::SignTypedData((address, hash.into(), data).into())));
+		self.handle_dispatch(res, |response| {
+			match response {
+				Ok(RpcConfirmationResponse::Signature(signature)) => ready.ready(Ok(signature)),
+				Err(e) => ready.ready(Err(e)),
+				e => ready.ready(Err(errors::internal("Unexpected result.", e))),
+			}
+		});
+	}
+
 	fn send_transaction(&self, ready: Ready<RpcH256>, request: RpcTransactionRequest) {
 		let res = self.active().and_then(|_| self.dispatch(RpcConfirmationPayload::SendTransaction(request)));
 		self.handle_dispatch(res, |response| {
@@ -27,6 +27,7 @@ use jsonrpc_core::Error;
 use v1::helpers::auto_args::Ready;
 use v1::helpers::errors;
 use v1::helpers::dispatch;
+use v1::helpers::eip712;
 use v1::traits::{EthSigning, ParitySigning};
 use v1::types::{
 	U256 as RpcU256,
@@ -34,6 +35,7 @@ use v1::types::{
 	Either as RpcEither,
 	RichRawTransaction as RpcRichRawTransaction,
 	TransactionRequest as RpcTransactionRequest,
+	TypedData as RpcTypedData,
 	ConfirmationPayload as RpcConfirmationPayload,
 	ConfirmationResponse as RpcConfirmationResponse,
 };
@@ -94,6 +96,17 @@ impl<C: 'static, M: 'static> EthSigning for SigningUnsafeClient<C, M> where
 		ready.ready(result);
 	}
 
+	fn sign_typed_data(&self, ready: Ready<RpcH520>, address: RpcH160, data: RpcTypedData) {
+		let hash = eip712::hash(&data);
+		let result = hash.and_then(|hash| self.handle(RpcConfirmationPayload::SignTypedData((address, hash.into(), data).into())));
+		let result = match result {
+			Ok(RpcConfirmationResponse::Signature(signature)) => Ok(signature),
+			Err(e) => Err(e),
+			e => Err(errors::internal("Unexpected result", e)),
+		};
+		ready.ready(result);
+	}
+
 	fn send_transaction(&self, ready: Ready<RpcH256>, request: RpcTransactionRequest) {
 		let result = match self.handle(RpcConfirmationPayload::SendTransaction(request)) {
 			Ok(RpcConfirmationResponse::SendTransaction(hash)) => Ok(hash),
@@ -21,17 +21,17 @@ use jsonrpc_core::*;
 use rlp::{UntrustedRlp, View};
 use ethcore::client::{BlockChainClient, CallAnalytics, TransactionId, TraceId};
 use ethcore::miner::MinerService;
-use ethcore::transaction::{Transaction as EthTransaction, SignedTransaction, Action};
 use v1::traits::Traces;
-use v1::helpers::{errors, CallRequest as CRequest};
-use v1::helpers::params::from_params_default_third;
-use v1::types::{TraceFilter, LocalizedTrace, BlockNumber, Index, CallRequest, Bytes, TraceResults, H256};
+use v1::helpers::{errors, dispatch};
+use v1::helpers::params::{from_params_default_second, from_params_default_third, from_params_default_third_fourth};
+use v1::types::{TraceFilter, LocalizedTrace, BlockNumber, Index, CallRequest, Bytes, TraceResults, StateOverride, H256};
 
 fn to_call_analytics(flags: Vec<String>) -> CallAnalytics {
 	CallAnalytics {
 		transaction_tracing: flags.contains(&("trace".to_owned())),
 		vm_tracing: flags.contains(&("vmTrace".to_owned())),
 		state_diffing: flags.contains(&("stateDiff".to_owned())),
+		call_tracing: flags.contains(&("callTracer".to_owned())),
 	}
 }
 
@@ -50,21 +50,6 @@ impl<C, M> TracesClient<C, M> where C: BlockChainClient, M: MinerService {
 		}
 	}
 
-	// TODO: share with eth.rs
-	fn sign_call(&self, request: CRequest) -> Result<SignedTransaction, Error> {
-		let client = take_weak!(self.client);
-		let miner = take_weak!(self.miner);
-		let from = request.from.unwrap_or(0.into());
-		Ok(EthTransaction {
-			nonce: request.nonce.unwrap_or_else(|| client.latest_nonce(&from)),
-			action: request.to.map_or(Action::Create, Action::Call),
-			gas: request.gas.unwrap_or(50_000_000.into()),
-			gas_price: request.gas_price.unwrap_or_else(|| miner.sensible_gas_price()),
-			value: request.value.unwrap_or(0.into()),
-			data: request.data.map_or_else(Vec::new, |d| d.to_vec())
-		}.fake_sign(from))
-	}
-
 	fn active(&self) -> Result<(), Error> {
 		// TODO: only call every 30s at most.
 		take_weak!(self.client).keep_alive();
@@ -123,24 +108,44 @@ impl<C, M> Traces for TracesClient<C, M> where C: BlockChainClient + 'static, M:
 
 	fn call(&self, params: Params) -> Result<Value, Error> {
 		try!(self.active());
-		from_params_default_third(params)
-			.and_then(|(request, flags, block)| {
+		from_params_default_third_fourth(params)
+			.and_then(|(request, flags, block, state_override)| {
 				let request = CallRequest::into(request);
-				let signed = try!(self.sign_call(request));
-				match take_weak!(self.client).call(&signed, block.into(), to_call_analytics(flags)) {
+				let signed = dispatch::sign_call(&*take_weak!(self.client), &*take_weak!(self.miner), request);
+				let state_override = StateOverride::into(state_override);
+				match take_weak!(self.client).call(&signed, block.into(), to_call_analytics(flags), &state_override) {
 					Ok(e) => Ok(to_value(&TraceResults::from(e))),
 					_ => Ok(Value::Null),
 				}
 			})
 	}
 
+	fn call_many(&self, params: Params) -> Result<Value, Error> {
+		try!(self.active());
+		from_params_default_second::<Vec<(CallRequest, Vec<String>)>>(params)
+			.and_then(|(requests, block)| {
+				let (client, miner) = (take_weak!(self.client), take_weak!(self.miner));
+				let mut transactions = Vec::with_capacity(requests.len());
+				let mut analytics = Vec::with_capacity(requests.len());
+				for (request, flags) in requests {
+					transactions.push(dispatch::sign_call(&*client, &*miner, CallRequest::into(request)));
+					analytics.push(to_call_analytics(flags));
+				}
+
+				match client.call_many(transactions, analytics, block.into()) {
+					Ok(results) => Ok(to_value(&results.into_iter().map(TraceResults::from).collect::<Vec<_>>())),
+					_ => Ok(Value::Null),
+				}
+			})
+	}
+
 	fn raw_transaction(&self, params: Params) -> Result<Value, Error> {
 		try!(self.active());
 		from_params_default_third(params)
 			.and_then(|(raw_transaction, flags, block)| {
 				let raw_transaction = Bytes::to_vec(raw_transaction);
 				match UntrustedRlp::new(&raw_transaction).as_val() {
-					Ok(signed) => match take_weak!(self.client).call(&signed, block.into(), to_call_analytics(flags)) {
+					Ok(signed) => match take_weak!(self.client).call(&signed, block.into(), to_call_analytics(flags), &Default::default()) {
 						Ok(e) => Ok(to_value(&TraceResults::from(e))),
 						_ => Ok(Value::Null),
 					},
@@ -159,4 +164,15 @@ impl<C, M> Traces for TracesClient<C, M> where C: BlockChainClient + 'static, M:
 				}
 			})
 	}
+
+	fn replay_block_transactions(&self, params: Params) -> Result<Value, Error> {
+		try!(self.active());
+		from_params::<(BlockNumber, _)>(params)
+			.and_then(|(block_number, flags)| {
+				match take_weak!(self.client).replay_block_transactions(block_number.into(), to_call_analytics(flags)) {
+					Ok(results) => Ok(to_value(&results.into_iter().map(TraceResults::from).collect::<Vec<_>>())),
+					_ => Ok(Value::Null),
+				}
+			})
+	}
 }
@@ -19,7 +19,7 @@
 use util::{Address, H256, Bytes, U256, FixedHash, Uint};
 use util::standard::*;
 use ethcore::error::{Error, CallError};
-use ethcore::client::{MiningBlockChainClient, Executed, CallAnalytics};
+use ethcore::client::{MiningBlockChainClient, Executed, CallAnalytics, StateOverride};
 use ethcore::block::{ClosedBlock, IsBlock};
 use ethcore::header::BlockNumber;
 use ethcore::transaction::SignedTransaction;
@@ -254,7 +254,11 @@ impl MinerService for TestMinerService {
 		self.latest_closed_block.lock().as_ref().map_or_else(U256::zero, |b| b.block().fields().state.balance(address).clone())
 	}
 
-	fn call(&self, _chain: &MiningBlockChainClient, _t: &SignedTransaction, _analytics: CallAnalytics) -> Result<Executed, CallError> {
+	fn call(&self, _chain: &MiningBlockChainClient, _t: &SignedTransaction, _analytics: CallAnalytics, _state_override: &StateOverride) -> Result<Executed, CallError> {
+		unimplemented!();
+	}
+
+	fn estimate_gas(&self, _chain: &MiningBlockChainClient, _t: &SignedTransaction, _state_override: &StateOverride) -> Result<U256, CallError> {
 		unimplemented!();
 	}
 
@@ -0,0 +1,169 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use ethcore::client::{TestBlockChainClient, BlockChainClient, BlockId, Executed};
+use ethcore::error::CallError;
+use ethcore::executed::CallType;
+use ethcore::trace::{StructLog, StructLogTrace, CallFrame, FrameKind};
+
+use jsonrpc_core::{IoHandler, GenericIoHandler};
+use rustc_serialize::hex::ToHex;
+use v1::{Debug, DebugClient};
+use v1::tests::helpers::TestMinerService;
+
+struct Tester {
+	client: Arc<TestBlockChainClient>,
+	io: IoHandler,
+}
+
+fn setup() -> Tester {
+	let client = Arc::new(TestBlockChainClient::default());
+	let miner = Arc::new(TestMinerService::default());
+	let io = IoHandler::new();
+	io.add_delegate(DebugClient::new(&client, &miner).to_delegate());
+
+	client.set_struct_log_result(Ok(StructLogTrace {
+		gas_used: 21_003.into(),
+		failed: false,
+		output: vec![],
+		struct_logs: vec![StructLog {
+			pc: 0,
+			instruction: 0x60,
+			gas: 3.into(),
+			gas_cost: 3.into(),
+			depth: 1,
+			stack: Some(vec![]),
+			memory: Some(vec![]),
+			storage: Some(Default::default()),
+		}],
+	}));
+
+	Tester {
+		client: client,
+		io: io,
+	}
+}
+
+#[test]
+fn rpc_debug_trace_transaction() {
+	let tester = setup();
+
+	let request = r#"{"jsonrpc":"2.0","method":"debug_traceTransaction","params":["0x0000000000000000000000000000000000000000000000000000000000000005"],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"failed":false,"gas":21003,"returnValue":"","structLogs":[{"depth":1,"gas":3,"gasCost":3,"memory":[],"op":"PUSH1","pc":0,"stack":[],"storage":{}}]},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_debug_trace_transaction_not_found() {
+	let tester = setup();
+	tester.client.set_struct_log_result(Err(CallError::TransactionNotFound));
+
+	let request = r#"{"jsonrpc":"2.0","method":"debug_traceTransaction","params":["0x0000000000000000000000000000000000000000000000000000000000000005", {"disableStack":true}],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Transaction not found.","data":null},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_debug_trace_call() {
+	let tester = setup();
+
+	let request = r#"{"jsonrpc":"2.0","method":"debug_traceCall","params":[{"to":"0x8895b33f2d8fa6c9e1cc5ac4b6d4a5ec2e0eba1c","data":"0x6000"},"latest",{"disableMemory":true}],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"failed":false,"gas":21003,"returnValue":"","structLogs":[{"depth":1,"gas":3,"gasCost":3,"memory":[],"op":"PUSH1","pc":0,"stack":[],"storage":{}}]},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_debug_trace_transaction_with_call_tracer() {
+	let tester = setup();
+	tester.client.set_execution_result(Ok(Executed {
+		gas: 50_000.into(),
+		gas_used: 21_000.into(),
+		refunded: 0.into(),
+		cumulative_gas_used: 21_000.into(),
+		logs: vec![],
+		contracts_created: vec![],
+		output: vec![],
+		trace: vec![],
+		vm_trace: None,
+		call_trace: Some(CallFrame {
+			kind: FrameKind::Call(CallType::Call),
+			from: 1.into(),
+			to: Some(2.into()),
+			value: 0.into(),
+			gas: 29_000.into(),
+			gas_used: 0.into(),
+			input: vec![],
+			output: vec![],
+			error: None,
+			calls: vec![],
+		}),
+		state_diff: None,
+		reverted: false,
+		exception: None,
+	}));
+
+	let request = r#"{"jsonrpc":"2.0","method":"debug_traceTransaction","params":["0x0000000000000000000000000000000000000000000000000000000000000005", {"tracer":"callTracer"}],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"from":"0x0000000000000000000000000000000000000001","gas":"0x7148","gasUsed":"0x0","input":"0x","output":"0x","to":"0x0000000000000000000000000000000000000002","type":"CALL","value":"0x0"},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_debug_trace_transaction_with_unknown_tracer() {
+	let tester = setup();
+
+	let request = r#"{"jsonrpc":"2.0","method":"debug_traceTransaction","params":["0x0000000000000000000000000000000000000000000000000000000000000005", {"tracer":"prestateTracer"}],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: tracer","data":"\"prestateTracer\""},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_debug_get_raw_block() {
+	let tester = setup();
+	let block = tester.client.block(BlockId::Number(0)).unwrap();
+
+	let request = r#"{"jsonrpc":"2.0","method":"debug_getRawBlock","params":["0x0"],"id":1}"#;
+	let response = format!(r#"{{"jsonrpc":"2.0","result":"0x{}","id":1}}"#, block.to_hex());
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response));
+}
+
+#[test]
+fn rpc_debug_get_raw_header() {
+	let tester = setup();
+	let header = tester.client.block_header(BlockId::Number(0)).unwrap();
+
+	let request = r#"{"jsonrpc":"2.0","method":"debug_getRawHeader","params":["earliest"],"id":1}"#;
+	let response = format!(r#"{{"jsonrpc":"2.0","result":"0x{}","id":1}}"#, header.to_hex());
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response));
+}
+
+#[test]
+fn rpc_debug_get_raw_receipts_unknown_block() {
+	let tester = setup();
+
+	let request = r#"{"jsonrpc":"2.0","method":"debug_getRawReceipts","params":["0x10"],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":null,"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
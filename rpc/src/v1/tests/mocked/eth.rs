@@ -22,12 +22,15 @@ use rustc_serialize::hex::{FromHex, ToHex};
 use time::get_time;
 use rlp;
 
-use util::{Uint, U256, Address, H256, FixedHash, Mutex, Hashable};
+use util::{Uint, U256, Address, H256, H2048, FixedHash, Mutex, Hashable};
 use ethcore::account_provider::AccountProvider;
-use ethcore::client::{TestBlockChainClient, EachBlockWith, Executed, TransactionId};
+use ethcore::client::{BlockChainClient, TestBlockChainClient, EachBlockWith, Executed, BlockId, TransactionId};
+use ethcore::error::CallError;
 use ethcore::log_entry::{LocalizedLogEntry, LogEntry};
 use ethcore::receipt::LocalizedReceipt;
 use ethcore::transaction::{Transaction, Action};
+use ethcore::views::BlockView;
+use ethcore::contract_address;
 use ethcore::miner::{ExternalMiner, MinerService};
 use ethsync::SyncState;
 
@@ -431,6 +434,36 @@ fn rpc_eth_storage_at() {
 	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_eth_get_proof() {
+	let tester = EthTester::default();
+	tester.client.set_balance(Address::from(1), U256::from(5));
+	tester.client.set_storage(Address::from(1), H256::from(4), H256::from(7));
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_getProof",
+		"params": ["0x0000000000000000000000000000000000000001", ["0x4"], "latest"],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"address":"0x0000000000000000000000000000000000000001","balance":"0x5","nonce":"0x0","codeHash":"0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470","storageHash":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421","accountProof":[],"storageProof":[{"key":"0x4","value":"0x0000000000000000000000000000000000000000000000000000000000000007","proof":[]}]},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_eth_get_proof_pending() {
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_getProof",
+		"params": ["0x0000000000000000000000000000000000000001", [], "pending"],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: block","data":"\"Proofs are not available for the pending block.\""},"id":1}"#;
+
+	assert_eq!(EthTester::default().io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_eth_transaction_count() {
 	let request = r#"{
@@ -560,7 +593,10 @@ fn rpc_eth_call_latest() {
 		output: vec![0x12, 0x34, 0xff],
 		trace: vec![],
 		vm_trace: None,
+		call_trace: None,
 		state_diff: None,
+		reverted: false,
+		exception: None,
 	}));
 
 	let request = r#"{
@@ -595,7 +631,10 @@ fn rpc_eth_call() {
 		output: vec![0x12, 0x34, 0xff],
 		trace: vec![],
 		vm_trace: None,
+		call_trace: None,
 		state_diff: None,
+		reverted: false,
+		exception: None,
 	}));
 
 	let request = r#"{
@@ -630,7 +669,10 @@ fn rpc_eth_call_default_block() {
 		output: vec![0x12, 0x34, 0xff],
 		trace: vec![],
 		vm_trace: None,
+		call_trace: None,
 		state_diff: None,
+		reverted: false,
+		exception: None,
 	}));
 
 	let request = r#"{
@@ -651,6 +693,71 @@ fn rpc_eth_call_default_block() {
 	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_eth_call_with_state_override() {
+	let tester = EthTester::default();
+	tester.client.set_execution_result(Ok(Executed {
+		gas: U256::zero(),
+		gas_used: U256::from(0xff30),
+		refunded: U256::from(0x5),
+		cumulative_gas_used: U256::zero(),
+		logs: vec![],
+		contracts_created: vec![],
+		output: vec![0x12, 0x34, 0xff],
+		trace: vec![],
+		vm_trace: None,
+		call_trace: None,
+		state_diff: None,
+		reverted: false,
+		exception: None,
+	}));
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_call",
+		"params": [{
+			"from": "0xb60e8dd61c5d32be8058bb8eb970870f07233155",
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567",
+			"data": "0xd46e8dd6"
+		},
+		"latest",
+		{
+			"0xd46e8dd67c5d32be8058bb8eb970870f07244567": {
+				"balance": "0x1",
+				"code": "0x6000",
+				"stateDiff": {
+					"0x0000000000000000000000000000000000000000000000000000000000000001": "0x0000000000000000000000000000000000000000000000000000000000000002"
+				}
+			}
+		}],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"0x1234ff","id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_eth_call_with_invalid_state_override() {
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_call",
+		"params": [{
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567"
+		},
+		"latest",
+		{
+			"0xd46e8dd67c5d32be8058bb8eb970870f07244567": {
+				"storage": {}
+			}
+		}],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Invalid params","data":null},"id":1}"#;
+
+	assert_eq!(EthTester::default().io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_eth_estimate_gas() {
 	let tester = EthTester::default();
@@ -664,7 +771,10 @@ fn rpc_eth_estimate_gas() {
 		output: vec![0x12, 0x34, 0xff],
 		trace: vec![],
 		vm_trace: None,
+		call_trace: None,
 		state_diff: None,
+		reverted: false,
+		exception: None,
 	}));
 
 	let request = r#"{
@@ -699,7 +809,10 @@ fn rpc_eth_estimate_gas_default_block() {
 		output: vec![0x12, 0x34, 0xff],
 		trace: vec![],
 		vm_trace: None,
+		call_trace: None,
 		state_diff: None,
+		reverted: false,
+		exception: None,
 	}));
 
 	let request = r#"{
@@ -720,6 +833,26 @@ fn rpc_eth_estimate_gas_default_block() {
 	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_eth_estimate_gas_fails_at_cap() {
+	let tester = EthTester::default();
+	tester.client.set_execution_result(Err(CallError::GasEstimationFailed(U256::from(4_712_388))));
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_estimateGas",
+		"params": [{
+			"from": "0xb60e8dd61c5d32be8058bb8eb970870f07233155",
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567"
+		},
+		"latest"],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32015,"message":"Transaction execution fails even with the maximal gas limit of 4712388.","data":null},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_eth_send_transaction() {
 	let tester = EthTester::default();
@@ -980,6 +1113,48 @@ fn rpc_eth_transaction_receipt_null() {
 	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_eth_block_receipts_null() {
+	let tester = EthTester::default();
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_getBlockReceipts",
+		"params": ["0x10"],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","result":null,"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_eth_block_receipts() {
+	let tester = EthTester::default();
+	// the test client only has receipts of blocks whose hash starts with `f`.
+	while tester.client.chain_info().best_block_hash < H256::from("f000000000000000000000000000000000000000000000000000000000000000") {
+		tester.client.add_blocks(1, EachBlockWith::Transaction);
+	}
+
+	let block = tester.client.block(BlockId::Latest).unwrap();
+	let view = BlockView::new(&block);
+	let transaction = view.transaction_at(0).unwrap();
+	let contract = contract_address(&transaction.sender().unwrap(), &transaction.nonce);
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_getBlockReceipts",
+		"params": ["latest"],
+		"id": 1
+	}"#;
+	let response = format!(
+		r#"{{"jsonrpc":"2.0","result":[{{"blockHash":"0x{:?}","blockNumber":"0x{:x}","contractAddress":"0x{:?}","cumulativeGasUsed":"0x0","gasUsed":"0x0","logs":[],"logsBloom":"0x{:?}","root":"0x{:?}","transactionHash":"0x{:?}","transactionIndex":"0x0"}}],"id":1}}"#,
+		view.hash(), view.header_view().number(), contract, H2048::zero(), H256::zero(), transaction.hash()
+	);
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response));
+}
+
 // These tests are incorrect: their output is undefined as long as eth_getCompilers is [].
 // Will ignore for now, but should probably be replaced by more substantial tests which check
 // the output of eth_getCompilers to determine whether to test. CI systems can then be preinstalled
@@ -0,0 +1,259 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use util::{H256, Address, Mutex};
+use ethcore::client::{TestBlockChainClient, EachBlockWith, BlockId, ChainNotify, BlockChainClient};
+use ethcore::log_entry::{LocalizedLogEntry, LogEntry};
+use ethsync::SyncState;
+use jsonrpc_core::{IoHandler, GenericIoHandler};
+use v1::EthPubSubHandler;
+use v1::pubsub::{Sink, SubscriptionManager};
+use v1::tests::helpers::{TestSyncProvider, Config};
+
+#[derive(Default)]
+struct TestSink {
+	notifications: Mutex<Vec<String>>,
+}
+
+impl Sink for TestSink {
+	fn send(&self, notification: String) {
+		self.notifications.lock().push(notification);
+	}
+}
+
+impl TestSink {
+	fn take(&self) -> Vec<String> {
+		::std::mem::replace(&mut *self.notifications.lock(), Vec::new())
+	}
+
+	// notifications are sent from the handler's own thread.
+	fn wait(&self, count: usize) -> Vec<String> {
+		let start = Instant::now();
+		while self.notifications.lock().len() < count && start.elapsed() < Duration::from_secs(5) {
+			thread::sleep(Duration::from_millis(10));
+		}
+		self.take()
+	}
+}
+
+struct PubSubTester {
+	client: Arc<TestBlockChainClient>,
+	sync: Arc<TestSyncProvider>,
+	handler: EthPubSubHandler<TestBlockChainClient, TestSyncProvider>,
+}
+
+impl Default for PubSubTester {
+	fn default() -> Self {
+		let client = Arc::new(TestBlockChainClient::default());
+		let sync = Arc::new(TestSyncProvider::new(Config {
+			network_id: 3,
+			num_peers: 120,
+		}));
+		let handler = EthPubSubHandler::new(&client, &sync);
+
+		PubSubTester {
+			client: client,
+			sync: sync,
+			handler: handler,
+		}
+	}
+}
+
+impl PubSubTester {
+	fn connect(&self, connection: usize) -> (IoHandler, Arc<TestSink>) {
+		let sink = Arc::new(TestSink::default());
+		(self.handler.connection_handler(connection, sink.clone()), sink)
+	}
+
+	fn import_blocks(&self, count: usize) -> Vec<H256> {
+		let best = self.client.chain_info().best_block_number as usize;
+		self.client.add_blocks(count, EachBlockWith::Nothing);
+		let enacted = (best + 1..best + 1 + count)
+			.filter_map(|n| self.client.block_hash(BlockId::Number(n as u64)))
+			.collect::<Vec<_>>();
+		self.handler.new_blocks(vec![], vec![], enacted.clone(), vec![], vec![], 0);
+		enacted
+	}
+}
+
+fn subscribe(io: &IoHandler, params: &str) -> Option<String> {
+	let request = format!(r#"{{"jsonrpc": "2.0", "method": "eth_subscribe", "params": {}, "id": 1}}"#, params);
+	io.handle_request_sync(&request)
+}
+
+#[test]
+fn rpc_eth_subscribe_new_heads() {
+	let tester = PubSubTester::default();
+	let (io, sink) = tester.connect(1);
+
+	let response = r#"{"jsonrpc":"2.0","result":"0x1","id":1}"#;
+	assert_eq!(subscribe(&io, r#"["newHeads"]"#), Some(response.to_owned()));
+
+	let enacted = tester.import_blocks(2);
+
+	let notifications = sink.wait(2);
+	assert_eq!(notifications.len(), 2);
+	for (notification, hash) in notifications.iter().zip(enacted.iter()) {
+		assert!(notification.starts_with(r#"{"jsonrpc":"2.0","method":"eth_subscription","params":{"result":{"#));
+		assert!(notification.contains(&format!(r#""hash":"0x{:?}""#, hash)));
+		assert!(notification.ends_with(r#""subscription":"0x1"}}"#));
+	}
+}
+
+#[test]
+fn rpc_eth_subscribe_logs() {
+	let tester = PubSubTester::default();
+	let (io, sink) = tester.connect(1);
+	tester.client.set_logs(vec![LocalizedLogEntry {
+		block_number: 1,
+		block_hash: H256::default(),
+		entry: LogEntry {
+			address: Address::default(),
+			topics: vec![],
+			data: vec![1,2,3],
+		},
+		transaction_index: 0,
+		transaction_hash: H256::default(),
+		log_index: 0,
+	}]);
+
+	let response = r#"{"jsonrpc":"2.0","result":"0x1","id":1}"#;
+	assert_eq!(subscribe(&io, r#"["logs", {}]"#), Some(response.to_owned()));
+
+	tester.import_blocks(1);
+
+	let notification = r#"{"jsonrpc":"2.0","method":"eth_subscription","params":{"result":{"address":"0x0000000000000000000000000000000000000000","blockHash":"0x0000000000000000000000000000000000000000000000000000000000000000","blockNumber":"0x1","data":"0x010203","logIndex":"0x0","topics":[],"transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000000","transactionIndex":"0x0","type":"mined"},"subscription":"0x1"}}"#;
+	assert_eq!(sink.wait(1), vec![notification.to_owned()]);
+}
+
+#[test]
+fn rpc_eth_subscribe_logs_matches_filters() {
+	let tester = PubSubTester::default();
+	let (io1, sink1) = tester.connect(1);
+	let (io2, sink2) = tester.connect(2);
+	tester.client.set_logs(vec![LocalizedLogEntry {
+		block_number: 1,
+		block_hash: H256::default(),
+		entry: LogEntry {
+			address: Address::from(1),
+			topics: vec![],
+			data: vec![],
+		},
+		transaction_index: 0,
+		transaction_hash: H256::default(),
+		log_index: 0,
+	}]);
+
+	subscribe(&io1, r#"["logs", {"address": "0x0000000000000000000000000000000000000001"}]"#).unwrap();
+	subscribe(&io2, r#"["logs", {"address": "0x0000000000000000000000000000000000000002"}]"#).unwrap();
+
+	tester.import_blocks(1);
+
+	let notifications = sink1.wait(1);
+	assert_eq!(notifications.len(), 1);
+	assert!(notifications[0].contains(r#""address":"0x0000000000000000000000000000000000000001""#));
+	assert!(sink2.take().is_empty());
+}
+
+#[test]
+fn rpc_eth_subscribe_new_pending_transactions() {
+	let tester = PubSubTester::default();
+	let (io, sink) = tester.connect(1);
+
+	let response = r#"{"jsonrpc":"2.0","result":"0x1","id":1}"#;
+	assert_eq!(subscribe(&io, r#"["newPendingTransactions"]"#), Some(response.to_owned()));
+
+	tester.handler.notify_pending_transactions(&[H256::from(5)]);
+
+	let notification = r#"{"jsonrpc":"2.0","method":"eth_subscription","params":{"result":"0x0000000000000000000000000000000000000000000000000000000000000005","subscription":"0x1"}}"#;
+	assert_eq!(sink.wait(1), vec![notification.to_owned()]);
+}
+
+#[test]
+fn rpc_eth_subscribe_syncing() {
+	let tester = PubSubTester::default();
+	let (io, sink) = tester.connect(1);
+
+	let response = r#"{"jsonrpc":"2.0","result":"0x1","id":1}"#;
+	assert_eq!(subscribe(&io, r#"["syncing"]"#), Some(response.to_owned()));
+
+	// not syncing and nothing changed
+	tester.import_blocks(1);
+	assert!(sink.take().is_empty());
+
+	{
+		let mut status = tester.sync.status.write();
+		status.state = SyncState::Blocks;
+		status.highest_block_number = Some(100);
+	}
+	tester.import_blocks(1);
+	let notifications = sink.wait(1);
+	assert_eq!(notifications.len(), 1);
+	assert!(notifications[0].contains(r#""highestBlock":"0x64""#));
+
+	tester.sync.status.write().state = SyncState::Idle;
+	tester.import_blocks(1);
+	let notification = r#"{"jsonrpc":"2.0","method":"eth_subscription","params":{"result":false,"subscription":"0x1"}}"#;
+	assert_eq!(sink.wait(1), vec![notification.to_owned()]);
+}
+
+#[test]
+fn rpc_eth_subscribe_invalid_params() {
+	let tester = PubSubTester::default();
+	let (io, _sink) = tester.connect(1);
+
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: filter","data":"\"Only `logs` subscription accepts a filter.\""},"id":1}"#;
+	assert_eq!(subscribe(&io, r#"["newHeads", {}]"#), Some(response.to_owned()));
+
+	let response = subscribe(&io, r#"["newBlocks"]"#).unwrap();
+	assert!(response.contains(r#""code":-32602"#));
+}
+
+#[test]
+fn rpc_eth_unsubscribe() {
+	let tester = PubSubTester::default();
+	let (io1, sink) = tester.connect(1);
+	let (io2, _) = tester.connect(2);
+	subscribe(&io1, r#"["newPendingTransactions"]"#).unwrap();
+
+	// other connections can't cancel the subscription
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_unsubscribe", "params": ["0x1"], "id": 1}"#;
+	assert_eq!(io2.handle_request_sync(request), Some(r#"{"jsonrpc":"2.0","result":false,"id":1}"#.to_owned()));
+	assert_eq!(io1.handle_request_sync(request), Some(r#"{"jsonrpc":"2.0","result":true,"id":1}"#.to_owned()));
+	assert_eq!(io1.handle_request_sync(request), Some(r#"{"jsonrpc":"2.0","result":false,"id":1}"#.to_owned()));
+
+	tester.handler.notify_pending_transactions(&[H256::from(5)]);
+	assert!(sink.take().is_empty());
+}
+
+#[test]
+fn should_drop_subscriptions_of_closed_connection() {
+	let tester = PubSubTester::default();
+	let (io1, sink1) = tester.connect(1);
+	let (io2, sink2) = tester.connect(2);
+	subscribe(&io1, r#"["newPendingTransactions"]"#).unwrap();
+	subscribe(&io2, r#"["newPendingTransactions"]"#).unwrap();
+
+	tester.handler.drop_connection(1);
+	tester.handler.notify_pending_transactions(&[H256::from(5)]);
+
+	let notification = r#"{"jsonrpc":"2.0","method":"eth_subscription","params":{"result":"0x0000000000000000000000000000000000000000000000000000000000000005","subscription":"0x2"}}"#;
+	assert_eq!(sink2.wait(1), vec![notification.to_owned()]);
+	assert!(sink1.take().is_empty());
+}
@@ -17,7 +17,9 @@
 //! RPC mocked tests. Most of these test that the RPC server is serializing and forwarding
 //! method calls properly.
 
+mod debug;
 mod eth;
+mod eth_pubsub;
 mod net;
 mod web3;
 mod personal;
@@ -27,4 +29,5 @@ mod parity_set;
 mod rpc;
 mod signer;
 mod signing;
+mod traces;
 mod manage_network;
@@ -21,12 +21,12 @@ use rlp;
 use jsonrpc_core::{IoHandler, Success, GenericIoHandler};
 use v1::impls::SigningQueueClient;
 use v1::traits::{EthSigning, ParitySigning, Parity};
-use v1::helpers::{SignerService, SigningQueue};
+use v1::helpers::{SignerService, SigningQueue, ConfirmationPayload};
 use v1::types::ConfirmationResponse;
 use v1::tests::helpers::TestMinerService;
 use v1::tests::mocked::parity;
 
-use util::{Address, FixedHash, Uint, U256, ToPretty, Hashable};
+use util::{Address, FixedHash, Uint, U256, H256, ToPretty, Hashable};
 use ethcore::account_provider::AccountProvider;
 use ethcore::client::TestBlockChainClient;
 use ethcore::transaction::{Transaction, Action};
@@ -410,3 +410,93 @@ fn should_add_decryption_to_the_queue() {
 	let res = rx.try_recv().unwrap();
 	assert_eq!(res, Some(response.to_owned()));
 }
+
+const TYPED_DATA: &'static str = r#"{
+	"types": {
+		"EIP712Domain": [
+			{"name": "name", "type": "string"},
+			{"name": "version", "type": "string"},
+			{"name": "chainId", "type": "uint256"},
+			{"name": "verifyingContract", "type": "address"}
+		],
+		"Person": [
+			{"name": "name", "type": "string"},
+			{"name": "wallet", "type": "address"}
+		],
+		"Mail": [
+			{"name": "from", "type": "Person"},
+			{"name": "to", "type": "Person"},
+			{"name": "contents", "type": "string"}
+		]
+	},
+	"primaryType": "Mail",
+	"domain": {
+		"name": "Ether Mail",
+		"version": "1",
+		"chainId": 1,
+		"verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+	},
+	"message": {
+		"from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+		"to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+		"contents": "Hello, Bob!"
+	}
+}"#;
+
+#[test]
+fn should_add_sign_typed_data_to_queue() {
+	// given
+	let tester = eth_signing();
+	let address = Address::random();
+	assert_eq!(tester.signer.requests().len(), 0);
+
+	// when
+	let request = format!(r#"{{
+		"jsonrpc": "2.0",
+		"method": "eth_signTypedData",
+		"params": ["0x{:?}", {}],
+		"id": 1
+	}}"#, address, TYPED_DATA);
+	let response = r#"{"jsonrpc":"2.0","result":"0x0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","id":1}"#;
+
+	// then
+	let (tx, rx) = mpsc::channel();
+	tester.io.handle_request(&request, move |response| {
+		tx.send(response).unwrap();
+	});
+	let requests = tester.signer.requests();
+	assert_eq!(requests.len(), 1);
+	match requests[0].payload {
+		ConfirmationPayload::SignTypedData(ref from, ref hash, ref data) => {
+			assert_eq!(from, &address);
+			assert_eq!(hash, &H256::from_str("be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2").unwrap());
+			assert_eq!(data.primary_type, "Mail".to_owned());
+		},
+		ref payload => panic!("Unexpected payload: {:?}", payload),
+	}
+	// respond
+	tester.signer.request_confirmed(1.into(), Ok(ConfirmationResponse::Signature(0.into())));
+
+	let res = rx.try_recv().unwrap();
+	assert_eq!(res, Some(response.to_owned()));
+}
+
+#[test]
+fn should_reject_invalid_typed_data() {
+	// given
+	let tester = eth_signing();
+	let address = Address::random();
+
+	// when
+	let request = format!(r#"{{
+		"jsonrpc": "2.0",
+		"method": "eth_signTypedData",
+		"params": ["0x{:?}", {}],
+		"id": 1
+	}}"#, address, TYPED_DATA.replace(r#""chainId": 1"#, r#""chainId": -1"#));
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: Invalid typed data","data":"\"value out of range for type uint256\""},"id":1}"#;
+
+	// then
+	assert_eq!(tester.io.handle_request_sync(&request), Some(response.to_owned()));
+	assert_eq!(tester.signer.requests().len(), 0);
+}
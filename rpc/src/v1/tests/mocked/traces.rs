@@ -0,0 +1,89 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+
+use std::sync::Arc;
+
+use ethcore::client::{TestBlockChainClient, Executed};
+use ethcore::error::CallError;
+
+use jsonrpc_core::{IoHandler, GenericIoHandler};
+use v1::{Traces, TracesClient};
+use v1::tests::helpers::TestMinerService;
+
+struct Tester {
+	client: Arc<TestBlockChainClient>,
+	io: IoHandler,
+}
+
+fn setup() -> Tester {
+	let client = Arc::new(TestBlockChainClient::default());
+	let miner = Arc::new(TestMinerService::default());
+	let io = IoHandler::new();
+	io.add_delegate(TracesClient::new(&client, &miner).to_delegate());
+
+	client.set_execution_result(Ok(Executed {
+		gas: 20_000.into(),
+		gas_used: 10_000.into(),
+		refunded: 0.into(),
+		cumulative_gas_used: 10_000.into(),
+		logs: vec![],
+		contracts_created: vec![],
+		output: vec![0x90],
+		trace: vec![],
+		vm_trace: None,
+		call_trace: None,
+		state_diff: None,
+		reverted: false,
+		exception: None,
+	}));
+
+	Tester {
+		client: client,
+		io: io,
+	}
+}
+
+#[test]
+fn rpc_trace_call_many() {
+	let tester = setup();
+
+	let request = r#"{"jsonrpc":"2.0","method":"trace_callMany","params":[[[{}, ["stateDiff"]], [{}, ["trace"]]], "latest"],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":[{"output":"0x90","stateDiff":null,"trace":[],"vmTrace":null},{"output":"0x90","stateDiff":null,"trace":[],"vmTrace":null}],"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_trace_call_many_state_pruned() {
+	let tester = setup();
+	tester.client.set_execution_result(Err(CallError::StatePruned));
+
+	let request = r#"{"jsonrpc":"2.0","method":"trace_callMany","params":[[[{}, ["trace"]]]],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":null,"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_trace_replay_block_transactions() {
+	let tester = setup();
+
+	let request = r#"{"jsonrpc":"2.0","method":"trace_replayBlockTransactions","params":["0x10", ["trace", "stateDiff"]],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":[{"output":"0x90","stateDiff":null,"trace":[],"vmTrace":null}],"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
@@ -0,0 +1,48 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Debug rpc interface.
+use jsonrpc_core::Error;
+
+use v1::helpers::auto_args::{Trailing, Wrap};
+use v1::types::{BlockNumber, Bytes, CallRequest, H256, TraceOptions, DebugTrace};
+
+build_rpc_trait! {
+	/// Geth-compatible debug rpc interface.
+	pub trait Debug {
+		/// Replays the transaction with given hash and returns the state of the VM before each executed instruction,
+		/// or the call tree if `callTracer` is selected.
+		#[rpc(name = "debug_traceTransaction")]
+		fn trace_transaction(&self, H256, Trailing<TraceOptions>) -> Result<DebugTrace, Error>;
+
+		/// Executes the given call and returns the state of the VM before each executed instruction,
+		/// or the call tree if `callTracer` is selected.
+		#[rpc(name = "debug_traceCall")]
+		fn trace_call(&self, CallRequest, BlockNumber, Trailing<TraceOptions>) -> Result<DebugTrace, Error>;
+
+		/// Returns the RLP-encoded block.
+		#[rpc(name = "debug_getRawBlock")]
+		fn raw_block(&self, BlockNumber) -> Result<Option<Bytes>, Error>;
+
+		/// Returns the RLP-encoded block header.
+		#[rpc(name = "debug_getRawHeader")]
+		fn raw_header(&self, BlockNumber) -> Result<Option<Bytes>, Error>;
+
+		/// Returns the RLP-encoded list of receipts of the block, as stored by the client.
+		#[rpc(name = "debug_getRawReceipts")]
+		fn raw_receipts(&self, BlockNumber) -> Result<Option<Bytes>, Error>;
+	}
+}
@@ -17,7 +17,7 @@
 //! Eth rpc interface.
 use jsonrpc_core::Error;
 
-use v1::types::{RichBlock, BlockNumber, Bytes, CallRequest, Filter, FilterChanges, Index, DappId};
+use v1::types::{RichBlock, BlockNumber, Bytes, CallRequest, Filter, FilterChanges, Index, DappId, AccountProof, StateOverride};
 use v1::types::{Log, Receipt, SyncStatus, Transaction, Work};
 use v1::types::{H64, H160, H256, U256};
 
@@ -66,6 +66,10 @@ build_rpc_trait! {
 		#[rpc(name = "eth_getStorageAt")]
 		fn storage_at(&self, H160, U256, Trailing<BlockNumber>) -> Result<H256, Error>;
 
+		/// Returns the account and the given storage entries together with their Merkle proofs.
+		#[rpc(name = "eth_getProof")]
+		fn proof(&self, H160, Vec<U256>, Trailing<BlockNumber>) -> Result<AccountProof, Error>;
+
 		/// Returns block with given hash.
 		#[rpc(name = "eth_getBlockByHash")]
 		fn block_by_hash(&self, H256, bool) -> Result<Option<RichBlock>, Error>;
@@ -106,13 +110,13 @@ build_rpc_trait! {
 		#[rpc(name = "eth_submitTransaction")]
 		fn submit_transaction(&self, Bytes) -> Result<H256, Error>;
 
-		/// Call contract, returning the output data.
+		/// Call contract, returning the output data. The state of the block may be altered by the optional state override set.
 		#[rpc(name = "eth_call")]
-		fn call(&self, CallRequest, Trailing<BlockNumber>) -> Result<Bytes, Error>;
+		fn call(&self, CallRequest, Trailing<BlockNumber>, Trailing<StateOverride>) -> Result<Bytes, Error>;
 
-		/// Estimate gas needed for execution of given contract.
+		/// Estimate gas needed for execution of given contract, optionally on top of overridden state.
 		#[rpc(name = "eth_estimateGas")]
-		fn estimate_gas(&self, CallRequest, Trailing<BlockNumber>) -> Result<U256, Error>;
+		fn estimate_gas(&self, CallRequest, Trailing<BlockNumber>, Trailing<StateOverride>) -> Result<U256, Error>;
 
 		/// Get transaction by its hash.
 		#[rpc(name = "eth_getTransactionByHash")]
@@ -130,6 +134,10 @@ build_rpc_trait! {
 		#[rpc(name = "eth_getTransactionReceipt")]
 		fn transaction_receipt(&self, H256) -> Result<Option<Receipt>, Error>;
 
+		/// Returns receipts of all transactions in given block.
+		#[rpc(name = "eth_getBlockReceipts")]
+		fn block_receipts(&self, BlockNumber) -> Result<Option<Vec<Receipt>>, Error>;
+
 		/// Returns an uncles at given block and index.
 		#[rpc(name = "eth_getUncleByBlockHashAndIndex")]
 		fn uncle_by_block_hash_and_index(&self, H256, Index) -> Result<Option<RichBlock>, Error>;
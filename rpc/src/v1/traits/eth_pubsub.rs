@@ -0,0 +1,34 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Eth PUB-SUB rpc interface.
+use jsonrpc_core::Error;
+
+use v1::helpers::auto_args::{Trailing, Wrap};
+use v1::types::{Index, SubscriptionKind, SubscriptionParams, U256};
+
+build_rpc_trait! {
+	/// Eth PUB-SUB rpc interface.
+	pub trait EthPubSub {
+		/// Subscribes to notifications of given kind and returns the subscription id.
+		#[rpc(name = "eth_subscribe")]
+		fn subscribe(&self, SubscriptionKind, Trailing<SubscriptionParams>) -> Result<U256, Error>;
+
+		/// Cancels the subscription with given id.
+		#[rpc(name = "eth_unsubscribe")]
+		fn unsubscribe(&self, Index) -> Result<bool, Error>;
+	}
+}
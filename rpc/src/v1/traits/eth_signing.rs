@@ -17,7 +17,7 @@
 //! Eth rpc interface.
 
 use v1::helpers::auto_args::{WrapAsync, Ready};
-use v1::types::{Bytes, H160, H256, H520, TransactionRequest, RichRawTransaction};
+use v1::types::{Bytes, H160, H256, H520, TransactionRequest, RichRawTransaction, TypedData};
 
 build_rpc_trait! {
 	/// Signing methods implementation relying on unlocked accounts.
@@ -26,6 +26,10 @@ build_rpc_trait! {
 		#[rpc(async, name = "eth_sign")]
 		fn sign(&self, Ready<H520>, H160, Bytes);
 
+		/// Signs the EIP-712 hash of given typed structured data with given address signature.
+		#[rpc(async, name = "eth_signTypedData")]
+		fn sign_typed_data(&self, Ready<H520>, H160, TypedData);
+
 		/// Sends transaction; will block waiting for signer to return the
 		/// transaction hash.
 		/// If Signer is disable it will require the account to be unlocked.
@@ -17,7 +17,9 @@
 //! Ethereum rpc interfaces.
 
 pub mod web3;
+pub mod debug;
 pub mod eth;
+pub mod eth_pubsub;
 pub mod eth_signing;
 pub mod net;
 pub mod parity;
@@ -30,7 +32,9 @@ pub mod traces;
 pub mod rpc;
 
 pub use self::web3::Web3;
+pub use self::debug::Debug;
 pub use self::eth::{Eth, EthFilter};
+pub use self::eth_pubsub::EthPubSub;
 pub use self::eth_signing::EthSigning;
 pub use self::net::Net;
 pub use self::parity::Parity;
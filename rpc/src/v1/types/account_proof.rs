@@ -0,0 +1,78 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Account state with Merkle proofs.
+
+use v1::types::{Bytes, H160, H256, U256};
+
+/// Proof of a single storage entry.
+#[derive(Debug, Default, Serialize)]
+pub struct StorageProof {
+	/// Storage key.
+	pub key: U256,
+	/// Value stored under the key.
+	pub value: H256,
+	/// Storage trie nodes (in order from the root) proving the value.
+	pub proof: Vec<Bytes>,
+}
+
+/// Account state with Merkle proofs of the account and the requested storage entries.
+#[derive(Debug, Default, Serialize)]
+pub struct AccountProof {
+	/// Address of the account.
+	pub address: H160,
+	/// Balance.
+	pub balance: U256,
+	/// Nonce.
+	pub nonce: U256,
+	/// Hash of the code.
+	#[serde(rename="codeHash")]
+	pub code_hash: H256,
+	/// Root of the storage trie.
+	#[serde(rename="storageHash")]
+	pub storage_hash: H256,
+	/// State trie nodes (in order from the root) proving the account.
+	#[serde(rename="accountProof")]
+	pub account_proof: Vec<Bytes>,
+	/// Proofs of the requested storage entries.
+	#[serde(rename="storageProof")]
+	pub storage_proof: Vec<StorageProof>,
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use v1::types::{Bytes, H256, U256};
+	use super::{AccountProof, StorageProof};
+
+	#[test]
+	fn should_serialize_account_proof() {
+		let proof = AccountProof {
+			balance: U256::from(1),
+			nonce: U256::from(2),
+			account_proof: vec![Bytes::new(vec![0xc0])],
+			storage_proof: vec![StorageProof {
+				key: U256::from(3),
+				value: H256::from(4),
+				proof: vec![Bytes::new(vec![0x80])],
+			}],
+			..Default::default()
+		};
+
+		let serialized = serde_json::to_string(&proof).unwrap();
+		assert_eq!(serialized, r#"{"address":"0x0000000000000000000000000000000000000000","balance":"0x1","nonce":"0x2","codeHash":"0x0000000000000000000000000000000000000000000000000000000000000000","storageHash":"0x0000000000000000000000000000000000000000000000000000000000000000","accountProof":["0xc0"],"storageProof":[{"key":"0x3","value":"0x0000000000000000000000000000000000000000000000000000000000000004","proof":["0x80"]}]}"#);
+	}
+}
@@ -18,7 +18,7 @@
 
 use std::fmt;
 use serde::{Serialize, Serializer};
-use v1::types::{U256, TransactionRequest, RichRawTransaction, H160, H256, H520, Bytes};
+use v1::types::{U256, TransactionRequest, RichRawTransaction, H160, H256, H520, Bytes, TypedData};
 use v1::helpers;
 
 /// Confirmation waiting in a queue
@@ -75,6 +75,27 @@ impl From<(H160, Bytes)> for DecryptRequest {
 	}
 }
 
+/// Sign typed data request
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize)]
+pub struct SignTypedDataRequest {
+	/// Address
+	pub address: H160,
+	/// EIP-712 hash to sign
+	pub hash: H256,
+	/// Typed data to display
+	pub data: TypedData,
+}
+
+impl From<(H160, H256, TypedData)> for SignTypedDataRequest {
+	fn from(tuple: (H160, H256, TypedData)) -> Self {
+		SignTypedDataRequest {
+			address: tuple.0,
+			hash: tuple.1,
+			data: tuple.2,
+		}
+	}
+}
+
 /// Confirmation response for particular payload
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConfirmationResponse {
@@ -116,6 +137,9 @@ pub enum ConfirmationPayload {
 	/// Decryption
 	#[serde(rename="decrypt")]
 	Decrypt(DecryptRequest),
+	/// Typed data signature
+	#[serde(rename="signTypedData")]
+	SignTypedData(SignTypedDataRequest),
 }
 
 impl From<helpers::ConfirmationPayload> for ConfirmationPayload {
@@ -131,6 +155,11 @@ impl From<helpers::ConfirmationPayload> for ConfirmationPayload {
 				address: address.into(),
 				msg: msg.into(),
 			}),
+			helpers::ConfirmationPayload::SignTypedData(address, hash, data) => ConfirmationPayload::SignTypedData(SignTypedDataRequest {
+				address: address.into(),
+				hash: hash.into(),
+				data: data,
+			}),
 		}
 	}
 }
@@ -205,6 +234,31 @@ mod tests {
 		assert_eq!(res.unwrap(), expected.to_owned());
 	}
 
+	#[test]
+	fn should_serialize_sign_typed_data_confirmation() {
+		// given
+		let data = serde_json::from_str(r#"{
+			"types": {
+				"EIP712Domain": [{"name": "name", "type": "string"}],
+				"Message": [{"name": "value", "type": "uint8"}]
+			},
+			"primaryType": "Message",
+			"domain": {"name": "Test"},
+			"message": {"value": 1}
+		}"#).unwrap();
+		let request = helpers::ConfirmationRequest {
+			id: 15.into(),
+			payload: helpers::ConfirmationPayload::SignTypedData(1.into(), 5.into(), data),
+		};
+
+		// when
+		let res = serde_json::to_string(&ConfirmationRequest::from(request));
+		let expected = r#"{"id":"0xf","payload":{"signTypedData":{"address":"0x0000000000000000000000000000000000000001","hash":"0x0000000000000000000000000000000000000000000000000000000000000005","data":{"types":{"EIP712Domain":[{"name":"name","type":"string"}],"Message":[{"name":"value","type":"uint8"}]},"primaryType":"Message","domain":{"name":"Test"},"message":{"value":1}}}}}"#;
+
+		// then
+		assert_eq!(res.unwrap(), expected.to_owned());
+	}
+
 	#[test]
 	fn should_serialize_transaction_confirmation() {
 		// given
@@ -0,0 +1,187 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Debug api types.
+
+use std::collections::BTreeMap;
+use serde::{Serialize, Serializer};
+use rustc_serialize::hex::ToHex;
+use ethcore::evm::instruction_name;
+use ethcore::trace as et;
+use util::{self, Uint};
+use v1::types::CallFrame;
+
+/// Options of `debug_traceTransaction` and `debug_traceCall`.
+#[derive(Debug, Default, PartialEq, Deserialize)]
+pub struct TraceOptions {
+	/// Do not return the stack.
+	#[serde(rename="disableStack")]
+	pub disable_stack: Option<bool>,
+	/// Do not return the memory.
+	#[serde(rename="disableMemory")]
+	pub disable_memory: Option<bool>,
+	/// Do not return the storage.
+	#[serde(rename="disableStorage")]
+	pub disable_storage: Option<bool>,
+	/// Name of the tracer to use instead of the struct logger, e.g. `callTracer`.
+	pub tracer: Option<String>,
+}
+
+impl Into<et::StructLogOptions> for TraceOptions {
+	fn into(self) -> et::StructLogOptions {
+		et::StructLogOptions {
+			disable_stack: self.disable_stack.unwrap_or(false),
+			disable_memory: self.disable_memory.unwrap_or(false),
+			disable_storage: self.disable_storage.unwrap_or(false),
+		}
+	}
+}
+
+// words are formatted like geth does: 32 bytes of hex without `0x` prefix.
+fn word(value: util::U256) -> String {
+	util::H256::from(value).hex()
+}
+
+/// State of the VM before execution of a single instruction.
+#[derive(Debug, Serialize)]
+pub struct StructLog {
+	/// The program counter.
+	pub pc: usize,
+	/// Name of the instruction.
+	pub op: String,
+	/// Gas left before execution of the instruction.
+	pub gas: u64,
+	/// The gas cost for this instruction.
+	#[serde(rename="gasCost")]
+	pub gas_cost: u64,
+	/// Call depth.
+	pub depth: usize,
+	/// The stack, bottom first.
+	#[serde(skip_serializing_if="Option::is_none")]
+	pub stack: Option<Vec<String>>,
+	/// The memory, split into 32 byte words.
+	#[serde(skip_serializing_if="Option::is_none")]
+	pub memory: Option<Vec<String>>,
+	/// Storage values written by the current call so far.
+	#[serde(skip_serializing_if="Option::is_none")]
+	pub storage: Option<BTreeMap<String, String>>,
+}
+
+impl From<et::StructLog> for StructLog {
+	fn from(l: et::StructLog) -> Self {
+		StructLog {
+			pc: l.pc,
+			op: instruction_name(l.instruction).into(),
+			gas: l.gas.low_u64(),
+			gas_cost: l.gas_cost.low_u64(),
+			depth: l.depth,
+			stack: l.stack.map(|stack| stack.into_iter().map(word).collect()),
+			memory: l.memory.map(|memory| memory.chunks(32).map(|w| w.to_hex()).collect()),
+			storage: l.storage.map(|storage| storage.into_iter().map(|(k, v)| (k.hex(), v.hex())).collect()),
+		}
+	}
+}
+
+/// Result of `debug_traceTransaction` and `debug_traceCall`.
+#[derive(Debug, Serialize)]
+pub struct StructLogTrace {
+	/// Gas used by the transaction.
+	pub gas: u64,
+	/// True if the execution failed or was reverted.
+	pub failed: bool,
+	/// Transaction output.
+	#[serde(rename="returnValue")]
+	pub return_value: String,
+	/// State of the VM for every executed instruction.
+	#[serde(rename="structLogs")]
+	pub struct_logs: Vec<StructLog>,
+}
+
+impl From<et::StructLogTrace> for StructLogTrace {
+	fn from(t: et::StructLogTrace) -> Self {
+		StructLogTrace {
+			gas: t.gas_used.low_u64(),
+			failed: t.failed,
+			return_value: t.output.to_hex(),
+			struct_logs: t.struct_logs.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+/// Result of `debug_traceTransaction` and `debug_traceCall`, depending on the selected tracer.
+#[derive(Debug)]
+pub enum DebugTrace {
+	/// State of the VM for every executed instruction.
+	StructLogs(StructLogTrace),
+	/// Nested call tree, produced by `callTracer`.
+	CallTree(CallFrame),
+}
+
+impl Serialize for DebugTrace {
+	fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error> where S: Serializer {
+		match *self {
+			DebugTrace::StructLogs(ref trace) => trace.serialize(serializer),
+			DebugTrace::CallTree(ref frame) => frame.serialize(serializer),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use ethcore::trace as et;
+	use super::{TraceOptions, StructLogTrace};
+
+	#[test]
+	fn test_trace_options_deserialize() {
+		let s = r#"{"disableStack":true,"disableStorage":false}"#;
+		let deserialized: TraceOptions = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, TraceOptions {
+			disable_stack: Some(true),
+			disable_memory: None,
+			disable_storage: Some(false),
+			tracer: None,
+		});
+
+		let options: et::StructLogOptions = deserialized.into();
+		assert!(options.disable_stack && !options.disable_memory && !options.disable_storage);
+
+		let s = r#"{"tracer":"callTracer"}"#;
+		let deserialized: TraceOptions = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.tracer, Some("callTracer".to_owned()));
+	}
+
+	#[test]
+	fn test_struct_log_trace_serialize() {
+		let trace = StructLogTrace::from(et::StructLogTrace {
+			gas_used: 21_003.into(),
+			failed: false,
+			output: vec![0x12, 0x34],
+			struct_logs: vec![et::StructLog {
+				pc: 0,
+				instruction: 0x60,
+				gas: 79_000.into(),
+				gas_cost: 3.into(),
+				depth: 1,
+				stack: Some(vec![0x2a.into()]),
+				memory: Some(vec![0; 32]),
+				storage: None,
+			}],
+		});
+		let serialized = serde_json::to_string(&trace).unwrap();
+		assert_eq!(serialized, r#"{"gas":21003,"failed":false,"returnValue":"1234","structLogs":[{"pc":0,"op":"PUSH1","gas":79000,"gasCost":3,"depth":1,"stack":["000000000000000000000000000000000000000000000000000000000000002a"],"memory":["0000000000000000000000000000000000000000000000000000000000000000"]}]}"#);
+	}
+}
@@ -0,0 +1,145 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Pub-Sub types.
+
+use serde::{Serialize, Serializer, Deserialize, Deserializer, Error};
+use serde::de::Visitor;
+use v1::types::{Filter, Header, Log, SyncStatus, H256};
+
+/// Kind of subscription made with `eth_subscribe`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum SubscriptionKind {
+	/// New block headers subscription.
+	NewHeads,
+	/// Logs subscription.
+	Logs,
+	/// New pending transactions subscription.
+	NewPendingTransactions,
+	/// Node syncing status subscription.
+	Syncing,
+}
+
+impl Deserialize for SubscriptionKind {
+	fn deserialize<D>(deserializer: &mut D) -> Result<SubscriptionKind, D::Error>
+	where D: Deserializer {
+		deserializer.deserialize(SubscriptionKindVisitor)
+	}
+}
+
+struct SubscriptionKindVisitor;
+
+impl Visitor for SubscriptionKindVisitor {
+	type Value = SubscriptionKind;
+
+	fn visit_str<E>(&mut self, value: &str) -> Result<Self::Value, E> where E: Error {
+		match value {
+			"newHeads" => Ok(SubscriptionKind::NewHeads),
+			"logs" => Ok(SubscriptionKind::Logs),
+			"newPendingTransactions" => Ok(SubscriptionKind::NewPendingTransactions),
+			"syncing" => Ok(SubscriptionKind::Syncing),
+			_ => Err(Error::custom(format!("invalid subscription kind: {}", value))),
+		}
+	}
+
+	fn visit_string<E>(&mut self, value: String) -> Result<Self::Value, E> where E: Error {
+		self.visit_str(value.as_ref())
+	}
+}
+
+/// Additional parameters of a subscription.
+#[derive(Debug, PartialEq, Clone)]
+pub enum SubscriptionParams {
+	/// Filter for the `logs` subscription.
+	Logs(Filter),
+	/// No parameters.
+	None,
+}
+
+impl Default for SubscriptionParams {
+	fn default() -> Self {
+		SubscriptionParams::None
+	}
+}
+
+impl Deserialize for SubscriptionParams {
+	fn deserialize<D>(deserializer: &mut D) -> Result<SubscriptionParams, D::Error>
+	where D: Deserializer {
+		Filter::deserialize(deserializer).map(SubscriptionParams::Logs)
+	}
+}
+
+/// Payload of a subscription notification.
+#[derive(Debug)]
+pub enum SubscriptionResult {
+	/// New block header.
+	Header(Header),
+	/// Log matching the subscription filter.
+	Log(Log),
+	/// Hash of a new pending transaction.
+	TransactionHash(H256),
+	/// Current syncing status.
+	SyncState(SyncStatus),
+}
+
+impl Serialize for SubscriptionResult {
+	fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+	where S: Serializer {
+		match *self {
+			SubscriptionResult::Header(ref header) => header.serialize(serializer),
+			SubscriptionResult::Log(ref log) => log.serialize(serializer),
+			SubscriptionResult::TransactionHash(ref hash) => hash.serialize(serializer),
+			SubscriptionResult::SyncState(ref status) => status.serialize(serializer),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use v1::types::{Filter, SyncStatus, H256};
+	use super::{SubscriptionKind, SubscriptionParams, SubscriptionResult};
+
+	#[test]
+	fn subscription_kind_deserialization() {
+		let s = r#"["newHeads", "logs", "newPendingTransactions", "syncing"]"#;
+		let deserialized: Vec<SubscriptionKind> = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, vec![
+			SubscriptionKind::NewHeads,
+			SubscriptionKind::Logs,
+			SubscriptionKind::NewPendingTransactions,
+			SubscriptionKind::Syncing,
+		]);
+		assert!(serde_json::from_str::<SubscriptionKind>(r#""newBlocks""#).is_err());
+	}
+
+	#[test]
+	fn subscription_params_deserialization() {
+		let s = r#"{"address":"0x0000000000000000000000000000000000000001"}"#;
+		let deserialized: SubscriptionParams = serde_json::from_str(s).unwrap();
+		let filter: Filter = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, SubscriptionParams::Logs(filter));
+	}
+
+	#[test]
+	fn subscription_result_serialization() {
+		let hash = SubscriptionResult::TransactionHash(H256::from(5));
+		assert_eq!(serde_json::to_string(&hash).unwrap(), r#""0x0000000000000000000000000000000000000000000000000000000000000005""#);
+
+		let syncing = SubscriptionResult::SyncState(SyncStatus::None);
+		assert_eq!(serde_json::to_string(&syncing).unwrap(), "false");
+	}
+}
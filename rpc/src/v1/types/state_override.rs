@@ -0,0 +1,116 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+
+use std::collections::BTreeMap;
+use serde::{Deserialize, Deserializer};
+use ethcore::client::{StateOverride as EthStateOverride, AccountOverride as EthAccountOverride};
+use v1::types::{Bytes, H160, H256, U256};
+
+/// Overridden state of a single account.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccountOverride {
+	/// Balance
+	pub balance: Option<U256>,
+	/// Nonce
+	pub nonce: Option<U256>,
+	/// Code
+	pub code: Option<Bytes>,
+	/// Full storage, replacing the existing one
+	pub state: Option<BTreeMap<H256, H256>>,
+	/// Storage entries to change, keeping the rest
+	#[serde(rename="stateDiff")]
+	pub state_diff: Option<BTreeMap<H256, H256>>,
+}
+
+impl Into<EthAccountOverride> for AccountOverride {
+	fn into(self) -> EthAccountOverride {
+		EthAccountOverride {
+			balance: self.balance.map(Into::into),
+			nonce: self.nonce.map(Into::into),
+			code: self.code.map(Into::into),
+			state: self.state.map(|s| s.into_iter().map(|(k, v)| (k.into(), v.into())).collect()),
+			state_diff: self.state_diff.map(|s| s.into_iter().map(|(k, v)| (k.into(), v.into())).collect()),
+		}
+	}
+}
+
+/// State override set, mapping addresses to their overridden state.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StateOverride(pub BTreeMap<H160, AccountOverride>);
+
+impl Deserialize for StateOverride {
+	fn deserialize<D>(deserializer: &mut D) -> Result<StateOverride, D::Error>
+	where D: Deserializer {
+		BTreeMap::deserialize(deserializer).map(StateOverride)
+	}
+}
+
+impl Into<EthStateOverride> for StateOverride {
+	fn into(self) -> EthStateOverride {
+		EthStateOverride {
+			accounts: self.0.into_iter().map(|(k, v)| (k.into(), v.into())).collect(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::BTreeMap;
+	use serde_json;
+	use v1::types::{Bytes, H160, H256, U256};
+	use super::{StateOverride, AccountOverride};
+
+	#[test]
+	fn state_override_deserialize() {
+		let s = r#"{
+			"0x0000000000000000000000000000000000000001": {
+				"balance": "0x1",
+				"nonce": "0x2",
+				"code": "0x6000"
+			},
+			"0x0000000000000000000000000000000000000002": {
+				"stateDiff": {
+					"0x0000000000000000000000000000000000000000000000000000000000000003": "0x0000000000000000000000000000000000000000000000000000000000000004"
+				}
+			}
+		}"#;
+		let deserialized: StateOverride = serde_json::from_str(s).unwrap();
+
+		let mut state_diff = BTreeMap::new();
+		state_diff.insert(H256::from(3), H256::from(4));
+		let mut accounts = BTreeMap::new();
+		accounts.insert(H160::from(1), AccountOverride {
+			balance: Some(U256::from(1)),
+			nonce: Some(U256::from(2)),
+			code: Some(Bytes::new(vec![0x60, 0x00])),
+			..Default::default()
+		});
+		accounts.insert(H160::from(2), AccountOverride {
+			state_diff: Some(state_diff),
+			..Default::default()
+		});
+		assert_eq!(deserialized, StateOverride(accounts));
+	}
+
+	#[test]
+	fn account_override_deserialize_unknown_field() {
+		let s = r#"{"storage": {}}"#;
+		let deserialized: Result<AccountOverride, _> = serde_json::from_str(s);
+		assert!(deserialized.is_err());
+	}
+}
@@ -223,6 +223,9 @@ pub struct Create {
 	gas: U256,
 	/// Initialization code
 	init: Bytes,
+	/// The type of the create.
+	#[serde(rename="createType")]
+	create_type: CreateType,
 }
 
 impl From<trace::Create> for Create {
@@ -232,6 +235,27 @@ impl From<trace::Create> for Create {
 			value: c.value.into(),
 			gas: c.gas.into(),
 			init: Bytes::new(c.init),
+			create_type: c.create_type.into(),
+		}
+	}
+}
+
+/// Create type.
+#[derive(Debug, Serialize)]
+pub enum CreateType {
+	/// Create
+	#[serde(rename="create")]
+	Create,
+	/// Create2
+	#[serde(rename="create2")]
+	Create2,
+}
+
+impl From<trace::CreateType> for CreateType {
+	fn from(c: trace::CreateType) -> Self {
+		match c {
+			trace::CreateType::Create => CreateType::Create,
+			trace::CreateType::Create2 => CreateType::Create2,
 		}
 	}
 }
@@ -251,6 +275,9 @@ pub enum CallType {
 	/// Delegate call
 	#[serde(rename="delegatecall")]
 	DelegateCall,
+	/// Static call
+	#[serde(rename="staticcall")]
+	StaticCall,
 }
 
 impl From<executed::CallType> for CallType {
@@ -260,6 +287,7 @@ impl From<executed::CallType> for CallType {
 			executed::CallType::Call => CallType::Call,
 			executed::CallType::CallCode => CallType::CallCode,
 			executed::CallType::DelegateCall => CallType::DelegateCall,
+			executed::CallType::StaticCall => CallType::StaticCall,
 		}
 	}
 }
@@ -317,6 +345,48 @@ impl From<trace::Suicide> for Suicide {
 	}
 }
 
+/// Reward type.
+#[derive(Debug, Serialize)]
+pub enum RewardType {
+	/// Block
+	#[serde(rename="block")]
+	Block,
+	/// Uncle
+	#[serde(rename="uncle")]
+	Uncle,
+}
+
+impl From<trace::RewardType> for RewardType {
+	fn from(c: trace::RewardType) -> Self {
+		match c {
+			trace::RewardType::Block => RewardType::Block,
+			trace::RewardType::Uncle => RewardType::Uncle,
+		}
+	}
+}
+
+/// Reward action
+#[derive(Debug, Serialize)]
+pub struct Reward {
+	/// Author's address.
+	pub author: H160,
+	/// Reward amount.
+	pub value: U256,
+	/// Reward type.
+	#[serde(rename="rewardType")]
+	pub reward_type: RewardType,
+}
+
+impl From<trace::Reward> for Reward {
+	fn from(r: trace::Reward) -> Self {
+		Reward {
+			author: r.author.into(),
+			value: r.value.into(),
+			reward_type: r.reward_type.into(),
+		}
+	}
+}
+
 /// Action
 #[derive(Debug)]
 pub enum Action {
@@ -326,6 +396,8 @@ pub enum Action {
 	Create(Create),
 	/// Suicide
 	Suicide(Suicide),
+	/// Reward
+	Reward(Reward),
 }
 
 impl From<trace::Action> for Action {
@@ -334,6 +406,7 @@ impl From<trace::Action> for Action {
 			trace::Action::Call(call) => Action::Call(call.into()),
 			trace::Action::Create(create) => Action::Create(create.into()),
 			trace::Action::Suicide(suicide) => Action::Suicide(suicide.into()),
+			trace::Action::Reward(reward) => Action::Reward(reward.into()),
 		}
 	}
 }
@@ -418,9 +491,9 @@ pub struct LocalizedTrace {
 	/// Subtraces
 	subtraces: usize,
 	/// Transaction position
-	transaction_position: usize,
+	transaction_position: Option<usize>,
 	/// Transaction hash
-	transaction_hash: H256,
+	transaction_hash: Option<H256>,
 	/// Block Number
 	block_number: u64,
 	/// Block Hash
@@ -445,6 +518,10 @@ impl Serialize for LocalizedTrace {
 				try!(serializer.serialize_struct_elt(&mut state, "type", "suicide"));
 				try!(serializer.serialize_struct_elt(&mut state, "action", suicide));
 			},
+			Action::Reward(ref reward) => {
+				try!(serializer.serialize_struct_elt(&mut state, "type", "reward"));
+				try!(serializer.serialize_struct_elt(&mut state, "action", reward));
+			},
 		}
 
 		match self.result {
@@ -473,8 +550,8 @@ impl From<EthLocalizedTrace> for LocalizedTrace {
 			result: t.result.into(),
 			trace_address: t.trace_address.into_iter().map(Into::into).collect(),
 			subtraces: t.subtraces.into(),
-			transaction_position: t.transaction_number.into(),
-			transaction_hash: t.transaction_hash.into(),
+			transaction_position: t.transaction_number,
+			transaction_hash: t.transaction_hash.map(Into::into),
 			block_number: t.block_number.into(),
 			block_hash: t.block_hash.into(),
 		}
@@ -512,6 +589,10 @@ impl Serialize for Trace {
 				try!(serializer.serialize_struct_elt(&mut state, "type", "suicide"));
 				try!(serializer.serialize_struct_elt(&mut state, "action", suicide));
 			},
+			Action::Reward(ref reward) => {
+				try!(serializer.serialize_struct_elt(&mut state, "type", "reward"));
+				try!(serializer.serialize_struct_elt(&mut state, "action", reward));
+			},
 		}
 
 		match self.result {
@@ -540,6 +621,62 @@ impl From<FlatTrace> for Trace {
 	}
 }
 
+/// A frame of a nested call tree.
+#[derive(Debug, Serialize)]
+pub struct CallFrame {
+	/// Type of the frame, e.g. `CALL` or `CREATE`.
+	#[serde(rename="type")]
+	pub frame_type: String,
+	/// The sending account.
+	pub from: H160,
+	/// The destination account.
+	#[serde(skip_serializing_if="Option::is_none")]
+	pub to: Option<H160>,
+	/// The value transferred.
+	pub value: U256,
+	/// The gas available.
+	pub gas: U256,
+	/// The gas used.
+	#[serde(rename="gasUsed")]
+	pub gas_used: U256,
+	/// The input data or init code.
+	pub input: Bytes,
+	/// The output, deployed code or revert data.
+	pub output: Bytes,
+	/// The error which halted the frame.
+	#[serde(skip_serializing_if="Option::is_none")]
+	pub error: Option<String>,
+	/// Frames spawned by this frame.
+	#[serde(skip_serializing_if="Vec::is_empty")]
+	pub calls: Vec<CallFrame>,
+}
+
+impl From<et::CallFrame> for CallFrame {
+	fn from(f: et::CallFrame) -> Self {
+		let frame_type = match f.kind {
+			et::FrameKind::Call(executed::CallType::CallCode) => "CALLCODE",
+			et::FrameKind::Call(executed::CallType::DelegateCall) => "DELEGATECALL",
+			et::FrameKind::Call(executed::CallType::StaticCall) => "STATICCALL",
+			et::FrameKind::Call(_) => "CALL",
+			et::FrameKind::Create(trace::CreateType::Create) => "CREATE",
+			et::FrameKind::Create(trace::CreateType::Create2) => "CREATE2",
+			et::FrameKind::Suicide => "SELFDESTRUCT",
+		};
+		CallFrame {
+			frame_type: frame_type.into(),
+			from: f.from.into(),
+			to: f.to.map(Into::into),
+			value: f.value.into(),
+			gas: f.gas.into(),
+			gas_used: f.gas_used.into(),
+			input: f.input.into(),
+			output: f.output.into(),
+			error: f.error.map(|e| e.to_string()),
+			calls: f.calls.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
 #[derive(Debug, Serialize)]
 /// A diff of some chunk of memory.
 pub struct TraceResults {
@@ -553,6 +690,10 @@ pub struct TraceResults {
 	/// The transaction trace.
 	#[serde(rename="stateDiff")]
 	pub state_diff: Option<StateDiff>,
+	/// The nested call tree.
+	#[serde(rename="callTrace")]
+	#[serde(skip_serializing_if="Option::is_none")]
+	pub call_trace: Option<CallFrame>,
 }
 
 impl From<Executed> for TraceResults {
@@ -562,6 +703,7 @@ impl From<Executed> for TraceResults {
 			trace: t.trace.into_iter().map(Into::into).collect(),
 			vm_trace: t.vm_trace.map(Into::into),
 			state_diff: t.state_diff.map(Into::into),
+			call_trace: t.call_trace.map(Into::into),
 		}
 	}
 }
@@ -581,6 +723,7 @@ mod tests {
 			trace: vec![],
 			vm_trace: None,
 			state_diff: None,
+			call_trace: None,
 		};
 		let serialized = serde_json::to_string(&r).unwrap();
 		assert_eq!(serialized, r#"{"output":"0x60","trace":[],"vmTrace":null,"stateDiff":null}"#);
@@ -603,8 +746,8 @@ mod tests {
 			}),
 			trace_address: vec![10],
 			subtraces: 1,
-			transaction_position: 11,
-			transaction_hash: 12.into(),
+			transaction_position: Some(11),
+			transaction_hash: Some(12.into()),
 			block_number: 13,
 			block_hash: 14.into(),
 		};
@@ -626,8 +769,8 @@ mod tests {
 			result: Res::FailedCall(TraceError::OutOfGas),
 			trace_address: vec![10],
 			subtraces: 1,
-			transaction_position: 11,
-			transaction_hash: 12.into(),
+			transaction_position: Some(11),
+			transaction_hash: Some(12.into()),
 			block_number: 13,
 			block_hash: 14.into(),
 		};
@@ -643,6 +786,7 @@ mod tests {
 				value: 6.into(),
 				gas: 7.into(),
 				init: Bytes::new(vec![0x12, 0x34]),
+				create_type: CreateType::Create,
 			}),
 			result: Res::Create(CreateResult {
 				gas_used: 8.into(),
@@ -651,13 +795,13 @@ mod tests {
 			}),
 			trace_address: vec![10],
 			subtraces: 1,
-			transaction_position: 11,
-			transaction_hash: 12.into(),
+			transaction_position: Some(11),
+			transaction_hash: Some(12.into()),
 			block_number: 13,
 			block_hash: 14.into(),
 		};
 		let serialized = serde_json::to_string(&t).unwrap();
-		assert_eq!(serialized, r#"{"type":"create","action":{"from":"0x0000000000000000000000000000000000000004","value":"0x6","gas":"0x7","init":"0x1234"},"result":{"gasUsed":"0x8","code":"0x5678","address":"0x00000000000000000000000000000000000000ff"},"traceAddress":[10],"subtraces":1,"transactionPosition":11,"transactionHash":"0x000000000000000000000000000000000000000000000000000000000000000c","blockNumber":13,"blockHash":"0x000000000000000000000000000000000000000000000000000000000000000e"}"#);
+		assert_eq!(serialized, r#"{"type":"create","action":{"from":"0x0000000000000000000000000000000000000004","value":"0x6","gas":"0x7","init":"0x1234","createType":"create"},"result":{"gasUsed":"0x8","code":"0x5678","address":"0x00000000000000000000000000000000000000ff"},"traceAddress":[10],"subtraces":1,"transactionPosition":11,"transactionHash":"0x000000000000000000000000000000000000000000000000000000000000000c","blockNumber":13,"blockHash":"0x000000000000000000000000000000000000000000000000000000000000000e"}"#);
 	}
 
 	#[test]
@@ -668,17 +812,18 @@ mod tests {
 				value: 6.into(),
 				gas: 7.into(),
 				init: Bytes::new(vec![0x12, 0x34]),
+				create_type: CreateType::Create,
 			}),
 			result: Res::FailedCreate(TraceError::OutOfGas),
 			trace_address: vec![10],
 			subtraces: 1,
-			transaction_position: 11,
-			transaction_hash: 12.into(),
+			transaction_position: Some(11),
+			transaction_hash: Some(12.into()),
 			block_number: 13,
 			block_hash: 14.into(),
 		};
 		let serialized = serde_json::to_string(&t).unwrap();
-		assert_eq!(serialized, r#"{"type":"create","action":{"from":"0x0000000000000000000000000000000000000004","value":"0x6","gas":"0x7","init":"0x1234"},"error":"Out of gas","traceAddress":[10],"subtraces":1,"transactionPosition":11,"transactionHash":"0x000000000000000000000000000000000000000000000000000000000000000c","blockNumber":13,"blockHash":"0x000000000000000000000000000000000000000000000000000000000000000e"}"#);
+		assert_eq!(serialized, r#"{"type":"create","action":{"from":"0x0000000000000000000000000000000000000004","value":"0x6","gas":"0x7","init":"0x1234","createType":"create"},"error":"Out of gas","traceAddress":[10],"subtraces":1,"transactionPosition":11,"transactionHash":"0x000000000000000000000000000000000000000000000000000000000000000c","blockNumber":13,"blockHash":"0x000000000000000000000000000000000000000000000000000000000000000e"}"#);
 	}
 
 	#[test]
@@ -692,8 +837,8 @@ mod tests {
 			result: Res::None,
 			trace_address: vec![10],
 			subtraces: 1,
-			transaction_position: 11,
-			transaction_hash: 12.into(),
+			transaction_position: Some(11),
+			transaction_hash: Some(12.into()),
 			block_number: 13,
 			block_hash: 14.into(),
 		};
@@ -701,6 +846,26 @@ mod tests {
 		assert_eq!(serialized, r#"{"type":"suicide","action":{"address":"0x0000000000000000000000000000000000000004","refundAddress":"0x0000000000000000000000000000000000000006","balance":"0x7"},"result":null,"traceAddress":[10],"subtraces":1,"transactionPosition":11,"transactionHash":"0x000000000000000000000000000000000000000000000000000000000000000c","blockNumber":13,"blockHash":"0x000000000000000000000000000000000000000000000000000000000000000e"}"#);
 	}
 
+	#[test]
+	fn test_trace_reward_serialize() {
+		let t = LocalizedTrace {
+			action: Action::Reward(Reward {
+				author: 4.into(),
+				value: 6.into(),
+				reward_type: RewardType::Block,
+			}),
+			result: Res::None,
+			trace_address: vec![],
+			subtraces: 0,
+			transaction_position: None,
+			transaction_hash: None,
+			block_number: 13,
+			block_hash: 14.into(),
+		};
+		let serialized = serde_json::to_string(&t).unwrap();
+		assert_eq!(serialized, r#"{"type":"reward","action":{"author":"0x0000000000000000000000000000000000000004","value":"0x6","rewardType":"block"},"result":null,"traceAddress":[],"subtraces":0,"transactionPosition":null,"transactionHash":null,"blockNumber":13,"blockHash":"0x000000000000000000000000000000000000000000000000000000000000000e"}"#);
+	}
+
 	#[test]
 	fn test_vmtrace_serialize() {
 		let t = VMTrace {
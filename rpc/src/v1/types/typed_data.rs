@@ -0,0 +1,146 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+
+//! EIP-712 typed structured data.
+
+use std::collections::BTreeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, Error};
+use jsonrpc_core::Value;
+
+/// JSON value of a typed data field. Floating point numbers are not allowed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TypedValue {
+	/// Null
+	Null,
+	/// Boolean
+	Bool(bool),
+	/// Negative integer
+	I64(i64),
+	/// Non-negative integer
+	U64(u64),
+	/// String
+	String(String),
+	/// Array
+	Array(Vec<TypedValue>),
+	/// Object
+	Object(BTreeMap<String, TypedValue>),
+}
+
+impl TypedValue {
+	fn from_json(value: Value) -> Result<TypedValue, &'static str> {
+		Ok(match value {
+			Value::Null => TypedValue::Null,
+			Value::Bool(b) => TypedValue::Bool(b),
+			Value::I64(i) => TypedValue::I64(i),
+			Value::U64(u) => TypedValue::U64(u),
+			Value::F64(_) => return Err("floating point numbers are not allowed in typed data"),
+			Value::String(s) => TypedValue::String(s),
+			Value::Array(a) => TypedValue::Array(try!(a.into_iter().map(TypedValue::from_json).collect())),
+			Value::Object(o) => TypedValue::Object(try!(o.into_iter()
+				.map(|(k, v)| TypedValue::from_json(v).map(|v| (k, v)))
+				.collect())),
+		})
+	}
+}
+
+impl Into<Value> for TypedValue {
+	fn into(self) -> Value {
+		match self {
+			TypedValue::Null => Value::Null,
+			TypedValue::Bool(b) => Value::Bool(b),
+			TypedValue::I64(i) => Value::I64(i),
+			TypedValue::U64(u) => Value::U64(u),
+			TypedValue::String(s) => Value::String(s),
+			TypedValue::Array(a) => Value::Array(a.into_iter().map(Into::into).collect()),
+			TypedValue::Object(o) => Value::Object(o.into_iter().map(|(k, v)| (k, v.into())).collect()),
+		}
+	}
+}
+
+impl Serialize for TypedValue {
+	fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+	where S: Serializer {
+		let value: Value = self.clone().into();
+		value.serialize(serializer)
+	}
+}
+
+impl Deserialize for TypedValue {
+	fn deserialize<D>(deserializer: &mut D) -> Result<TypedValue, D::Error>
+	where D: Deserializer {
+		let value = try!(Value::deserialize(deserializer));
+		TypedValue::from_json(value).map_err(|e| D::Error::custom(e))
+	}
+}
+
+/// Member of a struct type.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MemberType {
+	/// Name of the member
+	pub name: String,
+	/// Type of the member
+	#[serde(rename="type")]
+	pub kind: String,
+}
+
+/// Typed structured data to sign, as defined by EIP-712.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TypedData {
+	/// Struct types by name, including `EIP712Domain`
+	pub types: BTreeMap<String, Vec<MemberType>>,
+	/// Type of the message
+	#[serde(rename="primaryType")]
+	pub primary_type: String,
+	/// Signing domain, of type `EIP712Domain`
+	pub domain: TypedValue,
+	/// Message, of type `primaryType`
+	pub message: TypedValue,
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use super::{TypedData, TypedValue};
+
+	#[test]
+	fn typed_data_deserialize() {
+		let s = r#"{
+			"types": {
+				"EIP712Domain": [{"name": "name", "type": "string"}],
+				"Message": [{"name": "value", "type": "int8"}]
+			},
+			"primaryType": "Message",
+			"domain": {"name": "Test"},
+			"message": {"value": -1}
+		}"#;
+		let deserialized: TypedData = serde_json::from_str(s).unwrap();
+
+		assert_eq!(deserialized.primary_type, "Message".to_owned());
+		assert_eq!(deserialized.types["Message"][0].kind, "int8".to_owned());
+		assert_eq!(deserialized.domain, TypedValue::Object(vec![("name".to_owned(), TypedValue::String("Test".into()))].into_iter().collect()));
+		assert_eq!(deserialized.message, TypedValue::Object(vec![("value".to_owned(), TypedValue::I64(-1))].into_iter().collect()));
+		assert_eq!(serde_json::to_string(&deserialized.message).unwrap(), r#"{"value":-1}"#);
+	}
+
+	#[test]
+	fn typed_value_rejects_floats() {
+		let deserialized: Result<TypedValue, _> = serde_json::from_str(r#"{"value": 1.5}"#);
+		assert!(deserialized.is_err());
+	}
+}
@@ -0,0 +1,205 @@
+// Copyright 2015, 2016 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! JSON-RPC over `WebSockets` server.
+
+use std;
+use std::thread;
+use std::sync::Arc;
+use std::net::SocketAddr;
+use io::PanicHandler;
+use jsonrpc_core::{IoHandler, GenericIoHandler};
+use util::Mutex;
+use ws;
+use v1::pubsub::{self, ConnectionId, Sink, SubscriptionManager};
+
+/// `WebSockets` server startup error
+#[derive(Debug)]
+pub enum ServerError {
+	/// Wrapped `std::io::Error`
+	IoError(std::io::Error),
+	/// Other `ws-rs` error
+	WebSocket(ws::Error)
+}
+
+impl From<ws::Error> for ServerError {
+	fn from(err: ws::Error) -> Self {
+		match err.kind {
+			ws::ErrorKind::Io(e) => ServerError::IoError(e),
+			_ => ServerError::WebSocket(err),
+		}
+	}
+}
+
+struct WsSink(Mutex<ws::Sender>);
+
+impl Sink for WsSink {
+	fn send(&self, notification: String) {
+		if let Err(e) = self.0.lock().send(notification) {
+			warn!(target: "rpc", "Error while sending notification: {:?}", e);
+		}
+	}
+}
+
+struct Session {
+	id: ConnectionId,
+	out: ws::Sender,
+	allowed_origins: Arc<Option<Vec<String>>>,
+	handler: Arc<IoHandler>,
+	subscription_handler: IoHandler,
+	subscriptions: Arc<SubscriptionManager>,
+}
+
+/// Checks the `Origin` header value against the allow-list (`None` allows every origin).
+fn origin_is_allowed(allowed_origins: &Option<Vec<String>>, origin: &[u8]) -> bool {
+	match *allowed_origins {
+		None => true,
+		Some(ref origins) => origins.iter().any(|allowed| allowed.as_bytes() == origin),
+	}
+}
+
+impl ws::Handler for Session {
+	fn on_request(&mut self, req: &ws::Request) -> ws::Result<ws::Response> {
+		// Only browsers send the origin, other clients are not restricted by it.
+		if let Some(origin) = req.header("origin").or_else(|| req.header("Origin")) {
+			if !origin_is_allowed(&self.allowed_origins, origin) {
+				warn!(target: "rpc", "Blocked WebSockets connection from untrusted origin: {}", String::from_utf8_lossy(origin));
+				return Ok(ws::Response::forbidden("Origin is not allowed. Use --ws-origins to allow it.".into()));
+			}
+		}
+
+		ws::Response::from_request(req)
+	}
+
+	fn on_message(&mut self, msg: ws::Message) -> ws::Result<()> {
+		let req = try!(msg.as_text());
+		let out = self.out.clone();
+		let respond = move |response: Option<String>| {
+			if let Some(result) = response {
+				if let Err(e) = out.send(result) {
+					warn!(target: "rpc", "Error while sending response: {:?}", e);
+				}
+			}
+		};
+
+		match pubsub::route(req) {
+			pubsub::Route::Subscription => self.subscription_handler.handle_request(req, respond),
+			pubsub::Route::BatchedSubscription => respond(Some(pubsub::BATCHED_SUBSCRIPTION_ERROR.into())),
+			pubsub::Route::Regular => self.handler.handle_request(req, respond),
+		}
+		Ok(())
+	}
+}
+
+impl Drop for Session {
+	fn drop(&mut self) {
+		// the session is dropped however the connection ends, so it's the right place to clean up.
+		self.subscriptions.drop_connection(self.id);
+	}
+}
+
+struct Factory {
+	allowed_origins: Arc<Option<Vec<String>>>,
+	handler: Arc<IoHandler>,
+	subscriptions: Arc<SubscriptionManager>,
+	next_id: ConnectionId,
+}
+
+impl ws::Factory for Factory {
+	type Handler = Session;
+
+	fn connection_made(&mut self, sender: ws::Sender) -> Self::Handler {
+		self.next_id += 1;
+		let sink = Arc::new(WsSink(Mutex::new(sender.clone())));
+		Session {
+			id: self.next_id,
+			out: sender,
+			allowed_origins: self.allowed_origins.clone(),
+			handler: self.handler.clone(),
+			subscription_handler: self.subscriptions.connection_handler(self.next_id, sink),
+			subscriptions: self.subscriptions.clone(),
+		}
+	}
+}
+
+/// `WebSockets` server implementation.
+pub struct Server {
+	handle: Option<thread::JoinHandle<()>>,
+	broadcaster: ws::Sender,
+	addr: SocketAddr,
+}
+
+impl Server {
+	/// Returns the address this server is listening on
+	pub fn addr(&self) -> &SocketAddr {
+		&self.addr
+	}
+
+	/// Starts a new `WebSocket` server in separate thread.
+	/// Handshakes from origins not in `allowed_origins` are rejected, `None` allows all of them.
+	/// Returns a `Server` handle which closes the server when droped.
+	pub fn start(
+		addr: &SocketAddr,
+		allowed_origins: Option<Vec<String>>,
+		handler: Arc<IoHandler>,
+		subscriptions: Arc<SubscriptionManager>,
+		panic_handler: Arc<PanicHandler>,
+	) -> Result<Server, ServerError> {
+		let config = {
+			let mut config = ws::Settings::default();
+			// accept only handshakes beginning with GET
+			config.method_strict = true;
+			// Was shutting down server when suspending on linux:
+			config.shutdown_on_interrupt = false;
+			config
+		};
+
+		let ws = try!(ws::Builder::new().with_settings(config).build(Factory {
+			allowed_origins: Arc::new(allowed_origins),
+			handler: handler,
+			subscriptions: subscriptions,
+			next_id: 0,
+		}));
+		let broadcaster = ws.broadcaster();
+
+		// Spawn a thread with event loop
+		let listen_addr = addr.clone();
+		let handle = thread::spawn(move || {
+			let ph = panic_handler.clone();
+			ph.catch_panic(move || {
+				if let Err(e) = ws.listen(listen_addr).map_err(ServerError::from) {
+					warn!(target: "rpc", "WebSockets RPC server could not listen on {}: {:?}", listen_addr, e);
+					panic_handler.notify_all(format!("WebSockets RPC server error: {:?}", e));
+				}
+			}).unwrap()
+		});
+
+		Ok(Server {
+			handle: Some(handle),
+			broadcaster: broadcaster,
+			addr: addr.clone(),
+		})
+	}
+}
+
+impl Drop for Server {
+	fn drop(&mut self) {
+		if let Err(e) = self.broadcaster.shutdown() {
+			warn!(target: "rpc", "WebSockets RPC server was not closed cleanly. Details: {:?}", e);
+		}
+		self.handle.take().unwrap().join().unwrap();
+	}
+}